@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::paths::home_dir;
+use crate::config::profiles::Profile;
+use crate::config::Config;
+
+// ~/.my.cnf 的 [client] 段（[mysql] 段同名项覆盖），作为 mysql 连接的最低优先级配置
+pub fn my_cnf() -> Option<Profile> {
+    let raw = fs::read_to_string(home_dir().join(".my.cnf")).ok()?;
+    let mut profile = Profile::default();
+    let mut in_section = false;
+    for line in raw.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            let name = line[1..line.len() - 1].trim().to_ascii_lowercase();
+            in_section = name == "client" || name == "mysql";
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        // 选项名中 - 与 _ 等价；值可以带引号
+        let key = key.trim().replace('-', "_").to_ascii_lowercase();
+        let value = unquote(value.trim());
+        match key.as_str() {
+            "user" => profile.username = Some(value),
+            "password" => profile.password = Some(value),
+            "host" => profile.host = Some(value),
+            "port" => profile.port = value.parse().ok(),
+            "socket" => profile.socket = Some(PathBuf::from(value)),
+            "database" => profile.database = Some(value),
+            "default_character_set" => profile.charset = Some(value),
+            _ => {}
+        }
+    }
+    Some(profile)
+}
+
+fn unquote(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    value.to_string()
+}
+
+// 按 libpq 规则在 $PGPASSFILE 或 ~/.pgpass 中查找密码：
+// 每行 hostname:port:database:username:password，* 匹配任意值，\: 与 \\ 为转义
+pub fn pgpass(config: &Config) -> Option<String> {
+    let path = std::env::var_os("PGPASSFILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir().join(".pgpass"));
+    let raw = fs::read_to_string(path).ok()?;
+    let port = config.port().to_string();
+    let wanted = [
+        config.host.as_str(),
+        port.as_str(),
+        config.database.as_deref().unwrap_or(""),
+        config.username.as_str(),
+    ];
+    raw.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .map(split_pgpass_line)
+        .filter(|fields| fields.len() == 5)
+        .find(|fields| fields.iter().zip(wanted).all(|(field, value)| field == "*" || field == value))
+        .map(|mut fields| fields.pop().unwrap_or_default())
+}
+
+fn split_pgpass_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    fields.last_mut().unwrap().push(next);
+                }
+            }
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
@@ -0,0 +1,38 @@
+use anyhow::{anyhow, Result};
+use keyring::Entry;
+
+use crate::config::Config;
+use crate::t;
+
+// 系统密钥环（macOS 钥匙串 / Windows 凭据管理器 / Secret Service）中的服务名
+const SERVICE: &str = "sqltui";
+
+// 条目键：优先使用 --profile 名称，否则为 driver://user@host:port
+fn entry_key(config: &Config) -> String {
+    match config.profile.as_deref() {
+        Some(profile) => format!("profile:{}", profile),
+        None => format!("{}://{}@{}:{}", config.driver, config.username, config.host, config.port()),
+    }
+}
+
+// 读取已保存的密码；密钥环不可用（如无桌面会话的服务器）时视为没有保存
+pub fn load(config: &Config) -> Option<String> {
+    Entry::new(SERVICE, &entry_key(config))
+        .and_then(|entry| entry.get_password())
+        .ok()
+}
+
+pub fn save(config: &Config) -> Result<()> {
+    store(&entry_key(config), &config.password)
+}
+
+// 按连接配置名保存，与以 --profile 连接时读取的条目相同
+pub fn save_for_profile(profile: &str, password: &str) -> Result<()> {
+    store(&format!("profile:{}", profile), password)
+}
+
+fn store(key: &str, password: &str) -> Result<()> {
+    Entry::new(SERVICE, key)
+        .and_then(|entry| entry.set_password(password))
+        .map_err(|e| anyhow!(t!("credentials.save_failed", key, e)))
+}
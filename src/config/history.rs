@@ -0,0 +1,99 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{paths::data_dir, Config};
+
+// 保留的历史条数（超出后丢弃最早的）
+pub const MAX_ENTRIES: usize = 1000;
+
+// SQL 模式的历史文件。每条以 "#" 行开头，内容逐行加 "+" 前缀，多行语句可原样还原
+// （与 mycli/pgcli 使用的 prompt_toolkit 格式兼容）。
+// --history-file 优先；否则按驱动 + 主机（文件型数据库为路径）+ 连接时的数据库分文件保存，
+// 如 ~/.local/share/sqltui/history-mysql@db.prod@shop，生产与测试环境的历史互不混杂
+pub fn path(config: &Config) -> PathBuf {
+    if let Some(path) = &config.history_file {
+        return path.clone();
+    }
+    let server = match &config.path {
+        Some(path) => path.display().to_string(),
+        None => config.host.clone(),
+    };
+    let key = format!("{}@{}@{}", config.driver, server, config.database.as_deref().unwrap_or(""));
+    let key: String = key
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | '@') { c } else { '_' })
+        .collect();
+    data_dir().join(format!("history-{}", key))
+}
+
+// 按连接分文件之前所有连接共用的历史文件；连接的历史文件尚不存在时从中载入
+fn legacy_path() -> PathBuf {
+    data_dir().join("history")
+}
+
+// 载入连接的历史；首次使用某个连接时沿用旧的共用历史，避免升级后丢失
+pub fn load_for(path: &Path) -> Vec<String> {
+    if path.exists() {
+        load(path)
+    } else {
+        load(&legacy_path())
+    }
+}
+
+// 文件不存在或无法读取时返回空历史，不影响启动
+pub fn load(path: &Path) -> Vec<String> {
+    let Ok(raw) = fs::read_to_string(path) else { return Vec::new(); };
+    let mut entries = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in raw.lines() {
+        if let Some(text) = line.strip_prefix('+') {
+            current.push(text);
+        } else if !current.is_empty() {
+            entries.push(current.join("\n"));
+            current.clear();
+        }
+    }
+    if !current.is_empty() {
+        entries.push(current.join("\n"));
+    }
+    entries
+}
+
+// 追加本次会话的新命令后写回：先重新读取文件，保留其他同时运行的会话写入的条目；
+// 相同的命令只保留最近一次，最多保留 MAX_ENTRIES 条
+pub fn append(path: &Path, new_entries: &[String]) -> Result<()> {
+    if new_entries.is_empty() {
+        return Ok(());
+    }
+    let mut entries = load_for(path);
+    entries.extend(new_entries.iter().cloned());
+    let entries = dedup_recent(entries);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut out = String::new();
+    for entry in &entries {
+        out.push_str("#\n");
+        for line in entry.lines() {
+            out.push('+');
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    fs::write(path, out)?;
+    Ok(())
+}
+
+// 去掉重复与空白命令（保留最后一次出现的位置），截取最近的 MAX_ENTRIES 条
+fn dedup_recent(entries: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut kept: Vec<String> = entries
+        .into_iter()
+        .rev()
+        .filter(|e| !e.trim().is_empty() && seen.insert(e.clone()))
+        .take(MAX_ENTRIES)
+        .collect();
+    kept.reverse();
+    kept
+}
@@ -0,0 +1,336 @@
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+use crate::t;
+
+// 命令模式（浏览侧边栏与结果）下可重新绑定的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // 在根目录退出，其他情况下返回上一级
+    Quit,
+    // 任意位置直接退出（仍会确认）
+    ForceQuit,
+    Back,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    PrevPage,
+    NextPage,
+    Open,
+    Browse,
+    SqlMode,
+    Search,
+    SearchNext,
+    SearchPrev,
+    History,
+    Help,
+    Logs,
+    ToggleSidebar,
+    Snippets,
+    DatabaseDetail,
+    TableDetail,
+    SwitchDatabase,
+    Dedup,
+    Distinct,
+    Stats,
+    RelativeTime,
+    CellDetail,
+    NarrowColumn,
+    WidenColumn,
+    PrimaryKeyOrder,
+    RowActions,
+    YankCell,
+    YankRow,
+    YankResult,
+    YankMarkdown,
+    CopyInsert,
+    Export,
+}
+
+// config.toml [keys] 中的操作名与默认按键
+const DEFAULTS: &[(&str, Action, &[&str])] = &[
+    ("quit", Action::Quit, &["q"]),
+    ("force_quit", Action::ForceQuit, &["ctrl-c"]),
+    ("back", Action::Back, &["esc"]),
+    ("up", Action::Up, &["up"]),
+    ("down", Action::Down, &["down"]),
+    ("left", Action::Left, &["left"]),
+    ("right", Action::Right, &["right"]),
+    ("page_up", Action::PageUp, &["pageup"]),
+    ("page_down", Action::PageDown, &["pagedown"]),
+    ("top", Action::Top, &["home"]),
+    ("bottom", Action::Bottom, &["end"]),
+    ("prev_page", Action::PrevPage, &["["]),
+    ("next_page", Action::NextPage, &["]"]),
+    ("open", Action::Open, &["enter"]),
+    ("browse", Action::Browse, &["space"]),
+    ("sql_mode", Action::SqlMode, &[":"]),
+    ("search", Action::Search, &["/"]),
+    ("search_next", Action::SearchNext, &["n"]),
+    ("search_prev", Action::SearchPrev, &["N"]),
+    ("history", Action::History, &["f2"]),
+    ("help", Action::Help, &["?", "f1"]),
+    ("logs", Action::Logs, &["~"]),
+    ("toggle_sidebar", Action::ToggleSidebar, &["ctrl-b"]),
+    ("snippets", Action::Snippets, &["S"]),
+    ("database_detail", Action::DatabaseDetail, &["d"]),
+    ("table_detail", Action::TableDetail, &["t"]),
+    ("switch_database", Action::SwitchDatabase, &["s"]),
+    ("dedup", Action::Dedup, &["u"]),
+    ("distinct", Action::Distinct, &["v"]),
+    ("stats", Action::Stats, &["i"]),
+    ("relative_time", Action::RelativeTime, &["r"]),
+    ("cell_detail", Action::CellDetail, &["o"]),
+    ("narrow_column", Action::NarrowColumn, &["<"]),
+    ("widen_column", Action::WidenColumn, &[">"]),
+    ("primary_key_order", Action::PrimaryKeyOrder, &["O"]),
+    ("row_actions", Action::RowActions, &["x"]),
+    ("yank_cell", Action::YankCell, &["y"]),
+    ("yank_row", Action::YankRow, &["Y"]),
+    ("yank_result", Action::YankResult, &["ctrl-y"]),
+    ("yank_markdown", Action::YankMarkdown, &["alt-y"]),
+    ("copy_insert", Action::CopyInsert, &["I"]),
+    ("export", Action::Export, &["e"]),
+];
+
+// 一个按键：只区分 Ctrl 与 Alt，Shift 已体现在字符的大小写中
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn from_event(event: &KeyEvent) -> Self {
+        let modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let code = match event.code {
+            // 部分终端在 Ctrl 组合下上报大写字母
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => KeyCode::Char(c.to_ascii_lowercase()),
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    // 解析 "q"、"Y"、"esc"、"ctrl-c"、"alt-y"、"shift-tab"、"f2" 等写法（名称不区分大小写）
+    fn parse(spec: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        let mut rest = spec;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let Some(prefix) = ["ctrl-", "alt-", "shift-"].into_iter().find(|p| lower.starts_with(*p) && rest.len() > p.len()) else {
+                break;
+            };
+            match prefix {
+                "ctrl-" => modifiers |= KeyModifiers::CONTROL,
+                "alt-" => modifiers |= KeyModifiers::ALT,
+                _ => shift = true,
+            }
+            rest = &rest[prefix.len()..];
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::CONTROL) => KeyCode::Char(c.to_ascii_lowercase()),
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "space" => KeyCode::Char(' '),
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(anyhow!(t!("keymap.unknown_key", spec))),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    // 帮助中显示的写法，如 "q"、"Esc"、"Ctrl+Y"、"Space"
+    fn label(&self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        match self.code {
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) if self.modifiers.is_empty() => label.push(c),
+            KeyCode::Char(c) => label.push(c.to_ascii_uppercase()),
+            KeyCode::Esc => label.push_str("Esc"),
+            KeyCode::Enter => label.push_str("Enter"),
+            KeyCode::Tab => label.push_str("Tab"),
+            KeyCode::BackTab => label.push_str("Shift+Tab"),
+            KeyCode::Backspace => label.push_str("Backspace"),
+            KeyCode::PageUp => label.push_str("PgUp"),
+            KeyCode::PageDown => label.push_str("PgDn"),
+            KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+            code => label.push_str(&format!("{:?}", code)),
+        }
+        label
+    }
+
+    // 不带 Ctrl/Alt 的字符键：在文本输入中会被当作输入
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+    }
+}
+
+// vim 风格的附加按键（config.toml 中 vim_keys = true 时启用）；"g g" 为依次按下的按键序列
+const VIM_DEFAULTS: &[(&str, Action, &[&str])] = &[
+    ("up", Action::Up, &["k"]),
+    ("down", Action::Down, &["j"]),
+    ("left", Action::Left, &["h"]),
+    ("right", Action::Right, &["l"]),
+    ("page_up", Action::PageUp, &["ctrl-u"]),
+    ("page_down", Action::PageDown, &["ctrl-d", "ctrl-f"]),
+    ("top", Action::Top, &["g g"]),
+    ("bottom", Action::Bottom, &["G"]),
+];
+
+// 按空白分隔的按键序列，如 "g g"
+fn parse_sequence(spec: &str) -> Result<Vec<Key>> {
+    let keys = spec.split_whitespace().map(Key::parse).collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(anyhow!(t!("keymap.empty")));
+    }
+    Ok(keys)
+}
+
+// 按键到操作的映射：config.toml 的 [keys] 覆盖同名操作的默认按键，
+// 如 quit = ["esc"]、back = ["q"] 即互换 q 与 Esc；同一按键以配置中的绑定为准
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Vec<Key>, Action)>,
+    // 已按下、可能是某个按键序列开头的按键
+    pending: Vec<Key>,
+}
+
+impl Keymap {
+    pub fn new(overrides: &BTreeMap<String, Vec<String>>, vim: bool) -> Result<Self> {
+        let mut bindings: Vec<(Vec<Key>, Action)> = Vec::new();
+        let mut errors = Vec::new();
+        for (name, keys) in overrides {
+            let Some(&(_, action, _)) = DEFAULTS.iter().find(|(n, _, _)| *n == name.as_str()) else {
+                errors.push(t!("keymap.unknown_action", name));
+                continue;
+            };
+            for spec in keys {
+                match parse_sequence(spec) {
+                    Ok(keys) => bindings.push((keys, action)),
+                    Err(e) => errors.push(format!("keys.{}: {}", name, e)),
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(anyhow!(errors.join("\n  - ")));
+        }
+        let presets = if vim { [DEFAULTS, VIM_DEFAULTS].concat() } else { DEFAULTS.to_vec() };
+        for (name, action, keys) in presets {
+            if overrides.contains_key(name) {
+                continue;
+            }
+            for spec in keys.iter() {
+                let keys = parse_sequence(spec)?;
+                if !bindings.iter().any(|(bound, _)| *bound == keys) {
+                    bindings.push((keys, action));
+                }
+            }
+        }
+        Ok(Self { bindings, pending: Vec::new() })
+    }
+
+    // 按下一个键：完成某个绑定时返回其操作；是某个按键序列的开头时先记下，返回 None
+    pub fn action(&mut self, event: &KeyEvent) -> Option<Action> {
+        let key = Key::from_event(event);
+        self.pending.push(key);
+        loop {
+            if let Some(action) = self.lookup(&self.pending) {
+                self.pending.clear();
+                return Some(action);
+            }
+            if self.bindings.iter().any(|(bound, _)| bound.starts_with(&self.pending)) {
+                return None;
+            }
+            if self.pending.len() == 1 {
+                self.pending.clear();
+                return None;
+            }
+            // 未构成序列：丢弃之前的按键，从本次按键重新解释
+            self.pending = vec![key];
+        }
+    }
+
+    // 文本输入（SQL 模式等）中仍可触发的操作：绑定在功能键或 Ctrl/Alt 组合上的单个按键
+    pub fn global_action(&self, event: &KeyEvent) -> Option<Action> {
+        let key = [Key::from_event(event)];
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key && !key[0].is_text())
+            .map(|(_, action)| *action)
+    }
+
+    // 操作当前绑定的按键，如 "q"、"↑ / k"、"Home / gg"；未绑定时为 None
+    pub fn keys_label(&self, name: &str) -> Option<String> {
+        let &(_, action, _) = DEFAULTS.iter().find(|(n, _, _)| *n == name)?;
+        let labels: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(keys, _)| keys.iter().map(Key::label).collect::<Vec<_>>().join(""))
+            .collect();
+        (!labels.is_empty()).then(|| labels.join(" / "))
+    }
+
+    // 把文本中的 {操作名} 替换为当前绑定的按键，用于帮助等说明文字；不是操作名的 {…} 原样保留
+    pub fn expand(&self, text: &str) -> String {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let label = after
+                .find('}')
+                .filter(|&end| DEFAULTS.iter().any(|(n, _, _)| *n == &after[..end]))
+                .map(|end| (end, self.keys_label(&after[..end]).unwrap_or_else(|| "-".to_string())));
+            match label {
+                Some((end, label)) => {
+                    out.push_str(&label);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn lookup(&self, keys: &[Key]) -> Option<Action> {
+        self.bindings.iter().find(|(bound, _)| bound == keys).map(|(_, action)| *action)
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+// 用户主目录；取不到时退回当前目录
+pub fn home_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+// 配置目录：$SQLTUI_CONFIG_DIR > $XDG_CONFIG_HOME/sqltui > ~/.config/sqltui
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("SQLTUI_CONFIG_DIR") {
+        return PathBuf::from(dir);
+    }
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
+        if !xdg.is_empty() {
+            return PathBuf::from(xdg).join("sqltui");
+        }
+    }
+    home_dir().join(".config").join("sqltui")
+}
+
+// 数据目录（历史、视图偏好等运行时状态）：$XDG_DATA_HOME/sqltui > ~/.local/share/sqltui
+pub fn data_dir() -> PathBuf {
+    if let Some(xdg) = std::env::var_os("XDG_DATA_HOME") {
+        if !xdg.is_empty() {
+            return PathBuf::from(xdg).join("sqltui");
+        }
+    }
+    home_dir().join(".local").join("share").join("sqltui")
+}
@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use clap::ArgMatches;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::paths::{config_dir, data_dir};
+use crate::config::{credentials, from_cli, Config};
+use crate::t;
+
+// 命名连接配置 ~/.config/sqltui/connections.toml，每个表为一个配置：
+// [prod]
+// driver = "pgsql"
+// host = "db.internal"
+// user = "app"
+// database = "app"
+// ssh = "deploy@bastion"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Connections {
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+// 未填写的字段沿用命令行默认值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub driver: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    #[serde(alias = "user")]
+    pub username: Option<String>,
+    // 仅用于读取旧版本写入的明文密码，加载时移入系统密钥环，不再写回文件
+    #[serde(skip_serializing)]
+    pub password: Option<String>,
+    pub database: Option<String>,
+    pub path: Option<PathBuf>,
+    pub socket: Option<PathBuf>,
+    pub service: Option<String>,
+    pub schema: Option<String>,
+    pub charset: Option<String>,
+    pub collation: Option<String>,
+    pub tls: Option<bool>,
+    pub tls_ca: Option<PathBuf>,
+    pub tls_insecure: Option<bool>,
+    pub ssl_mode: Option<String>,
+    pub ssl_ca: Option<PathBuf>,
+    pub ssl_cert: Option<PathBuf>,
+    pub ssl_key: Option<PathBuf>,
+    pub ssh: Option<String>,
+    pub ssh_key: Option<PathBuf>,
+    pub read_only: Option<bool>,
+    pub timeout: Option<u64>,
+    pub init_sql: Option<Vec<String>>,
+}
+
+impl Connections {
+    pub fn path() -> PathBuf {
+        config_dir().join("connections.toml")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path)?;
+        let mut connections: Self =
+            toml::from_str(&raw).map_err(|e| anyhow!(t!("profiles.parse_failed", path.display(), e)))?;
+        connections.migrate_passwords();
+        Ok(connections)
+    }
+
+    // 密码不保存在配置文件中：文件里的明文密码移入系统密钥环并从文件删除；
+    // 密钥环不可用时本次仍使用该密码，并提示手动删除
+    fn migrate_passwords(&mut self) {
+        let mut migrated = false;
+        for (name, profile) in self.profiles.iter_mut() {
+            let Some(password) = profile.password.as_deref() else { continue; };
+            match credentials::save_for_profile(name, password) {
+                Ok(()) => {
+                    tracing::warn!(profile = name.as_str(), "{}", t!("log.password_migrated"));
+                    profile.password = None;
+                    migrated = true;
+                }
+                Err(e) => tracing::warn!(profile = name.as_str(), error = %e, "{}", t!("log.password_plaintext")),
+            }
+        }
+        if migrated {
+            if let Err(e) = self.save() {
+                tracing::warn!(error = %e, "{}", t!("log.password_writeback_failed"));
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let names: Vec<&str> = self.profiles.keys().map(|k| k.as_str()).collect();
+            if names.is_empty() {
+                anyhow!(t!("profiles.missing_none", name, Self::path().display()))
+            } else {
+                anyhow!(t!("profiles.missing", name, names.join(", ")))
+            }
+        })
+    }
+}
+
+impl Profile {
+    // 合并到命令行解析结果：命令行显式给出的参数优先，其余取配置文件中的值
+    pub fn apply(&self, config: &mut Config, matches: &ArgMatches) {
+        self.merge_into(config, |id| from_cli(matches, id));
+    }
+
+    // 不考虑命令行，直接覆盖（连接管理界面中选择配置时使用）
+    pub fn apply_all(&self, config: &mut Config) {
+        self.merge_into(config, |_| false);
+    }
+
+    // 用连接表单中可编辑的字段更新配置，其余字段（TLS、SSH 等）保持不变；密码不写入文件
+    pub fn update_from(&mut self, config: &Config) {
+        let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+        self.driver = Some(config.driver.clone());
+        self.host = non_empty(&config.host);
+        self.port = config.port;
+        self.username = non_empty(&config.username);
+        self.database = config.database.as_deref().and_then(non_empty);
+        self.path = config.path.clone();
+        self.service = config.service.as_deref().and_then(non_empty);
+    }
+
+    fn merge_into(&self, config: &mut Config, keep: impl Fn(&str) -> bool) {
+        macro_rules! merge {
+            ($field:ident) => {
+                if !keep(stringify!($field)) {
+                    if let Some(v) = self.$field.clone() {
+                        config.$field = v;
+                    }
+                }
+            };
+            ($field:ident, optional) => {
+                if !keep(stringify!($field)) && self.$field.is_some() {
+                    config.$field = self.$field.clone();
+                }
+            };
+        }
+        merge!(driver);
+        merge!(host);
+        merge!(port, optional);
+        merge!(username);
+        merge!(password);
+        merge!(database, optional);
+        merge!(path, optional);
+        merge!(socket, optional);
+        merge!(service, optional);
+        merge!(schema, optional);
+        merge!(charset);
+        merge!(collation, optional);
+        merge!(tls);
+        merge!(tls_ca, optional);
+        merge!(tls_insecure);
+        merge!(ssl_mode, optional);
+        merge!(ssl_ca, optional);
+        merge!(ssl_cert, optional);
+        merge!(ssl_key, optional);
+        merge!(ssh, optional);
+        merge!(ssh_key, optional);
+        // 只读只会被配置开启：read_only = false 不关闭命令行 --read-only 或当前会话已开启的只读
+        if !keep("read_only") && self.read_only == Some(true) {
+            config.read_only = true;
+        }
+        merge!(timeout, optional);
+        merge!(init_sql);
+    }
+}
+
+// 各连接配置的最近使用时间（~/.local/share/sqltui/connections-used.toml），用于启动界面排序
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProfileUsage {
+    pub last_used: BTreeMap<String, DateTime<Local>>,
+}
+
+impl ProfileUsage {
+    pub fn path() -> PathBuf {
+        data_dir().join("connections-used.toml")
+    }
+
+    // 文件不存在或损坏时视为从未使用
+    pub fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|raw| toml::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    // 记录一次成功连接；写入失败不影响使用
+    pub fn touch(name: &str) {
+        let mut usage = Self::load();
+        usage.last_used.insert(name.to_string(), Local::now());
+        let _ = usage.save();
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::paths::config_dir;
+use crate::config::keymap::Keymap;
+use crate::config::DRIVERS;
+use crate::t;
+use crate::ui::theme::Theme;
+
+// 侧边栏宽度（百分比）的可调范围
+pub const SIDEBAR_WIDTH_RANGE: std::ops::RangeInclusive<u16> = 10..=80;
+
+// 通用设置文件 ~/.config/sqltui/config.toml；缺省字段使用默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // 结果行的外部命令，如 { name = "后台", command = "open https://admin.example.com/users/${id}" }
+    pub row_actions: Vec<RowAction>,
+    // 空闲时探测连接的间隔（秒），0 为关闭
+    pub ping_interval: u64,
+    // 浏览表数据（空格）时每页的行数
+    pub page_size: usize,
+    // SQL 模式下未写 LIMIT 的 SELECT 自动追加的行数限制，0 为关闭
+    pub default_limit: usize,
+    // SQL 模式的命令别名，如 { name = "ps", sql = "SHOW FULL PROCESSLIST", driver = "mysql" }
+    pub aliases: Vec<Alias>,
+    // 命令模式的按键绑定，操作名 -> 按键列表，覆盖该操作的默认按键，如 back = ["q"]
+    pub keys: BTreeMap<String, Vec<String>>,
+    // 启用 vim 风格按键：j/k/h/l 移动，gg/G 首/末行，Ctrl+U/Ctrl+D 翻页
+    pub vim_keys: bool,
+    // 界面配色：内置主题与逐项覆盖的颜色
+    pub theme: ThemeSettings,
+    // 鼠标操作（点击选择、滚轮滚动、拖动分隔线）；关闭后可用终端自身的鼠标选择复制文字
+    pub mouse: bool,
+    // 侧边栏占主内容区的宽度（百分比）；界面中调整后记住在 views.toml 中
+    pub sidebar_width: u16,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            row_actions: Vec::new(),
+            ping_interval: 15,
+            page_size: 20,
+            default_limit: 500,
+            aliases: Vec::new(),
+            keys: BTreeMap::new(),
+            vim_keys: false,
+            theme: ThemeSettings::default(),
+            mouse: true,
+            sidebar_width: 30,
+        }
+    }
+}
+
+// [theme]：name = "dark" | "light" | "high-contrast"，其余键（如 border = "blue"）覆盖该主题的颜色
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub name: String,
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self { name: "dark".to_string(), colors: BTreeMap::new() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowAction {
+    pub name: String,
+    // ${列名} 会被替换为当前行对应列的值
+    pub command: String,
+}
+
+// \名称 展开为 sql，其后的文本原样追加；指定 driver 时仅对该驱动生效，且优先于不限驱动的同名别名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alias {
+    pub name: String,
+    pub sql: String,
+    #[serde(default)]
+    pub driver: Option<String>,
+}
+
+impl Settings {
+    pub fn path() -> PathBuf {
+        config_dir().join("config.toml")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path)?;
+        toml::from_str(&raw).map_err(|e| anyhow!(t!("settings.parse_failed", path.display(), e)))
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        for (i, action) in self.row_actions.iter().enumerate() {
+            if action.name.trim().is_empty() {
+                errors.push(t!("settings.row_action_name", i));
+            }
+            if action.command.trim().is_empty() {
+                errors.push(t!("settings.row_action_command", i));
+            }
+        }
+        for (i, alias) in self.aliases.iter().enumerate() {
+            let name = alias.name.trim_start_matches('\\');
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                errors.push(t!("settings.alias_name", i));
+            }
+            if alias.sql.trim().is_empty() {
+                errors.push(t!("settings.alias_sql", i));
+            }
+            if let Some(driver) = alias.driver.as_deref().filter(|d| !DRIVERS.contains(d)) {
+                errors.push(t!("settings.alias_driver", i, driver, DRIVERS.join(" | ")));
+            }
+        }
+        if let Err(e) = Keymap::new(&self.keys, self.vim_keys) {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = Theme::from_settings(&self.theme) {
+            errors.push(e.to_string());
+        }
+        if self.page_size == 0 {
+            errors.push(t!("settings.page_size").to_string());
+        }
+        if !SIDEBAR_WIDTH_RANGE.contains(&self.sidebar_width) {
+            errors.push(t!("settings.sidebar_width", SIDEBAR_WIDTH_RANGE.start(), SIDEBAR_WIDTH_RANGE.end()));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(t!("settings.invalid", Self::path().display(), errors.join("\n  - "))))
+        }
+    }
+
+    // 当前驱动（--driver 的取值）下名为 name（不含反斜杠）的别名
+    pub fn alias(&self, name: &str, driver: &str) -> Option<&Alias> {
+        let matches = |a: &&Alias| a.name.trim_start_matches('\\') == name;
+        self.aliases
+            .iter()
+            .filter(matches)
+            .find(|a| a.driver.as_deref() == Some(driver))
+            .or_else(|| self.aliases.iter().filter(matches).find(|a| a.driver.is_none()))
+    }
+}
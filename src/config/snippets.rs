@@ -0,0 +1,146 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::paths::{config_dir, data_dir};
+use crate::t;
+
+// 常用语句库 ~/.config/sqltui/snippets.toml，每个表为一条：
+// [locks]
+// sql = "SELECT * FROM pg_locks WHERE NOT granted"
+// description = "等待中的锁"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Snippets {
+    pub snippets: BTreeMap<String, Snippet>,
+}
+
+// sql 中可写 {{参数}} 或 {{参数:默认值}}，取用时先填写再执行
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snippet {
+    pub sql: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Snippets {
+    pub fn path() -> PathBuf {
+        config_dir().join("snippets.toml")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path)?;
+        toml::from_str(&raw).map_err(|e| anyhow!(t!("snippets.parse_failed", path.display(), e)))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Snippet> {
+        self.snippets.get(name)
+    }
+
+    // 保存（同名覆盖），返回是否覆盖了已有的条目
+    pub fn insert(&mut self, name: &str, sql: String) -> bool {
+        self.snippets.insert(name.to_string(), Snippet { sql, description: None }).is_some()
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.snippets.remove(name).is_some()
+    }
+}
+
+// 语句中的 {{参数}} 与 {{参数:默认值}} 占位符（按首次出现的顺序去重），取用时逐个填写后按文本替换
+pub fn template_params(sql: &str) -> Vec<(String, Option<String>)> {
+    let mut params: Vec<(String, Option<String>)> = Vec::new();
+    let mut rest = sql;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else { break; };
+        let inner = &rest[start + 2..start + 2 + len];
+        let (name, default) = match inner.split_once(':') {
+            Some((name, default)) => (name.trim(), Some(default.to_string())),
+            None => (inner.trim(), None),
+        };
+        if !name.is_empty() && !params.iter().any(|(n, _)| n == name) {
+            params.push((name.to_string(), default));
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    params
+}
+
+// 把占位符替换为填写的值；未提供的参数保留原样
+pub fn expand_template(sql: &str, values: &BTreeMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = sql;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else { break; };
+        let inner = &rest[start + 2..start + 2 + len];
+        let name = inner.split_once(':').map_or(inner, |(name, _)| name).trim();
+        out.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + len + 2]),
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// 每个参数最多记住的取值数
+const MAX_PARAM_VALUES: usize = 10;
+
+// 占位符用过的取值（~/.local/share/sqltui/snippet-values.toml，参数名 -> 最近的在前），填写时可翻看
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ParamHistory {
+    pub values: BTreeMap<String, Vec<String>>,
+}
+
+impl ParamHistory {
+    pub fn path() -> PathBuf {
+        data_dir().join("snippet-values.toml")
+    }
+
+    // 文件不存在或损坏时为空，不影响取用
+    pub fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|raw| toml::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> &[String] {
+        self.values.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn record(&mut self, name: &str, value: &str) {
+        let values = self.values.entry(name.to_string()).or_default();
+        values.retain(|v| v != value);
+        values.insert(0, value.to_string());
+        values.truncate(MAX_PARAM_VALUES);
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::paths::data_dir;
+
+// 视图偏好（~/.local/share/sqltui/views.toml）：侧边栏宽度与按表保存的列宽
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewPrefs {
+    // 上次调整后的侧边栏宽度（百分比），优先于 config.toml 的 sidebar_width
+    pub sidebar_width: Option<u16>,
+    // 键为 "<库>.<表>"
+    pub tables: BTreeMap<String, TableViewPrefs>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableViewPrefs {
+    pub column_widths: BTreeMap<String, u16>,
+}
+
+impl ViewPrefs {
+    pub fn path() -> PathBuf {
+        data_dir().join("views.toml")
+    }
+
+    // 文件不存在或损坏时使用空偏好，不影响启动
+    pub fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|raw| toml::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn table_key(database: &str, table: &str) -> String {
+        format!("{}.{}", database, table)
+    }
+
+    pub fn column_widths(&self, key: &str) -> HashMap<String, u16> {
+        self.tables
+            .get(key)
+            .map(|t| t.column_widths.iter().map(|(k, v)| (k.clone(), *v)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn set_column_width(&mut self, key: &str, column: &str, width: u16) {
+        self.tables
+            .entry(key.to_string())
+            .or_default()
+            .column_widths
+            .insert(column.to_string(), width);
+    }
+}
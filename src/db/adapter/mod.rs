@@ -1,11 +1,33 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::path::Path;
 use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
 
-use crate::{config::{Config, Driver}, models::{Database, Table, SchemaColumn}};
+use crate::{config::{Config, Driver}, models::{ColumnPrivileges, Database, ExecResult, ParamValue, Table, SchemaColumn}};
+use crate::db::docs::{self, DocEntry, KeywordDoc};
+use crate::sql::Dialect;
 
 use crate::db::adapters::mysql::MySqlAdapter;
 use crate::db::adapters::postgres::PostgresAdapter;
 use crate::db::adapters::clickhouse::ClickHouseAdapter;
+use crate::db::adapters::duckdb::DuckDbAdapter;
+use crate::db::adapters::mssql::MssqlAdapter;
+use crate::db::adapters::cql::CqlAdapter;
+use crate::db::adapters::offline::OfflineAdapter;
+use crate::db::adapters::redshift::RedshiftAdapter;
+use crate::db::adapters::trino::TrinoAdapter;
+use crate::db::adapters::oracle::OracleAdapter;
+use crate::db::adapters::sqlite::SqliteAdapter;
+
+// 流式查询每批发送的行数
+pub const STREAM_BATCH_ROWS: usize = 500;
+
+// 流式查询的输出：先发送列名，再按批发送行
+pub enum RowBatch {
+    Columns(Vec<String>),
+    Rows(Vec<Vec<String>>),
+}
 
 #[async_trait]
 pub trait DbAdapter: Send + Sync {
@@ -14,22 +36,209 @@ pub trait DbAdapter: Send + Sync {
     fn system_databases(&self) -> &'static [&'static str];
     fn supports_use_database(&self) -> bool { true }
     fn quote_ident(&self, ident: &str) -> String { format!("`{}`", ident.replace('`', "``")) }
+    // 拼进查询的表名；MySQL、SQLite 等表名中可以含点，默认整体引用，有架构的方言按 架构.表 覆盖
+    fn quote_table(&self, table: &str) -> String { self.quote_ident(table) }
+    // 字符串字面量（生成 INSERT 等语句时使用）；默认标准 SQL，单引号写两次
+    fn quote_literal(&self, value: &str) -> String { format!("'{}'", value.replace('\'', "''")) }
+    // 批量导入时一条 INSERT ... VALUES 最多携带的行数；不支持多行 VALUES 的方言为 1
+    fn max_insert_rows(&self) -> usize { 500 }
+    // 驱动特有的关键字/函数文档；未覆盖的条目回退到通用文档
+    fn keyword_docs(&self) -> &'static [DocEntry] { &[] }
+    fn keyword_doc(&self, keyword: &str) -> Option<KeywordDoc> { docs::lookup(self.keyword_docs(), keyword) }
+    // /*+ ... */ 中可补全的优化器提示名
+    fn optimizer_hints(&self) -> &'static [&'static str] { &[] }
     async fn get_databases(&self) -> Result<Vec<Database>>;
     async fn get_tables(&self, database_name: &str) -> Result<Vec<Table>>;
     async fn get_table_schema(&self, database_name: &str, table_name: &str) -> Result<(Vec<SchemaColumn>, Option<String>)>;
+    // 当前用户对各列的权限（列名 -> 权限）；默认不支持，返回空表示未知
+    async fn get_column_privileges(&self, _database_name: &str, _table_name: &str, _columns: &[String]) -> Result<HashMap<String, ColumnPrivileges>> {
+        Ok(HashMap::new())
+    }
+    // 当前用户没有 SELECT 权限（表级及任一列都没有）的表，侧边栏中以灰色显示；默认不检查
+    async fn get_unreadable_tables(&self, _database_name: &str, _tables: &[String]) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+    // 浏览表数据的查询（分页）；order_by 为空或 " ORDER BY ..."，不支持 LIMIT/OFFSET 的方言需覆盖
+    fn select_rows_sql(&self, table: &str, order_by: &str, limit: usize, offset: usize) -> String {
+        let mut sql = format!("SELECT * FROM {}{} LIMIT {}", self.quote_table(table), order_by, limit);
+        if offset > 0 {
+            sql.push_str(&format!(" OFFSET {}", offset));
+        }
+        sql
+    }
+    // 解析语句时的方言差异（注释、占位符写法）
+    fn dialect(&self) -> Dialect { Dialect::Other }
+    // 交互查询自动追加的行数限制子句；None 表示该方言无法在末尾追加（如 SQL Server 的 TOP）
+    fn limit_suffix(&self, limit: usize) -> Option<String> {
+        Some(format!("LIMIT {}", limit))
+    }
+    // 主键列（按定义顺序）；默认不支持，返回空
+    async fn get_primary_key(&self, _database_name: &str, _table_name: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+    // 建表语句（不含结尾分号）；默认按表结构与主键拼出通用的 CREATE TABLE，能直接取得原始 DDL 的驱动需覆盖
+    async fn table_ddl(&self, database_name: &str, table_name: &str) -> Result<String> {
+        generic_table_ddl(self, database_name, table_name).await
+    }
+    // get_tables 不包含的视图（如 PostgreSQL 的 pg_tables 只列出表），供导出整库结构；默认无
+    async fn get_views(&self, _database_name: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
     async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>) >;
-    async fn execute_non_query(&self, query: &str) -> Result<u64>;
+    // 流式查询：按批把行发送到 tx，接收端关闭（结果被替换或取消）时停止拉取；
+    // 有界通道提供背压，接收端不取时驱动也不再读取。默认一次性取回后分批发送
+    async fn stream_query(&self, query: &str, tx: Sender<RowBatch>) -> Result<()> {
+        let (headers, rows) = self.execute_query_raw(query).await?;
+        if tx.send(RowBatch::Columns(headers)).await.is_err() {
+            return Ok(());
+        }
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let batch: Vec<Vec<String>> = rows.by_ref().take(STREAM_BATCH_ROWS).collect();
+            if tx.send(RowBatch::Rows(batch)).await.is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+    async fn execute_non_query(&self, query: &str) -> Result<ExecResult>;
+    // 参数绑定：语句中的占位符已改写为 param_placeholder 的写法，params 按位置绑定；默认不支持
+    fn supports_params(&self) -> bool { false }
+    // 第 index 个（从 0 开始）占位符的写法
+    fn param_placeholder(&self, _index: usize) -> String { "?".to_string() }
+    async fn query_with_params(&self, _query: &str, _params: &[ParamValue]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        Err(anyhow!("{} 不支持参数绑定", self.driver_name()))
+    }
+    async fn execute_with_params(&self, _query: &str, _params: &[ParamValue]) -> Result<ExecResult> {
+        Err(anyhow!("{} 不支持参数绑定", self.driver_name()))
+    }
+    // 取走最近一条语句产生的警告（如 MySQL 的 SHOW WARNINGS，格式 "Warning 1265: ..."）；默认不支持
+    fn take_warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+    // 事务模式：开启事务后用户语句在同一连接上执行，直到提交或回滚。
+    // 默认不支持；单连接的适配器直接执行语句即可，连接池适配器需固定连接
+    fn supports_transactions(&self) -> bool { false }
+    // 自动提交关闭时隐式开启事务所用的语句
+    fn begin_statement(&self) -> &'static str { "BEGIN" }
+    async fn begin_transaction(&self, statement: &str) -> Result<()> {
+        self.execute_non_query(statement).await.map(|_| ())
+    }
+    // 执行 COMMIT / ROLLBACK 等结束事务的语句
+    async fn end_transaction(&self, statement: &str) -> Result<()> {
+        self.execute_non_query(statement).await.map(|_| ())
+    }
+    // 批量导入导出走 COPY 协议（PostgreSQL），比逐行 SELECT / INSERT 快得多；默认不支持
+    fn supports_copy(&self) -> bool { false }
+    // COPY (query) TO STDOUT 的 CSV（含表头，NULL 为空字段）写入文件，返回写入的字节数
+    async fn copy_out_csv(&self, _query: &str, _path: &Path) -> Result<u64> {
+        Err(anyhow!("{} 不支持 COPY", self.driver_name()))
+    }
+    // COPY table (columns) FROM STDIN 写入 CSV 数据（无表头，不加引号的空字段为 NULL），返回行数
+    async fn copy_in_csv(&self, _table: &str, _columns: &[String], _data: Vec<u8>) -> Result<u64> {
+        Err(anyhow!("{} 不支持 COPY", self.driver_name()))
+    }
+    // 在现有连接上切换当前库；返回 false 表示不支持，调用方改为按新库重新连接。
+    // 内存库等重连即丢失数据的适配器必须实现
+    async fn switch_database(&self, _database_name: &str) -> Result<bool> {
+        Ok(false)
+    }
+    // 请求服务端中止正在执行的用户语句（释放其持有的锁）；默认不支持，取消只停止等待结果
+    async fn cancel_running(&self) -> Result<()> { Ok(()) }
+    // 内存库：重建适配器会丢失全部数据，重连与 \timeout 不得新建连接
+    fn in_memory(&self) -> bool { false }
     async fn get_version(&self) -> Result<String>;
     async fn get_current_user(&self) -> Result<String>;
+    // 连接探活（状态栏的连接状态与往返耗时）；默认执行 SELECT 1
+    async fn ping(&self) -> Result<()> {
+        self.execute_query_raw("SELECT 1").await.map(|_| ())
+    }
+    // 当前连接协商的 TLS 状态（协议/加密套件），未加密时为 "off"；None 表示驱动不支持查询
+    async fn get_tls_status(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+// 按表结构与主键拼出的 CREATE TABLE（table_ddl 的默认实现）
+pub async fn generic_table_ddl<D: DbAdapter + ?Sized>(db: &D, database_name: &str, table_name: &str) -> Result<String> {
+    let (columns, _) = db.get_table_schema(database_name, table_name).await?;
+    if columns.is_empty() {
+        return Err(anyhow!("表 {} 不存在或没有列", table_name));
+    }
+    let primary_key = db.get_primary_key(database_name, table_name).await.unwrap_or_default();
+    let mut lines: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            let mut line = format!("    {} {}", db.quote_ident(&c.name), c.data_type);
+            if !c.is_nullable {
+                line.push_str(" NOT NULL");
+            }
+            if let Some(default) = &c.default_value {
+                line.push_str(&format!(" DEFAULT {}", default));
+            }
+            line
+        })
+        .collect();
+    if !primary_key.is_empty() {
+        let keys: Vec<String> = primary_key.iter().map(|k| db.quote_ident(k)).collect();
+        lines.push(format!("    PRIMARY KEY ({})", keys.join(", ")));
+    }
+    Ok(format!("CREATE TABLE {} (\n{}\n)", db.quote_table(table_name), lines.join(",\n")))
+}
+
+// 架构.表 形式的表名按点拆分后逐段引用，供有架构的方言实现 quote_table
+pub fn quote_qualified<D: DbAdapter + ?Sized>(db: &D, table: &str) -> String {
+    table.split('.').map(|part| db.quote_ident(part)).collect::<Vec<_>>().join(".")
 }
 
 pub async fn new_adapter(config: &Config) -> Result<Box<dyn DbAdapter>> {
     let dsn = config.get_dsn();
+    let init_sql = config.session_sql();
     match config.driver() {
-        Driver::Mysql => Ok(Box::new(MySqlAdapter::new(&dsn).await?)),
-        Driver::Postgres => Ok(Box::new(PostgresAdapter::new(&dsn).await?)),
-        Driver::Clickhouse => Ok(Box::new(ClickHouseAdapter::new(&dsn).await?)),
+        Driver::Mysql => Ok(Box::new(MySqlAdapter::new(&dsn, &init_sql, &config.charset, config.collation()).await?)),
+        Driver::Postgres => Ok(Box::new(PostgresAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Redshift => Ok(Box::new(RedshiftAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Clickhouse => Ok(Box::new(ClickHouseAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Mssql => Ok(Box::new(MssqlAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Cql => Ok(Box::new(CqlAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Trino => Ok(Box::new(TrinoAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Oracle => Ok(Box::new(OracleAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Sqlite => Ok(Box::new(SqliteAdapter::new(&dsn, &init_sql).await?)),
+        Driver::Duckdb => Ok(Box::new(
+            DuckDbAdapter::new(config.path.as_deref(), config.database.as_deref(), &init_sql).await?,
+        )),
     }
 }
 
+// 连接层错误（服务端重启、空闲超时被断开等），重建连接后可重试；SQL 本身的错误返回 false
+pub fn is_connection_error(err: &anyhow::Error) -> bool {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<sqlx::Error>() {
+            return matches!(
+                e,
+                sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::WorkerCrashed
+            );
+        }
+        if cause.downcast_ref::<std::io::Error>().is_some() {
+            return true;
+        }
+    }
+    // 其他驱动的错误按消息识别
+    let message = err.to_string().to_lowercase();
+    [
+        "server has gone away",
+        "lost connection",
+        "connection reset",
+        "connection refused",
+        "connection closed",
+        "broken pipe",
+        "terminating connection",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
 
+// 不连接数据库的适配器，用于只读打开结果快照
+pub fn offline_adapter() -> Box<dyn DbAdapter> {
+    Box::new(OfflineAdapter)
+}
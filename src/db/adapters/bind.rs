@@ -0,0 +1,24 @@
+use sqlx::query::Query;
+use sqlx::{Database, Encode, Type};
+
+use crate::models::ParamValue;
+
+// 按位置把参数绑定到 sqlx 查询上
+pub fn bind_params<'q, DB>(
+    query: Query<'q, DB, <DB as Database>::Arguments<'q>>,
+    params: &[ParamValue],
+) -> Query<'q, DB, <DB as Database>::Arguments<'q>>
+where
+    DB: Database,
+    Option<String>: Encode<'q, DB> + Type<DB>,
+    String: Encode<'q, DB> + Type<DB>,
+    i64: Encode<'q, DB> + Type<DB>,
+    f64: Encode<'q, DB> + Type<DB>,
+{
+    params.iter().fold(query, |query, param| match param {
+        ParamValue::Null => query.bind(None::<String>),
+        ParamValue::Int(v) => query.bind(*v),
+        ParamValue::Float(v) => query.bind(*v),
+        ParamValue::Text(v) => query.bind(v.clone()),
+    })
+}
@@ -0,0 +1,215 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ::duckdb::types::ValueRef;
+use ::duckdb::{params, Connection};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::db::adapter::DbAdapter;
+use crate::models::{Database, ExecResult, SchemaColumn, Table};
+
+// DuckDB 本地分析库：库列表对应 schema；嵌入式同步 API 放到阻塞线程中执行
+pub struct DuckDbAdapter {
+    conn: Arc<Mutex<Connection>>,
+    in_memory: bool,
+}
+
+impl DuckDbAdapter {
+    pub async fn new(path: Option<&Path>, schema: Option<&str>, init_sql: &[String]) -> Result<Self> {
+        let conn = match path {
+            Some(path) => Connection::open(path)?,
+            None => Connection::open_in_memory()?,
+        };
+        if let Some(schema) = schema {
+            conn.execute_batch(&format!("SET schema = '{}'", schema.replace('\'', "''")))?;
+        }
+        for sql in init_sql {
+            conn.execute_batch(sql)?;
+        }
+        Ok(Self { conn: Arc::new(Mutex::new(conn)), in_memory: path.is_none() })
+    }
+
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|_| anyhow!("DuckDB 连接已损坏"))?;
+            f(&conn)
+        })
+        .await?
+    }
+
+    // 执行查询并把所有单元格转成字符串；params 按 ? 顺序绑定
+    async fn query_strings(&self, sql: String, params: Vec<String>) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query(::duckdb::params_from_iter(params.iter()))?;
+            let headers = rows.as_ref().map(|s| s.column_names()).unwrap_or_default();
+            let mut data = Vec::new();
+            while let Some(row) = rows.next()? {
+                let mut cells = Vec::with_capacity(headers.len());
+                for idx in 0..headers.len() {
+                    cells.push(Self::get_cell_value_as_string(row, idx));
+                }
+                data.push(cells);
+            }
+            Ok((headers, data))
+        })
+        .await
+    }
+
+    fn get_cell_value_as_string(row: &::duckdb::Row, index: usize) -> String {
+        if matches!(row.get_ref(index), Ok(ValueRef::Null) | Err(_)) { return "NULL".into(); }
+        if let Ok(v) = row.get::<_, String>(index) { return v; }
+        if let Ok(v) = row.get::<_, i64>(index) { return v.to_string(); }
+        if let Ok(v) = row.get::<_, f64>(index) { return v.to_string(); }
+        if let Ok(v) = row.get::<_, bool>(index) { return if v { "1".into() } else { "0".into() }; }
+        if let Ok(v) = row.get::<_, chrono::NaiveDateTime>(index) { return v.format("%Y-%m-%d %H:%M:%S").to_string(); }
+        if let Ok(v) = row.get::<_, chrono::NaiveDate>(index) { return v.format("%Y-%m-%d").to_string(); }
+        if let Ok(v) = row.get::<_, chrono::NaiveTime>(index) { return v.format("%H:%M:%S").to_string(); }
+        if let Ok(v) = row.get::<_, Vec<u8>>(index) { return String::from_utf8_lossy(&v).to_string(); }
+        // 列表/结构体等嵌套类型
+        row.get::<_, ::duckdb::types::Value>(index).map(|v| format!("{:?}", v)).unwrap_or_else(|_| "NULL".into())
+    }
+}
+
+#[async_trait]
+impl DbAdapter for DuckDbAdapter {
+    fn driver_name(&self) -> &'static str { "DuckDB" }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "SELECT","FROM","WHERE","INSERT","UPDATE","DELETE","CREATE","DROP",
+            "ALTER","DESCRIBE","SUMMARIZE","EXPLAIN","ANALYZE","JOIN","LEFT","RIGHT","INNER","OUTER",
+            "ASOF","POSITIONAL","ON","USING","GROUP","BY","ALL","ORDER","HAVING","QUALIFY","LIMIT","OFFSET",
+            "DISTINCT","COUNT","SUM","AVG","MIN","MAX","AND","OR","NOT","IN","LIKE","ILIKE","BETWEEN",
+            "IS","NULL","ASC","DESC","AS","UNION","EXCLUDE","REPLACE","EXISTS","TABLE","VIEW","SCHEMA",
+            "PIVOT","UNPIVOT","COPY","TO","FORMAT","ATTACH","DETACH","INSTALL","LOAD","PRAGMA",
+            "READ_PARQUET","READ_CSV_AUTO","READ_JSON_AUTO","PARQUET_SCAN","COALESCE","NULLIF","CAST",
+            "STRING_AGG","LIST","STRUCT_PACK","UNNEST","STRFTIME","DATE_TRUNC","EPOCH_MS"
+        ]
+    }
+
+    fn system_databases(&self) -> &'static [&'static str] { &["information_schema", "pg_catalog"] }
+
+    fn quote_ident(&self, ident: &str) -> String { format!("\"{}\"", ident.replace('"', "\"\"")) }
+
+    // 当前库中的 schema 视为“数据库”
+    async fn get_databases(&self) -> Result<Vec<Database>> {
+        let (_, rows) = self
+            .query_strings(
+                r#"
+                SELECT s.schema_name,
+                       (SELECT COUNT(*) FROM information_schema.tables t
+                        WHERE t.table_catalog = s.catalog_name AND t.table_schema = s.schema_name) AS table_count
+                FROM information_schema.schemata s
+                WHERE s.catalog_name = current_database()
+                ORDER BY s.schema_name
+                "#
+                .to_string(),
+                Vec::new(),
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|mut r| {
+                let count = r.get(1).and_then(|c| c.parse::<u64>().ok());
+                Database::with_details(r.swap_remove(0), None, None, count)
+            })
+            .collect())
+    }
+
+    // 重新打开会丢掉内存库中的全部表，改为在原连接上切换 schema
+    async fn switch_database(&self, database_name: &str) -> Result<bool> {
+        let sql = format!("SET schema = '{}'", database_name.replace('\'', "''"));
+        self.with_conn(move |conn| Ok(conn.execute_batch(&sql)?)).await?;
+        Ok(true)
+    }
+
+    fn in_memory(&self) -> bool { self.in_memory }
+
+    async fn get_tables(&self, database_name: &str) -> Result<Vec<Table>> {
+        let (_, rows) = self
+            .query_strings(
+                r#"
+                SELECT table_name, table_type FROM information_schema.tables
+                WHERE table_catalog = current_database() AND table_schema = ?
+                ORDER BY table_name
+                "#
+                .to_string(),
+                vec![database_name.to_string()],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let comment = (r.get(1).map(|t| t.as_str()) == Some("VIEW")).then(|| "VIEW".to_string());
+                Table::with_details(r[0].clone(), comment, None, None, None)
+            })
+            .collect())
+    }
+
+    async fn get_table_schema(&self, database_name: &str, table_name: &str) -> Result<(Vec<SchemaColumn>, Option<String>)> {
+        let (_, rows) = self
+            .query_strings(
+                r#"
+                SELECT column_name, data_type, is_nullable, column_default
+                FROM information_schema.columns
+                WHERE table_catalog = current_database() AND table_schema = ? AND table_name = ?
+                ORDER BY ordinal_position
+                "#
+                .to_string(),
+                vec![database_name.to_string(), table_name.to_string()],
+            )
+            .await?;
+        let cols = rows
+            .into_iter()
+            .map(|r| {
+                let default_value = r.get(3).filter(|d| *d != "NULL").cloned();
+                SchemaColumn::with_details(r[0].clone(), r[1].clone(), r[2] == "YES", default_value, None, None)
+            })
+            .collect();
+        Ok((cols, None))
+    }
+
+    async fn get_primary_key(&self, database_name: &str, table_name: &str) -> Result<Vec<String>> {
+        let (_, rows) = self
+            .query_strings(
+                r#"
+                SELECT UNNEST(constraint_column_names) FROM duckdb_constraints()
+                WHERE constraint_type = 'PRIMARY KEY' AND schema_name = ? AND table_name = ?
+                "#
+                .to_string(),
+                vec![database_name.to_string(), table_name.to_string()],
+            )
+            .await?;
+        Ok(rows.into_iter().filter_map(|mut r| r.pop()).collect())
+    }
+
+    async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let (headers, rows) = self.query_strings(query.to_string(), Vec::new()).await?;
+        if rows.is_empty() { return Ok((Vec::new(), Vec::new())); }
+        Ok((headers, rows))
+    }
+
+    async fn execute_non_query(&self, query: &str) -> Result<ExecResult> {
+        let query = query.to_string();
+        self.with_conn(move |conn| Ok(ExecResult::affected(conn.execute(&query, params![])? as u64))).await
+    }
+
+    // 单连接，事务语句直接在该连接上执行
+    fn supports_transactions(&self) -> bool { true }
+
+    async fn get_version(&self) -> Result<String> {
+        let (_, rows) = self.query_strings("SELECT version()".to_string(), Vec::new()).await?;
+        Ok(rows.into_iter().next().and_then(|mut r| r.pop()).unwrap_or_default())
+    }
+
+    // 本地文件没有用户概念
+    async fn get_current_user(&self) -> Result<String> {
+        Ok("local".to_string())
+    }
+}
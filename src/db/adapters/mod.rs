@@ -1,5 +1,22 @@
 pub mod mysql;
 pub mod postgres;
+pub mod redshift;
 pub mod clickhouse;
+pub mod cql;
+pub mod duckdb;
+pub mod mssql;
+pub mod sqlite;
+pub mod offline;
+pub mod trino;
+pub mod oracle;
+pub mod session;
+pub mod bind;
 
+use percent_encoding::percent_decode_str;
+use url::Url;
 
+// DSN 中的用户名与密码：URL 里是百分号编码的，交给驱动前需解码
+pub fn url_credentials(url: &Url) -> (String, Option<String>) {
+    let decode = |s: &str| percent_decode_str(s).decode_utf8_lossy().into_owned();
+    (decode(url.username()), url.password().map(decode))
+}
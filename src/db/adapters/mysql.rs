@@ -1,23 +1,111 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use sqlx::{MySql, Pool, Row, Column};
+use sqlx::{MySql, Pool, Row, Column, Connection};
+use futures::TryStreamExt;
+use sqlx::mysql::{MySqlConnection, MySqlPoolOptions};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::Sender;
 
-use crate::models::{Database, Table, SchemaColumn};
-use crate::db::adapter::DbAdapter;
+use crate::models::{ColumnPrivileges, Database, ExecResult, ParamValue, Table, SchemaColumn};
+use crate::db::adapter::{DbAdapter, RowBatch, STREAM_BATCH_ROWS};
+use crate::db::adapters::bind::bind_params;
+use crate::db::adapters::session::{BackendGuard, Session, SessionConn};
+use crate::db::docs::DocEntry;
+use crate::sql;
 
 pub struct MySqlAdapter {
     pool: Pool<MySql>,
+    // 用户语句的连接（事务中固定）
+    session: Session<MySql>,
+    // 最近一条用户语句的 SHOW WARNINGS 结果
+    warnings: Mutex<Vec<String>>,
 }
 
 impl MySqlAdapter {
-    pub async fn new(dsn: &str) -> Result<Self> {
-        let pool = sqlx::MySqlPool::connect(dsn).await?;
-        // 连接后设置字符集
-        sqlx::query("SET NAMES utf8mb4 COLLATE utf8mb4_unicode_ci").execute(&pool).await?;
-        sqlx::query("SET character_set_client=utf8mb4").execute(&pool).await?;
-        sqlx::query("SET character_set_connection=utf8mb4").execute(&pool).await?;
-        sqlx::query("SET character_set_results=utf8mb4").execute(&pool).await?;
-        Ok(Self { pool })
+    // information_schema 中的 GRANTEE（形如 'user'@'host'）：当前匹配的账号（CURRENT_USER，而非登录时的 USER()）
+    // 及其已激活的角色，角色授予的权限记在角色名下
+    async fn grantees(&self) -> Result<Vec<String>> {
+        let account: String = sqlx::query_scalar("SELECT CURRENT_USER()").fetch_one(&self.pool).await?;
+        let mut grantees = vec![Self::grantee(&account)];
+        // MySQL 8 返回 `r1`@`%`,`r2`@`%`（无角色时为 NONE）；MariaDB 返回角色名或 NULL；不支持时忽略
+        if let Ok(Some(roles)) = sqlx::query_scalar::<_, Option<String>>("SELECT CURRENT_ROLE()").fetch_one(&self.pool).await {
+            grantees.extend(
+                roles
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty() && !r.eq_ignore_ascii_case("NONE"))
+                    .map(Self::grantee),
+            );
+        }
+        Ok(grantees)
+    }
+
+    // user@host / `user`@`host` -> 'user'@'host'；MariaDB 的角色没有主机部分
+    fn grantee(account: &str) -> String {
+        let account = account.replace('`', "");
+        match account.rsplit_once('@') {
+            Some((user, host)) => format!("'{}'@'{}'", user, host),
+            None => format!("'{}'", account),
+        }
+    }
+
+    pub async fn new(dsn: &str, init_sql: &[String], charset: &str, collation: Option<&str>) -> Result<Self> {
+        // 字符集与启动 SQL 需在连接池的每个连接上执行，否则会话级设置只作用于其中一个连接
+        let set_names = match collation {
+            Some(collation) => format!("SET NAMES {} COLLATE {}", charset, collation),
+            None => format!("SET NAMES {}", charset),
+        };
+        let mut statements = vec![set_names];
+        statements.extend(init_sql.iter().cloned());
+        let init_sql: Arc<Vec<String>> = Arc::new(statements);
+        let pool = MySqlPoolOptions::new()
+            .after_connect(move |conn, _meta| {
+                let init_sql = init_sql.clone();
+                Box::pin(async move {
+                    for sql in init_sql.iter() {
+                        sqlx::query(sql.as_str()).execute(&mut *conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect(dsn)
+            .await?;
+        Ok(Self { session: Session::new(pool.clone()), pool, warnings: Mutex::new(Vec::new()) })
+    }
+
+    // 记录执行用户语句的连接 ID，Ctrl+C 取消时据此 KILL QUERY；取不到时只能停止等待结果。
+    // 返回的守卫须持有到语句结束；事务固定的连接复用缓存的连接 ID
+    async fn track_backend(&self, conn: &mut SessionConn<'_, MySql>) -> BackendGuard<'_> {
+        let id = match self.session.cached_backend(conn) {
+            Some(id) => Some(id),
+            None => sqlx::query_scalar::<_, u64>("SELECT CONNECTION_ID()").fetch_one(&mut **conn).await.ok().map(|id| id as i64),
+        };
+        self.session.track_backend(conn, id)
+    }
+
+    // 在执行语句的同一连接上读取警告（警告属于会话，换连接即丢失）；
+    // SHOW 语句本身不清除警告，跳过以免重复显示
+    async fn collect_warnings(&self, conn: &mut MySqlConnection, query: &str) {
+        let warnings = if sql::first_keyword(query, sql::Dialect::Mysql) == "SHOW" {
+            Vec::new()
+        } else {
+            sqlx::query("SHOW WARNINGS")
+                .fetch_all(&mut *conn)
+                .await
+                .unwrap_or_default()
+                .iter()
+                .map(|row| {
+                    format!(
+                        "{} {}: {}",
+                        Self::get_cell_value_as_string(row, 0),
+                        Self::get_cell_value_as_string(row, 1),
+                        Self::get_cell_value_as_string(row, 2)
+                    )
+                })
+                .collect()
+        };
+        *self.warnings.lock().unwrap() = warnings;
     }
 
     fn get_cell_value_as_string(row: &sqlx::mysql::MySqlRow, index: usize) -> String {
@@ -45,7 +133,9 @@ impl DbAdapter for MySqlAdapter {
             "COUNT", "SUM", "AVG", "MIN", "MAX", "AND", "OR", "NOT", "IN", "LIKE", "BETWEEN",
             "IS", "NULL", "TRUE", "FALSE", "ASC", "DESC", "AS", "UNION", "ALL", "EXISTS",
             "DATABASES", "TABLES", "COLUMNS", "INDEX", "INDEXES", "PROCESSLIST", "STATUS",
-            "VARIABLES", "GRANTS", "PRIVILEGES", "USERS", "FUNCTIONS", "PROCEDURES", "TRIGGERS"
+            "VARIABLES", "GRANTS", "PRIVILEGES", "USERS", "FUNCTIONS", "PROCEDURES", "TRIGGERS",
+            "IFNULL", "COALESCE", "NULLIF", "CAST", "CONCAT", "GROUP_CONCAT", "NOW", "DATE_FORMAT",
+            "LAST_INSERT_ID"
         ]
     }
     fn system_databases(&self) -> &'static [&'static str] {
@@ -53,6 +143,32 @@ impl DbAdapter for MySqlAdapter {
     }
     fn supports_use_database(&self) -> bool { true }
     fn quote_ident(&self, ident: &str) -> String { format!("`{}`", ident.replace('`', "``")) }
+    // 反斜杠在字符串中是转义符
+    fn quote_literal(&self, value: &str) -> String { format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")) }
+    fn optimizer_hints(&self) -> &'static [&'static str] {
+        &[
+            "BKA", "NO_BKA", "BNL", "NO_BNL", "HASH_JOIN", "NO_HASH_JOIN", "INDEX", "NO_INDEX",
+            "INDEX_MERGE", "NO_INDEX_MERGE", "JOIN_INDEX", "NO_JOIN_INDEX", "JOIN_ORDER",
+            "JOIN_PREFIX", "JOIN_SUFFIX", "JOIN_FIXED_ORDER", "MERGE", "NO_MERGE", "MRR", "NO_MRR",
+            "NO_ICP", "NO_RANGE_OPTIMIZATION", "ORDER_INDEX", "NO_ORDER_INDEX", "SEMIJOIN",
+            "NO_SEMIJOIN", "SKIP_SCAN", "NO_SKIP_SCAN", "MAX_EXECUTION_TIME", "SET_VAR",
+            "RESOURCE_GROUP", "QB_NAME",
+        ]
+    }
+    fn keyword_docs(&self) -> &'static [DocEntry] {
+        &[
+            ("SHOW", "SHOW DATABASES|TABLES|PROCESSLIST|VARIABLES ...", "查看 MySQL 服务器元数据与状态"),
+            ("PROCESSLIST", "SHOW [FULL] PROCESSLIST", "列出当前连接与正在执行的语句"),
+            ("VARIABLES", "SHOW [GLOBAL|SESSION] VARIABLES [LIKE 'pat']", "查看系统变量"),
+            ("GRANTS", "SHOW GRANTS [FOR user]", "查看用户权限"),
+            ("IFNULL", "IFNULL(expr, alt)", "expr 为 NULL 时返回 alt"),
+            ("GROUP_CONCAT", "GROUP_CONCAT(expr [ORDER BY ...] [SEPARATOR ','])", "将分组内的值拼接为字符串"),
+            ("NOW", "NOW()", "当前日期时间"),
+            ("DATE_FORMAT", "DATE_FORMAT(date, '%Y-%m-%d')", "按格式输出日期"),
+            ("CONCAT", "CONCAT(s1, s2, ...)", "拼接字符串；任一参数为 NULL 则结果为 NULL"),
+            ("LAST_INSERT_ID", "LAST_INSERT_ID()", "本连接最近一次自增插入的 ID"),
+        ]
+    }
 
     async fn get_databases(&self) -> Result<Vec<Database>> {
         let rows = sqlx::query("SHOW DATABASES").fetch_all(&self.pool).await?;
@@ -139,8 +255,127 @@ impl DbAdapter for MySqlAdapter {
         Ok((columns, table_comment))
     }
 
+    async fn table_ddl(&self, database_name: &str, table_name: &str) -> Result<String> {
+        let sql = format!("SHOW CREATE TABLE {}.{}", self.quote_ident(database_name), self.quote_ident(table_name));
+        let row = sqlx::query(&sql).fetch_one(&self.pool).await?;
+        Ok(Self::get_cell_value_as_string(&row, 1))
+    }
+
+    async fn get_primary_key(&self, database_name: &str, table_name: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT COLUMN_NAME FROM information_schema.KEY_COLUMN_USAGE
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND CONSTRAINT_NAME = 'PRIMARY'
+            ORDER BY ORDINAL_POSITION
+            "#
+        )
+        .bind(database_name)
+        .bind(table_name)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(|row| Self::get_cell_value_as_string(row, 0)).collect())
+    }
+
+    async fn get_column_privileges(&self, database_name: &str, table_name: &str, columns: &[String]) -> Result<HashMap<String, ColumnPrivileges>> {
+        let grantees = self.grantees().await?;
+        let list = vec!["?"; grantees.len()].join(", ");
+        // 全局/库/表级权限作用于所有列，列级权限只作用于对应列
+        let query = format!(
+            r#"
+            SELECT PRIVILEGE_TYPE AS privilege, COLUMN_NAME AS col
+            FROM information_schema.COLUMN_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND TABLE_SCHEMA = ? AND TABLE_NAME = ?
+            UNION ALL
+            SELECT PRIVILEGE_TYPE, NULL FROM information_schema.TABLE_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND TABLE_SCHEMA = ? AND TABLE_NAME = ?
+            UNION ALL
+            SELECT PRIVILEGE_TYPE, NULL FROM information_schema.SCHEMA_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND TABLE_SCHEMA = ?
+            UNION ALL
+            SELECT PRIVILEGE_TYPE, NULL FROM information_schema.USER_PRIVILEGES
+            WHERE GRANTEE IN ({list})
+            "#
+        );
+        let mut q = sqlx::query(&query);
+        let scopes: [&[&str]; 4] = [&[database_name, table_name], &[database_name, table_name], &[database_name], &[]];
+        for scope in scopes {
+            for grantee in &grantees {
+                q = q.bind(grantee.as_str());
+            }
+            for value in scope {
+                q = q.bind(*value);
+            }
+        }
+        let rows = q.fetch_all(&self.pool).await?;
+
+        let mut table_level = ColumnPrivileges::default();
+        let mut result: HashMap<String, ColumnPrivileges> = columns
+            .iter()
+            .map(|c| (c.clone(), ColumnPrivileges::default()))
+            .collect();
+        for row in rows {
+            let privilege = Self::get_cell_value_as_string(&row, 0);
+            let col = Self::get_cell_value_as_string(&row, 1);
+            if col == "NULL" {
+                table_level.grant(&privilege);
+            } else if let Some(p) = result.get_mut(&col) {
+                p.grant(&privilege);
+            }
+        }
+        for p in result.values_mut() { p.merge(&table_level); }
+        Ok(result)
+    }
+
+    async fn get_unreadable_tables(&self, database_name: &str, tables: &[String]) -> Result<Vec<String>> {
+        let grantees = self.grantees().await?;
+        let list = vec!["?"; grantees.len()].join(", ");
+        // 全局或库级 SELECT 覆盖全部表（表名为 NULL）；否则只有表级或列级授予了 SELECT 的表可读
+        let query = format!(
+            r#"
+            SELECT NULL AS tbl FROM information_schema.USER_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND PRIVILEGE_TYPE = 'SELECT'
+            UNION ALL
+            SELECT NULL FROM information_schema.SCHEMA_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND TABLE_SCHEMA = ? AND PRIVILEGE_TYPE = 'SELECT'
+            UNION ALL
+            SELECT TABLE_NAME FROM information_schema.TABLE_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND TABLE_SCHEMA = ? AND PRIVILEGE_TYPE = 'SELECT'
+            UNION ALL
+            SELECT TABLE_NAME FROM information_schema.COLUMN_PRIVILEGES
+            WHERE GRANTEE IN ({list}) AND TABLE_SCHEMA = ? AND PRIVILEGE_TYPE = 'SELECT'
+            "#
+        );
+        let mut q = sqlx::query(&query);
+        let scopes: [&[&str]; 4] = [&[], &[database_name], &[database_name], &[database_name]];
+        for scope in scopes {
+            for grantee in &grantees {
+                q = q.bind(grantee.as_str());
+            }
+            for value in scope {
+                q = q.bind(*value);
+            }
+        }
+        let rows = q.fetch_all(&self.pool).await?;
+        let readable: Vec<String> = rows.iter().map(|row| Self::get_cell_value_as_string(row, 0)).collect();
+        if readable.iter().any(|t| t == "NULL") {
+            return Ok(Vec::new());
+        }
+        Ok(tables.iter().filter(|t| !readable.contains(t)).cloned().collect())
+    }
+
     async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
-        let rows = sqlx::query(query).fetch_all(&self.pool).await?;
+        self.query_with_params(query, &[]).await
+    }
+
+    fn dialect(&self) -> sql::Dialect { sql::Dialect::Mysql }
+    fn supports_params(&self) -> bool { true }
+
+    async fn query_with_params(&self, query: &str, params: &[ParamValue]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let mut conn = self.session.acquire().await?;
+        let _backend = self.track_backend(&mut conn).await;
+        self.warnings.lock().unwrap().clear();
+        let rows = bind_params(sqlx::query(query), params).fetch_all(&mut *conn).await?;
+        self.collect_warnings(&mut conn, query).await;
         if rows.is_empty() { return Ok((Vec::new(), Vec::new())); }
         let headers: Vec<String> = rows[0].columns().iter().map(|c| c.name().to_string()).collect();
         let mut data_rows = Vec::new();
@@ -152,9 +387,72 @@ impl DbAdapter for MySqlAdapter {
         Ok((headers, data_rows))
     }
 
-    async fn execute_non_query(&self, query: &str) -> Result<u64> {
-        let result = sqlx::query(query).execute(&self.pool).await?;
-        Ok(result.rows_affected())
+    async fn stream_query(&self, query: &str, tx: Sender<RowBatch>) -> Result<()> {
+        let mut conn = self.session.acquire().await?;
+        let _backend = self.track_backend(&mut conn).await;
+        self.warnings.lock().unwrap().clear();
+        let mut batch = Vec::new();
+        {
+            let mut rows = sqlx::query(query).fetch(&mut *conn);
+            let mut sent_columns = false;
+            while let Some(row) = rows.try_next().await? {
+                if !sent_columns {
+                    let headers = row.columns().iter().map(|c| c.name().to_string()).collect();
+                    if tx.send(RowBatch::Columns(headers)).await.is_err() { return Ok(()); }
+                    sent_columns = true;
+                }
+                batch.push((0..row.columns().len()).map(|i| Self::get_cell_value_as_string(&row, i)).collect());
+                if batch.len() >= STREAM_BATCH_ROWS && tx.send(RowBatch::Rows(std::mem::take(&mut batch))).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        // 警告在全部行读取完后才可查询，先于末批写入，接收端取完末批即可读到
+        self.collect_warnings(&mut conn, query).await;
+        if !batch.is_empty() {
+            let _ = tx.send(RowBatch::Rows(batch)).await;
+        }
+        Ok(())
+    }
+
+    async fn execute_non_query(&self, query: &str) -> Result<ExecResult> {
+        self.execute_with_params(query, &[]).await
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[ParamValue]) -> Result<ExecResult> {
+        let mut conn = self.session.acquire().await?;
+        let _backend = self.track_backend(&mut conn).await;
+        self.warnings.lock().unwrap().clear();
+        let result = bind_params(sqlx::query(query), params).execute(&mut *conn).await?;
+        self.collect_warnings(&mut conn, query).await;
+        Ok(ExecResult {
+            rows_affected: result.rows_affected(),
+            // 未生成自增值时为 0
+            last_insert_id: Some(result.last_insert_id()).filter(|id| *id > 0),
+            warnings: self.warnings.lock().unwrap().len() as u64,
+        })
+    }
+
+    fn take_warnings(&self) -> Vec<String> {
+        std::mem::take(&mut *self.warnings.lock().unwrap())
+    }
+
+    fn supports_transactions(&self) -> bool { true }
+
+    async fn begin_transaction(&self, statement: &str) -> Result<()> {
+        self.session.begin(statement).await
+    }
+
+    async fn end_transaction(&self, statement: &str) -> Result<()> {
+        self.session.end(statement).await
+    }
+
+    // 在另一条连接上 KILL QUERY：只中止语句，连接与事务保留
+    async fn cancel_running(&self) -> Result<()> {
+        if let Some(id) = self.session.take_backend() {
+            sqlx::Executor::execute(&self.pool, format!("KILL QUERY {}", id).as_str()).await?;
+        }
+        Ok(())
     }
 
     async fn get_version(&self) -> Result<String> {
@@ -162,6 +460,32 @@ impl DbAdapter for MySqlAdapter {
         Ok(row.get::<String, _>("version"))
     }
 
+    async fn ping(&self) -> Result<()> {
+        self.pool.acquire().await?.ping().await?;
+        Ok(())
+    }
+
+    async fn get_tls_status(&self) -> Result<Option<String>> {
+        let rows = sqlx::query("SHOW SESSION STATUS WHERE Variable_name IN ('Ssl_version', 'Ssl_cipher')")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut version = String::new();
+        let mut cipher = String::new();
+        for row in rows {
+            let name = Self::get_cell_value_as_string(&row, 0);
+            let value = Self::get_cell_value_as_string(&row, 1);
+            match name.as_str() {
+                "Ssl_version" => version = value,
+                "Ssl_cipher" => cipher = value,
+                _ => {}
+            }
+        }
+        if cipher.is_empty() {
+            return Ok(Some("off".to_string()));
+        }
+        Ok(Some(format!("{} {}", version, cipher).trim().to_string()))
+    }
+
     async fn get_current_user(&self) -> Result<String> {
         let row = sqlx::query("SELECT USER() as user").fetch_one(&self.pool).await?;
         Ok(row.get::<String, _>("user"))
@@ -0,0 +1,34 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use crate::db::adapter::DbAdapter;
+use crate::models::{Database, ExecResult, SchemaColumn, Table};
+
+// 无数据库连接的适配器（只读快照模式）：元数据为空，执行 SQL 一律报错
+pub struct OfflineAdapter;
+
+impl OfflineAdapter {
+    fn unavailable() -> anyhow::Error {
+        anyhow!("只读快照模式，没有数据库连接")
+    }
+}
+
+#[async_trait]
+impl DbAdapter for OfflineAdapter {
+    fn driver_name(&self) -> &'static str { "snapshot" }
+    fn keywords(&self) -> &'static [&'static str] { &[] }
+    fn system_databases(&self) -> &'static [&'static str] { &[] }
+    fn supports_use_database(&self) -> bool { false }
+
+    async fn get_databases(&self) -> Result<Vec<Database>> { Ok(Vec::new()) }
+    async fn get_tables(&self, _database_name: &str) -> Result<Vec<Table>> { Ok(Vec::new()) }
+    async fn get_table_schema(&self, _database_name: &str, _table_name: &str) -> Result<(Vec<SchemaColumn>, Option<String>)> {
+        Err(Self::unavailable())
+    }
+    async fn execute_query_raw(&self, _query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        Err(Self::unavailable())
+    }
+    async fn execute_non_query(&self, _query: &str) -> Result<ExecResult> { Err(Self::unavailable()) }
+    async fn get_version(&self) -> Result<String> { Err(Self::unavailable()) }
+    async fn get_current_user(&self) -> Result<String> { Err(Self::unavailable()) }
+}
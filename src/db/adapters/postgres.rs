@@ -1,18 +1,76 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres, Row, Column};
+use sqlx::{Pool, Postgres, Row, Column, Connection, Executor, Statement};
+use futures::TryStreamExt;
+use sqlx::postgres::{PgPoolOptions, PgRow};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::Sender;
 
-use crate::db::adapter::DbAdapter;
-use crate::models::{Database, SchemaColumn, Table};
+use crate::db::adapter::{generic_table_ddl, quote_qualified, DbAdapter, RowBatch, STREAM_BATCH_ROWS};
+use crate::db::adapters::bind::bind_params;
+use crate::db::adapters::session::{BackendGuard, Session, SessionConn};
+use crate::db::docs::DocEntry;
+use crate::models::{ColumnPrivileges, Database, ExecResult, ParamValue, SchemaColumn, Table};
+use crate::sql::Dialect;
 
 pub struct PostgresAdapter {
     pool: Pool<Postgres>,
+    // 用户语句的连接（事务中固定）
+    session: Session<Postgres>,
 }
 
 impl PostgresAdapter {
-    pub async fn new(dsn: &str) -> Result<Self> {
-        let pool = sqlx::PgPool::connect(dsn).await?;
-        Ok(Self { pool })
+    pub async fn new(dsn: &str, init_sql: &[String]) -> Result<Self> {
+        // 启动 SQL（如 SET search_path）在每个新连接上执行
+        let init_sql: Arc<Vec<String>> = Arc::new(init_sql.to_vec());
+        let pool = PgPoolOptions::new()
+            .after_connect(move |conn, _meta| {
+                let init_sql = init_sql.clone();
+                Box::pin(async move {
+                    for sql in init_sql.iter() {
+                        sqlx::query(sql.as_str()).execute(&mut *conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect(dsn)
+            .await?;
+        Ok(Self { session: Session::new(pool.clone()), pool })
+    }
+
+    // 记录执行用户语句的后端进程 ID，Ctrl+C 取消时据此 pg_cancel_backend；取不到时只能停止等待结果。
+    // 返回的守卫须持有到语句结束；事务固定的连接复用缓存的进程 ID
+    async fn track_backend(&self, conn: &mut SessionConn<'_, Postgres>) -> BackendGuard<'_> {
+        let pid = match self.session.cached_backend(conn) {
+            Some(pid) => Some(pid),
+            None => sqlx::query_scalar::<_, i32>("SELECT pg_backend_pid()").fetch_one(&mut **conn).await.ok().map(i64::from),
+        };
+        self.session.track_backend(conn, pid)
+    }
+
+    // 尝试多种常见类型转字符串
+    fn row_to_strings(row: &PgRow) -> Vec<String> {
+        let mut r = Vec::new();
+        for idx in 0..row.columns().len() {
+            if let Ok(v) = row.try_get::<String, _>(idx) { r.push(v); continue; }
+            if let Ok(v) = row.try_get::<i64, _>(idx) { r.push(v.to_string()); continue; }
+            if let Ok(v) = row.try_get::<f64, _>(idx) { r.push(v.to_string()); continue; }
+            if let Ok(v) = row.try_get::<bool, _>(idx) { r.push((if v {"1"} else {"0"}).to_string()); continue; }
+            if let Ok(v) = row.try_get::<chrono::NaiveDateTime, _>(idx) { r.push(v.format("%Y-%m-%d %H:%M:%S").to_string()); continue; }
+            if let Ok(v) = row.try_get::<chrono::NaiveDate, _>(idx) { r.push(v.format("%Y-%m-%d").to_string()); continue; }
+            if let Ok(v) = row.try_get::<chrono::NaiveTime, _>(idx) { r.push(v.format("%H:%M:%S").to_string()); continue; }
+            if let Ok(v) = row.try_get::<serde_json::Value, _>(idx) { r.push(v.to_string()); continue; }
+            r.push("NULL".to_string());
+        }
+        r
+    }
+
+    // 供同协议的方言适配器（Redshift）复用连接池
+    pub(super) fn pool(&self) -> &Pool<Postgres> {
+        &self.pool
     }
 }
 
@@ -26,7 +84,8 @@ impl DbAdapter for PostgresAdapter {
             "ALTER","SHOW","EXPLAIN","JOIN","LEFT","RIGHT","INNER","OUTER","ON",
             "GROUP","BY","ORDER","HAVING","LIMIT","OFFSET","DISTINCT","COUNT","SUM",
             "AVG","MIN","MAX","AND","OR","NOT","IN","LIKE","BETWEEN","IS","NULL",
-            "TRUE","FALSE","ASC","DESC","AS","UNION","ALL","EXISTS","TABLE","SCHEMA"
+            "TRUE","FALSE","ASC","DESC","AS","UNION","ALL","EXISTS","TABLE","SCHEMA",
+            "ILIKE","RETURNING","COALESCE","NULLIF","CAST","STRING_AGG","NOW","TO_CHAR","GENERATE_SERIES"
         ]
     }
 
@@ -35,6 +94,30 @@ impl DbAdapter for PostgresAdapter {
     fn supports_use_database(&self) -> bool { false }
 
     fn quote_ident(&self, ident: &str) -> String { format!("\"{}\"", ident.replace('"', "\"\"")) }
+    fn quote_table(&self, table: &str) -> String { quote_qualified(self, table) }
+
+    // pg_hint_plan 扩展的提示名
+    fn optimizer_hints(&self) -> &'static [&'static str] {
+        &[
+            "SeqScan", "NoSeqScan", "IndexScan", "NoIndexScan", "IndexOnlyScan", "NoIndexOnlyScan",
+            "BitmapScan", "NoBitmapScan", "TidScan", "NoTidScan", "NestLoop", "NoNestLoop",
+            "HashJoin", "NoHashJoin", "MergeJoin", "NoMergeJoin", "Leading", "Rows", "Parallel",
+            "Set", "Memoize", "NoMemoize",
+        ]
+    }
+
+    fn keyword_docs(&self) -> &'static [DocEntry] {
+        &[
+            ("LIMIT", "LIMIT n | FETCH FIRST n ROWS ONLY", "限制返回行数"),
+            ("STRING_AGG", "STRING_AGG(expr, ',' [ORDER BY ...])", "将分组内的值拼接为字符串"),
+            ("NOW", "NOW()", "当前事务开始时间（timestamptz）"),
+            ("TO_CHAR", "TO_CHAR(ts, 'YYYY-MM-DD')", "按格式输出日期/数字"),
+            ("ILIKE", "expr ILIKE 'pattern'", "大小写不敏感的模式匹配"),
+            ("RETURNING", "INSERT|UPDATE|DELETE ... RETURNING cols", "返回被修改行的列值"),
+            ("SCHEMA", "CREATE SCHEMA name | SET search_path TO s", "模式（命名空间）"),
+            ("GENERATE_SERIES", "GENERATE_SERIES(start, stop [, step])", "生成连续序列的集合函数"),
+        ]
+    }
 
     async fn get_databases(&self) -> Result<Vec<Database>> {
         let rows = sqlx::query(
@@ -49,14 +132,18 @@ impl DbAdapter for PostgresAdapter {
     }
 
     async fn get_tables(&self, database_name: &str) -> Result<Vec<Table>> {
-        // 在 PostgreSQL 中，表属于 schema。默认使用 public schema。
+        // 在 PostgreSQL 中，表属于 schema：列出 search_path 中各 schema 的表（与不加限定名查询时的解析一致）；
+        // 同名表中 search_path 靠前的一个显示为表名，被它遮蔽的显示为 schema.表名
         let _ = database_name; // 已通过 DSN 指定数据库
         let rows = sqlx::query(
             r#"
-            SELECT tablename AS name
-            FROM pg_catalog.pg_tables
-            WHERE schemaname = 'public'
-            ORDER BY tablename
+            SELECT CASE
+                WHEN row_number() OVER (PARTITION BY t.tablename ORDER BY s.pos) = 1 THEN t.tablename
+                ELSE t.schemaname || '.' || t.tablename
+            END AS name
+            FROM pg_catalog.pg_tables t
+            JOIN unnest(current_schemas(false)) WITH ORDINALITY AS s(schema, pos) ON s.schema = t.schemaname
+            ORDER BY t.tablename, s.pos
             "#
         ).fetch_all(&self.pool).await?;
         let mut v = Vec::new();
@@ -73,10 +160,10 @@ impl DbAdapter for PostgresAdapter {
             SELECT obj_description(pg_class.oid) AS comment
             FROM pg_class
             JOIN pg_namespace ON pg_namespace.oid = pg_class.relnamespace
-            WHERE pg_class.relkind = 'r' AND pg_namespace.nspname = 'public' AND pg_class.relname = $1
+            WHERE pg_class.relkind = 'r' AND pg_class.oid = to_regclass($1)
             "#
         )
-        .bind(table_name)
+        .bind(self.quote_table(table_name))
         .fetch_optional(&self.pool)
         .await?;
         let table_comment: Option<String> = comment_row
@@ -96,12 +183,12 @@ impl DbAdapter for PostgresAdapter {
             JOIN pg_type t ON a.atttypid = t.oid
             LEFT JOIN pg_attrdef ad ON a.attrelid = ad.adrelid AND a.attnum = ad.adnum
             WHERE a.attnum > 0 AND NOT a.attisdropped
-              AND c.relname = $1
+              AND c.oid = to_regclass($1)
               AND c.relkind = 'r'
             ORDER BY a.attnum
             "#
         )
-        .bind(table_name)
+        .bind(self.quote_table(table_name))
         .fetch_all(&self.pool)
         .await?;
 
@@ -117,34 +204,198 @@ impl DbAdapter for PostgresAdapter {
         Ok((cols, table_comment))
     }
 
-    async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
-        let rows = sqlx::query(query).fetch_all(&self.pool).await?;
-        if rows.is_empty() { return Ok((Vec::new(), Vec::new())); }
-        let cols = rows[0].columns();
-        let headers: Vec<String> = cols.iter().map(|c| c.name().to_string()).collect();
-        let mut data_rows = Vec::new();
+    async fn get_primary_key(&self, _database_name: &str, table_name: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.attname AS name
+            FROM pg_index i
+            JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
+            WHERE i.indisprimary AND i.indrelid = to_regclass($1)
+            ORDER BY array_position(i.indkey::int2[], a.attnum)
+            "#
+        )
+        .bind(self.quote_table(table_name))
+        .fetch_all(&self.pool)
+        .await?;
+        let mut columns = Vec::new();
         for row in rows {
-            let mut r = Vec::new();
-            for (idx, _c) in row.columns().iter().enumerate() {
-                // 尝试多种常见类型转字符串
-                if let Ok(v) = row.try_get::<String, _>(idx) { r.push(v); continue; }
-                if let Ok(v) = row.try_get::<i64, _>(idx) { r.push(v.to_string()); continue; }
-                if let Ok(v) = row.try_get::<f64, _>(idx) { r.push(v.to_string()); continue; }
-                if let Ok(v) = row.try_get::<bool, _>(idx) { r.push((if v {"1"} else {"0"}).to_string()); continue; }
-                if let Ok(v) = row.try_get::<chrono::NaiveDateTime, _>(idx) { r.push(v.format("%Y-%m-%d %H:%M:%S").to_string()); continue; }
-                if let Ok(v) = row.try_get::<chrono::NaiveDate, _>(idx) { r.push(v.format("%Y-%m-%d").to_string()); continue; }
-                if let Ok(v) = row.try_get::<chrono::NaiveTime, _>(idx) { r.push(v.format("%H:%M:%S").to_string()); continue; }
-                if let Ok(v) = row.try_get::<serde_json::Value, _>(idx) { r.push(v.to_string()); continue; }
-                r.push("NULL".to_string());
-            }
-            data_rows.push(r);
+            columns.push(row.try_get::<String, _>("name")?);
+        }
+        Ok(columns)
+    }
+
+    // 视图按 pg_views 的定义生成 CREATE VIEW，表按结构与主键拼出
+    async fn table_ddl(&self, database_name: &str, table_name: &str) -> Result<String> {
+        // 与查询时一样按 search_path 解析不带 schema 的名称
+        let definition: Option<String> = sqlx::query_scalar(
+            "SELECT pg_get_viewdef(c.oid) FROM pg_catalog.pg_class c WHERE c.oid = to_regclass($1) AND c.relkind = 'v'",
+        )
+        .bind(self.quote_table(table_name))
+        .fetch_optional(&self.pool)
+        .await?;
+        match definition {
+            Some(definition) => Ok(format!(
+                "CREATE VIEW {} AS\n{}",
+                self.quote_table(table_name),
+                definition.trim().trim_end_matches(';')
+            )),
+            None => generic_table_ddl(self, database_name, table_name).await,
+        }
+    }
+
+    async fn get_views(&self, _database_name: &str) -> Result<Vec<String>> {
+        let views: Vec<String> = sqlx::query_scalar(
+            "SELECT DISTINCT viewname FROM pg_catalog.pg_views WHERE schemaname = ANY(current_schemas(false)) ORDER BY viewname",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(views)
+    }
+
+    // 表按 search_path 解析（与浏览数据时的 SELECT 一致），has_*_privilege 已计入经角色继承的权限
+    async fn get_column_privileges(&self, _database_name: &str, table_name: &str, _columns: &[String]) -> Result<HashMap<String, ColumnPrivileges>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                a.attname AS name,
+                has_column_privilege(a.attrelid, a.attnum, 'SELECT') AS can_select,
+                has_column_privilege(a.attrelid, a.attnum, 'INSERT') AS can_insert,
+                has_column_privilege(a.attrelid, a.attnum, 'UPDATE') AS can_update
+            FROM pg_attribute a
+            WHERE a.attrelid = to_regclass($1) AND a.attnum > 0 AND NOT a.attisdropped
+            "#
+        )
+        .bind(self.quote_table(table_name))
+        .fetch_all(&self.pool)
+        .await?;
+        let mut result = HashMap::new();
+        for row in rows {
+            let name: String = row.try_get("name")?;
+            result.insert(name, ColumnPrivileges {
+                select: row.try_get("can_select").unwrap_or(false),
+                insert: row.try_get("can_insert").unwrap_or(false),
+                update: row.try_get("can_update").unwrap_or(false),
+            });
         }
+        Ok(result)
+    }
+
+    async fn get_unreadable_tables(&self, _database_name: &str, tables: &[String]) -> Result<Vec<String>> {
+        let idents: Vec<String> = tables.iter().map(|t| self.quote_table(t)).collect();
+        let names: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT t.name
+            FROM unnest($1::text[], $2::text[]) AS t(name, ident)
+            WHERE to_regclass(t.ident) IS NOT NULL
+              AND NOT has_table_privilege(to_regclass(t.ident), 'SELECT')
+              AND NOT has_any_column_privilege(to_regclass(t.ident), 'SELECT')
+            "#
+        )
+        .bind(tables)
+        .bind(&idents)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(names)
+    }
+
+    async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        self.query_with_params(query, &[]).await
+    }
+
+    fn dialect(&self) -> Dialect { Dialect::Postgres }
+    fn supports_params(&self) -> bool { true }
+
+    fn param_placeholder(&self, index: usize) -> String { format!("${}", index + 1) }
+
+    async fn query_with_params(&self, query: &str, params: &[ParamValue]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let mut conn = self.session.acquire().await?;
+        let _backend = self.track_backend(&mut conn).await;
+        // 列名取自预编译语句，结果为空时也能显示表头
+        let statement = (&mut *conn).prepare(query).await?;
+        let headers: Vec<String> = statement.columns().iter().map(|c| c.name().to_string()).collect();
+        let rows = bind_params(statement.query(), params).fetch_all(&mut *conn).await?;
+        let data_rows = rows.iter().map(Self::row_to_strings).collect();
         Ok((headers, data_rows))
     }
 
-    async fn execute_non_query(&self, query: &str) -> Result<u64> {
-        let result = sqlx::query(query).execute(&self.pool).await?;
-        Ok(result.rows_affected())
+    async fn stream_query(&self, query: &str, tx: Sender<RowBatch>) -> Result<()> {
+        let mut conn = self.session.acquire().await?;
+        let _backend = self.track_backend(&mut conn).await;
+        // 先发送预编译语句的列名，再逐批发送行；没有结果行时也有表头
+        let statement = (&mut *conn).prepare(query).await?;
+        let headers = statement.columns().iter().map(|c| c.name().to_string()).collect();
+        if tx.send(RowBatch::Columns(headers)).await.is_err() { return Ok(()); }
+        let mut rows = statement.query().fetch(&mut *conn);
+        let mut batch = Vec::new();
+        while let Some(row) = rows.try_next().await? {
+            batch.push(Self::row_to_strings(&row));
+            if batch.len() >= STREAM_BATCH_ROWS && tx.send(RowBatch::Rows(std::mem::take(&mut batch))).await.is_err() {
+                return Ok(());
+            }
+        }
+        if !batch.is_empty() {
+            let _ = tx.send(RowBatch::Rows(batch)).await;
+        }
+        Ok(())
+    }
+
+    async fn execute_non_query(&self, query: &str) -> Result<ExecResult> {
+        self.execute_with_params(query, &[]).await
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[ParamValue]) -> Result<ExecResult> {
+        let mut conn = self.session.acquire().await?;
+        let _backend = self.track_backend(&mut conn).await;
+        let result = bind_params(sqlx::query(query), params).execute(&mut *conn).await?;
+        Ok(ExecResult::affected(result.rows_affected()))
+    }
+
+    fn supports_transactions(&self) -> bool { true }
+
+    async fn begin_transaction(&self, statement: &str) -> Result<()> {
+        self.session.begin(statement).await
+    }
+
+    async fn end_transaction(&self, statement: &str) -> Result<()> {
+        self.session.end(statement).await
+    }
+
+    // pg_cancel_backend 只中止当前语句；事务中被中止的语句使事务进入失败状态，需 ROLLBACK
+    async fn cancel_running(&self) -> Result<()> {
+        if let Some(pid) = self.session.take_backend() {
+            sqlx::query("SELECT pg_cancel_backend($1)").bind(pid as i32).execute(&self.pool).await?;
+        }
+        Ok(())
+    }
+
+    fn supports_copy(&self) -> bool { true }
+
+    async fn copy_out_csv(&self, query: &str, path: &Path) -> Result<u64> {
+        let mut conn = self.session.acquire().await?;
+        let statement = format!("COPY ({}) TO STDOUT WITH (FORMAT csv, HEADER true)", query);
+        let mut stream = conn.copy_out_raw(&statement).await?;
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut written = 0u64;
+        while let Some(chunk) = stream.try_next().await? {
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(written)
+    }
+
+    async fn copy_in_csv(&self, table: &str, columns: &[String], data: Vec<u8>) -> Result<u64> {
+        let mut conn = self.session.acquire().await?;
+        let table = self.quote_table(table);
+        let columns = columns.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", ");
+        let statement = format!("COPY {} ({}) FROM STDIN WITH (FORMAT csv)", table, columns);
+        let mut copy = conn.copy_in_raw(&statement).await?;
+        // 发送失败时显式中止本次 COPY，连接可继续使用
+        if let Err(e) = copy.send(data).await {
+            let _ = copy.abort(e.to_string()).await;
+            return Err(e.into());
+        }
+        Ok(copy.finish().await?)
     }
 
     async fn get_version(&self) -> Result<String> {
@@ -153,6 +404,24 @@ impl DbAdapter for PostgresAdapter {
         Ok(v)
     }
 
+    async fn ping(&self) -> Result<()> {
+        self.pool.acquire().await?.ping().await?;
+        Ok(())
+    }
+
+    async fn get_tls_status(&self) -> Result<Option<String>> {
+        let row = sqlx::query("SELECT ssl, version, cipher FROM pg_stat_ssl WHERE pid = pg_backend_pid()")
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some(row) = row else { return Ok(None) };
+        if !row.try_get::<bool, _>("ssl").unwrap_or(false) {
+            return Ok(Some("off".to_string()));
+        }
+        let version: String = row.try_get("version").unwrap_or_default();
+        let cipher: String = row.try_get("cipher").unwrap_or_default();
+        Ok(Some(format!("{} {}", version, cipher).trim().to_string()))
+    }
+
     async fn get_current_user(&self) -> Result<String> {
         // SHOW user 在 PG 不适用，使用 current_user
         let row = sqlx::query("SELECT current_user AS usr").fetch_one(&self.pool).await?;
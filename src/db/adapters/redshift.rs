@@ -0,0 +1,215 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+use tokio::sync::mpsc::Sender;
+
+use crate::db::adapter::{DbAdapter, RowBatch};
+use crate::db::adapters::postgres::PostgresAdapter;
+use crate::db::docs::DocEntry;
+use crate::models::{Database, ExecResult, ParamValue, SchemaColumn, Table};
+use crate::sql::Dialect;
+
+// Amazon Redshift：沿用 PostgreSQL 连接与查询执行，元数据改用 Redshift 系统视图
+// （Redshift 不支持 format_type / pg_get_expr / array_position 等 pg_catalog 用法）
+pub struct RedshiftAdapter {
+    inner: PostgresAdapter,
+}
+
+impl RedshiftAdapter {
+    pub async fn new(dsn: &str, init_sql: &[String]) -> Result<Self> {
+        Ok(Self { inner: PostgresAdapter::new(dsn, init_sql).await? })
+    }
+}
+
+#[async_trait]
+impl DbAdapter for RedshiftAdapter {
+    fn driver_name(&self) -> &'static str { "Redshift" }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "SELECT","FROM","WHERE","INSERT","UPDATE","DELETE","CREATE","DROP",
+            "ALTER","SHOW","EXPLAIN","JOIN","LEFT","RIGHT","INNER","OUTER","ON",
+            "GROUP","BY","ORDER","HAVING","LIMIT","OFFSET","DISTINCT","COUNT","SUM",
+            "AVG","MIN","MAX","AND","OR","NOT","IN","LIKE","BETWEEN","IS","NULL",
+            "TRUE","FALSE","ASC","DESC","AS","UNION","ALL","EXISTS","TABLE","SCHEMA",
+            "ILIKE","COALESCE","NVL","NULLIF","CAST","DECODE","LISTAGG","GETDATE","SYSDATE",
+            "DATEADD","DATEDIFF","DATE_TRUNC","TO_CHAR","APPROXIMATE","DISTKEY","SORTKEY",
+            "DISTSTYLE","ENCODE","COPY","UNLOAD","VACUUM","ANALYZE","IAM_ROLE"
+        ]
+    }
+
+    fn system_databases(&self) -> &'static [&'static str] { &["padb_harvest", "template0", "template1"] }
+
+    fn supports_use_database(&self) -> bool { false }
+
+    fn quote_ident(&self, ident: &str) -> String { self.inner.quote_ident(ident) }
+    fn quote_table(&self, table: &str) -> String { self.inner.quote_table(table) }
+    fn quote_literal(&self, value: &str) -> String { self.inner.quote_literal(value) }
+    fn max_insert_rows(&self) -> usize { self.inner.max_insert_rows() }
+
+    fn keyword_docs(&self) -> &'static [DocEntry] {
+        &[
+            ("LISTAGG", "LISTAGG(expr, ',') WITHIN GROUP (ORDER BY ...)", "将分组内的值拼接为字符串"),
+            ("GETDATE", "GETDATE()", "当前时间（不含时区）"),
+            ("DATEADD", "DATEADD(day, n, ts)", "日期加减"),
+            ("DATEDIFF", "DATEDIFF(day, start, end)", "两个日期的差值"),
+            ("DISTKEY", "CREATE TABLE ... DISTKEY(col)", "按列分布数据到各节点"),
+            ("SORTKEY", "CREATE TABLE ... [COMPOUND|INTERLEAVED] SORTKEY(cols)", "表的排序键"),
+            ("COPY", "COPY t FROM 's3://...' IAM_ROLE '...' FORMAT AS PARQUET", "从 S3 批量导入"),
+            ("UNLOAD", "UNLOAD ('SELECT ...') TO 's3://...' IAM_ROLE '...'", "把查询结果导出到 S3"),
+        ]
+    }
+
+    async fn get_databases(&self) -> Result<Vec<Database>> {
+        let rows = sqlx::query("SELECT datname FROM pg_database ORDER BY datname")
+            .fetch_all(self.inner.pool())
+            .await?;
+        let mut v = Vec::new();
+        for row in rows {
+            let name: String = row.try_get("datname")?;
+            v.push(Database::with_details(name, None, None, None));
+        }
+        Ok(v)
+    }
+
+    async fn get_tables(&self, _database_name: &str) -> Result<Vec<Table>> {
+        // SVV_TABLE_INFO 只列出已有数据块的表，空表需从 pg_tables 补齐
+        let rows = sqlx::query(
+            r#"
+            SELECT t.tablename::varchar AS name,
+                   i.tbl_rows::bigint AS row_count,
+                   i.size::bigint AS size_mb,
+                   i.diststyle::varchar AS diststyle
+            FROM pg_tables t
+            LEFT JOIN svv_table_info i ON i.schema = t.schemaname AND i."table" = t.tablename
+            WHERE t.schemaname = 'public'
+            ORDER BY t.tablename
+            "#
+        )
+        .fetch_all(self.inner.pool())
+        .await?;
+        let mut v = Vec::new();
+        for row in rows {
+            let name: String = row.try_get("name").unwrap_or_default();
+            let rows: Option<i64> = row.try_get("row_count").ok().flatten();
+            // size 以 1MB 数据块为单位
+            let size: Option<i64> = row.try_get("size_mb").ok().flatten();
+            let diststyle: Option<String> = row.try_get("diststyle").ok().flatten();
+            v.push(Table::with_details(
+                name,
+                None,
+                rows.map(|r| r.max(0) as u64),
+                size.map(|s| s.max(0) as u64 * 1024 * 1024),
+                diststyle,
+            ));
+        }
+        Ok(v)
+    }
+
+    async fn get_table_schema(&self, _database_name: &str, table_name: &str) -> Result<(Vec<SchemaColumn>, Option<String>)> {
+        // pg_table_def 只包含 search_path 中的 schema（默认 public）
+        let rows = sqlx::query(
+            r#"
+            SELECT "column"::varchar AS name,
+                   type::varchar AS data_type,
+                   encoding::varchar AS encoding,
+                   distkey,
+                   sortkey,
+                   notnull
+            FROM pg_table_def
+            WHERE schemaname = 'public' AND tablename = $1
+            "#
+        )
+        .bind(table_name)
+        .fetch_all(self.inner.pool())
+        .await?;
+        let mut cols = Vec::new();
+        for row in rows {
+            let name: String = row.try_get("name").unwrap_or_default();
+            let data_type: String = row.try_get("data_type").unwrap_or_default();
+            let not_null: bool = row.try_get("notnull").unwrap_or(false);
+            // 注释列展示分布键、排序键与压缩编码
+            let mut notes = Vec::new();
+            if row.try_get::<bool, _>("distkey").unwrap_or(false) {
+                notes.push("DISTKEY".to_string());
+            }
+            let sortkey: i32 = row.try_get("sortkey").unwrap_or(0);
+            if sortkey != 0 {
+                notes.push(format!("SORTKEY {}", sortkey));
+            }
+            if let Ok(encoding) = row.try_get::<String, _>("encoding") {
+                if encoding != "none" {
+                    notes.push(format!("ENCODE {}", encoding));
+                }
+            }
+            let comment = if notes.is_empty() { None } else { Some(notes.join(", ")) };
+            cols.push(SchemaColumn::with_details(name, data_type, !not_null, None, None, comment));
+        }
+        Ok((cols, None))
+    }
+
+    async fn get_primary_key(&self, _database_name: &str, table_name: &str) -> Result<Vec<String>> {
+        // 主键在 Redshift 中仅作信息约束
+        let rows = sqlx::query(
+            r#"
+            SELECT kcu.column_name::varchar AS name
+            FROM information_schema.table_constraints tc
+            JOIN information_schema.key_column_usage kcu
+              ON kcu.constraint_name = tc.constraint_name AND kcu.table_schema = tc.table_schema
+            WHERE tc.constraint_type = 'PRIMARY KEY' AND tc.table_schema = 'public' AND tc.table_name = $1
+            ORDER BY kcu.ordinal_position
+            "#
+        )
+        .bind(table_name)
+        .fetch_all(self.inner.pool())
+        .await?;
+        let mut columns = Vec::new();
+        for row in rows {
+            columns.push(row.try_get::<String, _>("name")?);
+        }
+        Ok(columns)
+    }
+
+    async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        self.inner.execute_query_raw(query).await
+    }
+
+    async fn stream_query(&self, query: &str, tx: Sender<RowBatch>) -> Result<()> {
+        self.inner.stream_query(query, tx).await
+    }
+
+    async fn execute_non_query(&self, query: &str) -> Result<ExecResult> {
+        self.inner.execute_non_query(query).await
+    }
+
+    fn dialect(&self) -> Dialect { Dialect::Postgres }
+    fn supports_params(&self) -> bool { true }
+
+    fn param_placeholder(&self, index: usize) -> String { self.inner.param_placeholder(index) }
+
+    async fn query_with_params(&self, query: &str, params: &[ParamValue]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        self.inner.query_with_params(query, params).await
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[ParamValue]) -> Result<ExecResult> {
+        self.inner.execute_with_params(query, params).await
+    }
+
+    fn supports_transactions(&self) -> bool { true }
+
+    async fn begin_transaction(&self, statement: &str) -> Result<()> {
+        self.inner.begin_transaction(statement).await
+    }
+
+    async fn end_transaction(&self, statement: &str) -> Result<()> {
+        self.inner.end_transaction(statement).await
+    }
+
+    async fn get_version(&self) -> Result<String> {
+        self.inner.get_version().await
+    }
+
+    async fn get_current_user(&self) -> Result<String> {
+        self.inner.get_current_user().await
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use sqlx::pool::PoolConnection;
+use sqlx::{Connection, Database, Executor, MySql, Pool, Postgres, Sqlite};
+use std::ops::{Deref, DerefMut};
+use tokio::sync::{Mutex, MutexGuard};
+
+// 连接池上执行用户语句的会话：事务进行中时固定一条连接，之后的用户语句都在该连接上执行，
+// 提交或回滚后归还连接池。元数据查询仍直接走连接池
+pub struct Session<DB: Database> {
+    pool: Pool<DB>,
+    pinned: Mutex<Option<PoolConnection<DB>>>,
+    // 正在执行的用户语句所在连接的服务端 ID（MySQL CONNECTION_ID / PostgreSQL pg_backend_pid），取消时据此中止服务端语句；
+    // 语句结束后由 BackendGuard 清除，以免取消误伤之后复用该连接的语句
+    backend: std::sync::Mutex<Option<i64>>,
+    // 固定连接的服务端 ID，事务内的语句不必每次查询
+    pinned_backend: std::sync::Mutex<Option<i64>>,
+}
+
+pub enum SessionConn<'a, DB: Database> {
+    // 持有期间其余用户语句等待，保证事务内语句按序执行
+    Pinned(MutexGuard<'a, Option<PoolConnection<DB>>>),
+    Pooled(PoolConnection<DB>),
+}
+
+impl<DB: Database> Session<DB> {
+    pub fn new(pool: Pool<DB>) -> Self {
+        Self {
+            pool,
+            pinned: Mutex::new(None),
+            backend: std::sync::Mutex::new(None),
+            pinned_backend: std::sync::Mutex::new(None),
+        }
+    }
+
+    // 连接为事务固定的连接时返回缓存的服务端 ID
+    pub fn cached_backend(&self, conn: &SessionConn<'_, DB>) -> Option<i64> {
+        match conn {
+            SessionConn::Pinned(_) => *self.pinned_backend.lock().unwrap(),
+            SessionConn::Pooled(_) => None,
+        }
+    }
+
+    // 记录语句所在连接的服务端 ID；固定的连接同时缓存到事务结束
+    pub fn track_backend(&self, conn: &SessionConn<'_, DB>, id: Option<i64>) -> BackendGuard<'_> {
+        if matches!(conn, SessionConn::Pinned(_)) {
+            *self.pinned_backend.lock().unwrap() = id;
+        }
+        *self.backend.lock().unwrap() = id;
+        BackendGuard { backend: &self.backend, id }
+    }
+
+    // 取走记录的服务端 ID，同一条语句只取消一次
+    pub fn take_backend(&self) -> Option<i64> {
+        self.backend.lock().unwrap().take()
+    }
+
+    // 事务进行中返回固定的连接，否则从连接池取一条
+    pub async fn acquire(&self) -> Result<SessionConn<'_, DB>> {
+        let pinned = self.pinned.lock().await;
+        if pinned.is_some() {
+            return Ok(SessionConn::Pinned(pinned));
+        }
+        drop(pinned);
+        Ok(SessionConn::Pooled(self.pool.acquire().await?))
+    }
+}
+
+// BEGIN / COMMIT 在各数据库的具体连接类型上执行：泛型的 DB::Connection 在 async_trait 方法中
+// 满足不了 Executor 的生命周期约束；raw_sql(..).execute(&mut *conn) 同样不满足，改用连接上的 Executor::execute
+macro_rules! impl_transactions {
+    ($($db:ty),*) => {$(
+        impl Session<$db> {
+            // 执行 BEGIN / START TRANSACTION 并固定所用连接；已在事务中时沿用固定的连接
+            pub async fn begin(&self, statement: &str) -> Result<()> {
+                let mut pinned = self.pinned.lock().await;
+                let mut conn = match pinned.take() {
+                    Some(conn) => conn,
+                    None => {
+                        *self.pinned_backend.lock().unwrap() = None;
+                        self.pool.acquire().await?
+                    }
+                };
+                conn.execute(statement).await?;
+                *pinned = Some(conn);
+                Ok(())
+            }
+
+            // 在固定的连接上执行 COMMIT / ROLLBACK 并归还连接；失败时关闭该连接，
+            // 避免把仍处于事务中的连接放回连接池
+            pub async fn end(&self, statement: &str) -> Result<()> {
+                let pinned = self.pinned.lock().await.take();
+                *self.pinned_backend.lock().unwrap() = None;
+                let Some(mut conn) = pinned else {
+                    sqlx::raw_sql(statement).execute(&self.pool).await?;
+                    return Ok(());
+                };
+                if let Err(e) = conn.execute(statement).await {
+                    let _ = conn.detach().close().await;
+                    return Err(e.into());
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_transactions!(MySql, Postgres, Sqlite);
+
+// 用户语句执行期间持有；语句结束或被丢弃（取消、超时）时清除记录的服务端 ID
+pub struct BackendGuard<'a> {
+    backend: &'a std::sync::Mutex<Option<i64>>,
+    id: Option<i64>,
+}
+
+impl Drop for BackendGuard<'_> {
+    fn drop(&mut self) {
+        let mut backend = self.backend.lock().unwrap();
+        // 并发的其他语句已改写记录时保留
+        if *backend == self.id {
+            *backend = None;
+        }
+    }
+}
+
+impl<DB: Database> Deref for SessionConn<'_, DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &DB::Connection {
+        match self {
+            Self::Pinned(pinned) => pinned.as_deref().expect("固定的连接"),
+            Self::Pooled(conn) => conn,
+        }
+    }
+}
+
+impl<DB: Database> DerefMut for SessionConn<'_, DB> {
+    fn deref_mut(&mut self) -> &mut DB::Connection {
+        match self {
+            Self::Pinned(pinned) => pinned.as_deref_mut().expect("固定的连接"),
+            Self::Pooled(conn) => conn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn backend_is_cleared_after_statement_and_cached_while_pinned() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        let session = Session::new(pool);
+
+        let conn = session.acquire().await.unwrap();
+        let guard = session.track_backend(&conn, Some(1));
+        assert_eq!(session.cached_backend(&conn), None);
+        drop(guard);
+        assert_eq!(session.take_backend(), None);
+        drop(conn);
+
+        session.begin("BEGIN").await.unwrap();
+        let conn = session.acquire().await.unwrap();
+        let guard = session.track_backend(&conn, Some(2));
+        assert_eq!(session.take_backend(), Some(2));
+        drop(guard);
+        assert_eq!(session.cached_backend(&conn), Some(2));
+        drop(conn);
+
+        session.end("COMMIT").await.unwrap();
+        let conn = session.acquire().await.unwrap();
+        assert_eq!(session.cached_backend(&conn), None);
+    }
+}
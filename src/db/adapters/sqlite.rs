@@ -0,0 +1,233 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{Column, Pool, Row, Sqlite};
+use futures::TryStreamExt;
+use sqlx::sqlite::SqlitePoolOptions;
+use std::sync::Arc;
+use tokio::sync::mpsc::Sender;
+
+use crate::db::adapter::{DbAdapter, RowBatch, STREAM_BATCH_ROWS};
+use crate::db::adapters::bind::bind_params;
+use crate::db::adapters::session::Session;
+use crate::sql;
+use crate::models::{Database, ExecResult, ParamValue, SchemaColumn, Table};
+
+pub struct SqliteAdapter {
+    pool: Pool<Sqlite>,
+    // 用户语句的连接（事务中固定）
+    session: Session<Sqlite>,
+    in_memory: bool,
+}
+
+impl SqliteAdapter {
+    pub async fn new(dsn: &str, init_sql: &[String]) -> Result<Self> {
+        // 启动 SQL（如 PRAGMA foreign_keys = ON）在每个新连接上执行
+        let init_sql: Arc<Vec<String>> = Arc::new(init_sql.to_vec());
+        let pool = SqlitePoolOptions::new()
+            .after_connect(move |conn, _meta| {
+                let init_sql = init_sql.clone();
+                Box::pin(async move {
+                    for sql in init_sql.iter() {
+                        sqlx::query(sql.as_str()).execute(&mut *conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect(dsn)
+            .await?;
+        let in_memory = dsn.contains(":memory:") || dsn.contains("mode=memory");
+        Ok(Self { session: Session::new(pool.clone()), pool, in_memory })
+    }
+
+    fn get_cell_value_as_string(row: &sqlx::sqlite::SqliteRow, index: usize) -> String {
+        if let Ok(v) = row.try_get::<String, _>(index) { return v; }
+        if let Ok(v) = row.try_get::<i64, _>(index) { return v.to_string(); }
+        if let Ok(v) = row.try_get::<f64, _>(index) { return v.to_string(); }
+        if let Ok(v) = row.try_get::<Vec<u8>, _>(index) { return String::from_utf8_lossy(&v).to_string(); }
+        "NULL".into()
+    }
+}
+
+#[async_trait]
+impl DbAdapter for SqliteAdapter {
+    fn driver_name(&self) -> &'static str { "SQLite" }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "SELECT","FROM","WHERE","INSERT","UPDATE","DELETE","CREATE","DROP",
+            "ALTER","EXPLAIN","JOIN","LEFT","INNER","CROSS","ON","USING",
+            "GROUP","BY","ORDER","HAVING","LIMIT","OFFSET","DISTINCT","COUNT","SUM",
+            "AVG","MIN","MAX","AND","OR","NOT","IN","LIKE","GLOB","BETWEEN","IS","NULL",
+            "ASC","DESC","AS","UNION","ALL","EXISTS","TABLE","VIEW","INDEX","PRAGMA",
+            "ATTACH","DETACH","VACUUM","REPLACE","COALESCE","IFNULL","NULLIF","CAST",
+            "GROUP_CONCAT","STRFTIME","DATETIME","JSON_EXTRACT"
+        ]
+    }
+
+    fn system_databases(&self) -> &'static [&'static str] { &["temp"] }
+
+    // 单文件库：库列表为 PRAGMA database_list（main 及 ATTACH 的库），无需切换连接
+    fn supports_use_database(&self) -> bool { false }
+
+    fn quote_ident(&self, ident: &str) -> String { format!("\"{}\"", ident.replace('"', "\"\"")) }
+
+    async fn get_databases(&self) -> Result<Vec<Database>> {
+        let rows = sqlx::query("PRAGMA database_list").fetch_all(&self.pool).await?;
+        let mut dbs = Vec::new();
+        for row in rows {
+            let name: String = row.try_get("name").unwrap_or_default();
+            let count_sql = format!(
+                "SELECT COUNT(*) FROM {}.sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+                self.quote_ident(&name)
+            );
+            let table_count = sqlx::query(&count_sql)
+                .fetch_one(&self.pool)
+                .await
+                .ok()
+                .and_then(|r| r.try_get::<i64, _>(0).ok())
+                .map(|n| n as u64);
+            dbs.push(Database::with_details(name, Some("UTF-8".to_string()), None, table_count));
+        }
+        Ok(dbs)
+    }
+
+    async fn get_tables(&self, database_name: &str) -> Result<Vec<Table>> {
+        let sql = format!(
+            "SELECT name, type FROM {}.sqlite_master WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' ORDER BY name",
+            self.quote_ident(database_name)
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+        let mut v = Vec::new();
+        for row in rows {
+            let name: String = row.try_get("name").unwrap_or_default();
+            let kind: String = row.try_get("type").unwrap_or_default();
+            let comment = if kind == "view" { Some("VIEW".to_string()) } else { None };
+            v.push(Table::with_details(name, comment, None, None, None));
+        }
+        Ok(v)
+    }
+
+    async fn get_table_schema(&self, database_name: &str, table_name: &str) -> Result<(Vec<SchemaColumn>, Option<String>)> {
+        let sql = format!(
+            "PRAGMA {}.table_info({})",
+            self.quote_ident(database_name),
+            self.quote_ident(table_name)
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+        let mut cols = Vec::new();
+        for row in rows {
+            let name: String = row.try_get("name").unwrap_or_default();
+            let data_type: String = row.try_get("type").unwrap_or_default();
+            let not_null: i64 = row.try_get("notnull").unwrap_or(0);
+            let default_value: Option<String> = row.try_get::<Option<String>, _>("dflt_value").unwrap_or(None);
+            let pk: i64 = row.try_get("pk").unwrap_or(0);
+            let extra = if pk > 0 { Some("PRIMARY KEY".to_string()) } else { None };
+            cols.push(SchemaColumn::with_details(name, data_type, not_null == 0, default_value, extra, None));
+        }
+        Ok((cols, None))
+    }
+
+    async fn table_ddl(&self, database_name: &str, table_name: &str) -> Result<String> {
+        let sql = format!("SELECT sql FROM {}.sqlite_master WHERE name = ?", self.quote_ident(database_name));
+        let row = sqlx::query(&sql).bind(table_name).fetch_optional(&self.pool).await?;
+        row.and_then(|row| row.try_get::<Option<String>, _>("sql").ok().flatten())
+            .ok_or_else(|| anyhow::anyhow!("表 {} 不存在", table_name))
+    }
+
+    async fn get_primary_key(&self, database_name: &str, table_name: &str) -> Result<Vec<String>> {
+        let sql = format!(
+            "PRAGMA {}.table_info({})",
+            self.quote_ident(database_name),
+            self.quote_ident(table_name)
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+        let mut keys: Vec<(i64, String)> = rows
+            .iter()
+            .filter_map(|row| {
+                let pk: i64 = row.try_get("pk").unwrap_or(0);
+                (pk > 0).then(|| (pk, row.try_get("name").unwrap_or_default()))
+            })
+            .collect();
+        keys.sort_by_key(|(pk, _)| *pk);
+        Ok(keys.into_iter().map(|(_, name)| name).collect())
+    }
+
+    async fn execute_query_raw(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        self.query_with_params(query, &[]).await
+    }
+
+    fn supports_params(&self) -> bool { true }
+
+    async fn query_with_params(&self, query: &str, params: &[ParamValue]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let mut conn = self.session.acquire().await?;
+        let rows = bind_params(sqlx::query(query), params).fetch_all(&mut *conn).await?;
+        if rows.is_empty() { return Ok((Vec::new(), Vec::new())); }
+        let headers: Vec<String> = rows[0].columns().iter().map(|c| c.name().to_string()).collect();
+        let data_rows = rows
+            .iter()
+            .map(|row| (0..row.columns().len()).map(|idx| Self::get_cell_value_as_string(row, idx)).collect())
+            .collect();
+        Ok((headers, data_rows))
+    }
+
+    async fn stream_query(&self, query: &str, tx: Sender<RowBatch>) -> Result<()> {
+        let mut conn = self.session.acquire().await?;
+        let mut rows = sqlx::query(query).fetch(&mut *conn);
+        let mut batch = Vec::new();
+        let mut sent_columns = false;
+        while let Some(row) = rows.try_next().await? {
+            if !sent_columns {
+                let headers = row.columns().iter().map(|c| c.name().to_string()).collect();
+                if tx.send(RowBatch::Columns(headers)).await.is_err() { return Ok(()); }
+                sent_columns = true;
+            }
+            batch.push((0..row.columns().len()).map(|i| Self::get_cell_value_as_string(&row, i)).collect());
+            if batch.len() >= STREAM_BATCH_ROWS && tx.send(RowBatch::Rows(std::mem::take(&mut batch))).await.is_err() {
+                return Ok(());
+            }
+        }
+        if !batch.is_empty() {
+            let _ = tx.send(RowBatch::Rows(batch)).await;
+        }
+        Ok(())
+    }
+
+    async fn execute_non_query(&self, query: &str) -> Result<ExecResult> {
+        self.execute_with_params(query, &[]).await
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[ParamValue]) -> Result<ExecResult> {
+        let mut conn = self.session.acquire().await?;
+        let result = bind_params(sqlx::query(query), params).execute(&mut *conn).await?;
+        // last_insert_rowid 是连接级的，只有本条语句确实插入了行才有意义
+        let inserted = matches!(sql::first_keyword(query, sql::Dialect::Other).as_str(), "INSERT" | "REPLACE") && result.rows_affected() > 0;
+        Ok(ExecResult {
+            rows_affected: result.rows_affected(),
+            last_insert_id: inserted.then(|| result.last_insert_rowid() as u64),
+            warnings: 0,
+        })
+    }
+
+    fn supports_transactions(&self) -> bool { true }
+
+    fn in_memory(&self) -> bool { self.in_memory }
+
+    async fn begin_transaction(&self, statement: &str) -> Result<()> {
+        self.session.begin(statement).await
+    }
+
+    async fn end_transaction(&self, statement: &str) -> Result<()> {
+        self.session.end(statement).await
+    }
+
+    async fn get_version(&self) -> Result<String> {
+        let row = sqlx::query("SELECT sqlite_version() AS version").fetch_one(&self.pool).await?;
+        let v: String = row.try_get("version")?;
+        Ok(v)
+    }
+
+    // 本地文件没有用户概念
+    async fn get_current_user(&self) -> Result<String> {
+        Ok("local".to_string())
+    }
+}
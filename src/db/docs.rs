@@ -0,0 +1,64 @@
+// 内置的 SQL 关键字/函数简要文档（用于补全浮框旁的说明面板）
+
+#[derive(Debug, Clone, Copy)]
+pub struct KeywordDoc {
+    pub signature: &'static str,
+    pub description: &'static str,
+}
+
+// (名称, 签名, 说明)；名称统一大写
+pub type DocEntry = (&'static str, &'static str, &'static str);
+
+// 各驱动通用的关键字与函数
+pub const COMMON_DOCS: &[DocEntry] = &[
+    ("SELECT", "SELECT expr [, ...] FROM t [WHERE ...]", "查询数据，返回结果集"),
+    ("FROM", "FROM table [AS alias]", "指定查询的数据来源表"),
+    ("WHERE", "WHERE condition", "按条件过滤行"),
+    ("INSERT", "INSERT INTO t (cols) VALUES (...)", "插入新行"),
+    ("UPDATE", "UPDATE t SET col = expr [WHERE ...]", "更新满足条件的行"),
+    ("DELETE", "DELETE FROM t [WHERE ...]", "删除满足条件的行"),
+    ("CREATE", "CREATE TABLE|INDEX|VIEW|DATABASE ...", "创建数据库对象"),
+    ("DROP", "DROP TABLE|INDEX|VIEW|DATABASE name", "删除数据库对象（不可恢复）"),
+    ("ALTER", "ALTER TABLE t ADD|DROP|MODIFY ...", "修改已有对象的结构"),
+    ("USE", "USE db", "切换当前默认数据库"),
+    ("SHOW", "SHOW DATABASES|TABLES|COLUMNS ...", "查看服务器元数据"),
+    ("DESCRIBE", "DESCRIBE t", "查看表的列定义"),
+    ("DESC", "DESC t | ORDER BY col DESC", "查看表结构，或在排序中表示降序"),
+    ("EXPLAIN", "EXPLAIN statement", "查看语句的执行计划"),
+    ("JOIN", "t1 JOIN t2 ON cond", "按条件连接两张表"),
+    ("LEFT", "t1 LEFT JOIN t2 ON cond", "左外连接，保留左表所有行"),
+    ("RIGHT", "t1 RIGHT JOIN t2 ON cond", "右外连接，保留右表所有行"),
+    ("INNER", "t1 INNER JOIN t2 ON cond", "内连接，仅保留匹配行"),
+    ("ON", "JOIN t ON cond", "指定连接条件"),
+    ("GROUP", "GROUP BY expr [, ...]", "按表达式分组聚合"),
+    ("ORDER", "ORDER BY expr [ASC|DESC]", "对结果排序"),
+    ("HAVING", "HAVING condition", "对分组后的结果过滤"),
+    ("LIMIT", "LIMIT n [OFFSET m]", "限制返回行数"),
+    ("OFFSET", "OFFSET m", "跳过前 m 行"),
+    ("DISTINCT", "SELECT DISTINCT expr", "去除重复行"),
+    ("COUNT", "COUNT(expr | *)", "计数；COUNT(col) 不统计 NULL"),
+    ("SUM", "SUM(expr)", "求和，忽略 NULL"),
+    ("AVG", "AVG(expr)", "求平均值，忽略 NULL"),
+    ("MIN", "MIN(expr)", "求最小值，忽略 NULL"),
+    ("MAX", "MAX(expr)", "求最大值，忽略 NULL"),
+    ("IN", "expr IN (v1, v2, ...)", "判断值是否在列表或子查询中"),
+    ("LIKE", "expr LIKE 'pattern'", "模式匹配；% 任意串，_ 单个字符"),
+    ("BETWEEN", "expr BETWEEN a AND b", "闭区间范围判断"),
+    ("IS", "expr IS [NOT] NULL", "判断是否为 NULL"),
+    ("UNION", "q1 UNION [ALL] q2", "合并两个结果集；不带 ALL 时去重"),
+    ("EXISTS", "EXISTS (subquery)", "子查询有结果时为真"),
+    ("COALESCE", "COALESCE(v1, v2, ...)", "返回第一个非 NULL 参数"),
+    ("NULLIF", "NULLIF(a, b)", "a = b 时返回 NULL，否则返回 a"),
+    ("CAST", "CAST(expr AS type)", "类型转换"),
+];
+
+// 按驱动特有文档优先、通用文档兜底查找
+pub fn lookup(driver_docs: &[DocEntry], keyword: &str) -> Option<KeywordDoc> {
+    let upper = keyword.trim().to_uppercase();
+    if upper.is_empty() { return None; }
+    driver_docs
+        .iter()
+        .chain(COMMON_DOCS.iter())
+        .find(|(name, _, _)| *name == upper)
+        .map(|(_, signature, description)| KeywordDoc { signature, description })
+}
@@ -1,4 +1,8 @@
 mod adapter; // trait 与工厂
 mod adapters; // 各后端适配器实现
+pub mod docs; // 关键字/函数内置文档
+mod tunnel; // SSH 端口转发
 
-pub use adapter::{DbAdapter, new_adapter};
+pub use tunnel::SshTunnel;
+
+pub use adapter::{DbAdapter, RowBatch, is_connection_error, new_adapter, offline_adapter};
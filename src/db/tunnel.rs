@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Result};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStderr, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+
+// 通过系统 ssh 建立本地端口转发（ssh -L），密钥与 ssh-agent 均由 ssh 自身处理；
+// 进程随 SshTunnel 一起释放
+pub struct SshTunnel {
+    child: Child,
+    local_port: u16,
+    // ssh 的错误输出由后台线程持续读取（避免管道写满阻塞 ssh），保留最近的若干行用于报错
+    stderr: Arc<Mutex<Vec<String>>>,
+    stderr_reader: Option<JoinHandle<()>>,
+}
+
+// 保留的 ssh 错误输出行数
+const STDERR_KEEP_LINES: usize = 20;
+
+impl SshTunnel {
+    // spec 形如 user@bastion[:port]；target 为跳板机视角下的数据库地址
+    pub fn open(spec: &str, identity: Option<&Path>, target_host: &str, target_port: u16) -> Result<Self> {
+        let (destination, ssh_port) = parse_spec(spec)?;
+        // 由系统分配空闲端口后立即释放，交给 ssh 监听
+        let local_port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+
+        let mut cmd = Command::new("ssh");
+        cmd.arg("-N")
+            .args(["-o", "ExitOnForwardFailure=yes"])
+            // 不交互询问密码/主机指纹，避免与 TUI 抢占终端
+            .args(["-o", "BatchMode=yes"])
+            .args(["-o", "ServerAliveInterval=30"])
+            .arg("-L")
+            .arg(format!("127.0.0.1:{}:{}:{}", local_port, target_host, target_port));
+        if let Some(port) = ssh_port {
+            cmd.arg("-p").arg(port.to_string());
+        }
+        if let Some(identity) = identity {
+            cmd.arg("-i").arg(identity);
+        }
+        cmd.arg(destination)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        let mut child = cmd.spawn().map_err(|e| anyhow!("启动 ssh 失败: {}", e))?;
+        let stderr = Arc::new(Mutex::new(Vec::new()));
+        let stderr_reader = child.stderr.take().map(|pipe| drain_stderr(pipe, stderr.clone()));
+        let mut tunnel = Self { child, local_port, stderr, stderr_reader };
+        tunnel.wait_ready(Duration::from_secs(15))?;
+        Ok(tunnel)
+    }
+
+    // 配置了 --ssh 时建立隧道，并把连接地址改写为本地转发端口；隧道需存活到连接关闭
+    pub fn for_config(config: &mut Config) -> Result<Option<Self>> {
+        let Some(spec) = config.ssh.clone() else {
+            return Ok(None);
+        };
+        let tunnel = Self::open(&spec, config.ssh_key.as_deref(), &config.host, config.port())?;
+        config.host = "127.0.0.1".to_string();
+        config.port = Some(tunnel.local_port());
+        Ok(Some(tunnel))
+    }
+
+    pub fn local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    // 等待本地端口可连接；ssh 提前退出时带上它的错误输出
+    fn wait_ready(&mut self, timeout: Duration) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                // ssh 已退出，读取线程随管道关闭结束
+                if let Some(reader) = self.stderr_reader.take() {
+                    let _ = reader.join();
+                }
+                let stderr = self.stderr.lock().map(|lines| lines.join("\n")).unwrap_or_default();
+                return Err(anyhow!("SSH 隧道建立失败（{}）: {}", status, stderr.trim()));
+            }
+            if TcpStream::connect(("127.0.0.1", self.local_port)).is_ok() {
+                return Ok(());
+            }
+            if started.elapsed() > timeout {
+                return Err(anyhow!("等待 SSH 隧道超时（{} 秒）", timeout.as_secs()));
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// 逐行读取 ssh 的错误输出直到管道关闭：记入日志，并保留最近的几行
+fn drain_stderr(pipe: ChildStderr, lines: Arc<Mutex<Vec<String>>>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            tracing::debug!(line = line.as_str(), "ssh");
+            if let Ok(mut lines) = lines.lock() {
+                if lines.len() >= STDERR_KEEP_LINES {
+                    lines.remove(0);
+                }
+                lines.push(line);
+            }
+        }
+    })
+}
+
+// user@host[:port] -> ("user@host", Some(port))；IPv6 需写成 user@[::1]:22
+fn parse_spec(spec: &str) -> Result<(String, Option<u16>)> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err(anyhow!("--ssh 不能为空"));
+    }
+    let host_start = spec.rfind('@').map(|i| i + 1).unwrap_or(0);
+    let host = &spec[host_start..];
+    let port_sep = if host.starts_with('[') {
+        host.find("]:").map(|i| i + 1)
+    } else {
+        host.rfind(':')
+    };
+    match port_sep {
+        Some(i) => {
+            let port = host[i + 1..]
+                .parse::<u16>()
+                .map_err(|_| anyhow!("无效的 SSH 端口: {}", &host[i + 1..]))?;
+            let host = host[..i].trim_start_matches('[').trim_end_matches(']');
+            Ok((format!("{}{}", &spec[..host_start], host), Some(port)))
+        }
+        None => Ok((spec.to_string(), None)),
+    }
+}
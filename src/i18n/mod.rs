@@ -0,0 +1,170 @@
+use clap::ValueEnum;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+mod en;
+mod zh;
+
+// 界面语言
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    /// 中文
+    Zh,
+    /// English
+    En,
+}
+
+static CURRENT: OnceLock<Lang> = OnceLock::new();
+
+// --lang 优先；否则按 LC_ALL / LC_MESSAGES / LANG 推断（zh* 为中文，其余为英文），都未设置时为中文
+pub fn detect(explicit: Option<Lang>) -> Lang {
+    explicit.or_else(env_lang).unwrap_or(Lang::Zh)
+}
+
+fn env_lang() -> Option<Lang> {
+    for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        let Ok(value) = std::env::var(var) else { continue; };
+        match value.as_str() {
+            // 未设置或为 C/POSIX 时看下一个变量
+            "" | "C" | "POSIX" => continue,
+            v if v.to_ascii_lowercase().starts_with("zh") => return Some(Lang::Zh),
+            _ => return Some(Lang::En),
+        }
+    }
+    None
+}
+
+// 启动时设置一次；之后的调用不生效
+pub fn init(lang: Lang) {
+    let _ = CURRENT.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *CURRENT.get_or_init(|| detect(None))
+}
+
+// 当前语言的消息；英文目录缺少的条目回退到中文，两者都没有时返回键本身
+pub fn tr(key: &'static str) -> &'static str {
+    let catalog = match lang() {
+        Lang::Zh => zh::MESSAGES,
+        Lang::En => en::MESSAGES,
+    };
+    lookup(catalog, key).or_else(|| lookup(zh::MESSAGES, key)).unwrap_or(key)
+}
+
+// 带 {} 占位符的消息，按顺序代入参数
+pub fn format(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(key).split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+fn lookup(catalog: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+// t!("键") 取消息；t!("键", 参数...) 代入 {} 占位符
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        let missing = |from: &[(&str, &str)], to: &[(&str, &'static str)]| -> Vec<String> {
+            from.iter().filter(|(k, _)| lookup(to, k).is_none()).map(|(k, _)| k.to_string()).collect()
+        };
+        assert_eq!(missing(en::MESSAGES, zh::MESSAGES), Vec::<String>::new(), "en.rs 中有而 zh.rs 中没有的键");
+        assert_eq!(missing(zh::MESSAGES, en::MESSAGES), Vec::<String>::new(), "zh.rs 中有而 en.rs 中没有的键");
+    }
+
+    // 源码中字符串字面量里的中文（跳过注释、字符字面量与 #[cfg(test)] 之后的测试代码）；
+    // .expect() 的内部断言说明不面向用户，不算
+    fn cjk_literals(source: &str) -> Vec<String> {
+        let source = source.split("#[cfg(test)]").next().unwrap_or("");
+        let chars: Vec<char> = source.chars().collect();
+        let is_cjk = |c: &char| matches!(*c, '\u{3000}'..='\u{303f}' | '\u{4e00}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}');
+        let mut found = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '/' if chars.get(i + 1) == Some(&'/') => {
+                    i = (i..chars.len()).find(|&j| chars[j] == '\n').unwrap_or(chars.len());
+                }
+                '/' if chars.get(i + 1) == Some(&'*') => {
+                    i = (i + 2..chars.len().saturating_sub(1))
+                        .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                        .map_or(chars.len(), |j| j + 2);
+                }
+                // 字符字面量（'"'、'\''）；其余的 ' 是生命周期
+                '\'' if chars.get(i + 2) == Some(&'\'') => i += 3,
+                '\'' if chars.get(i + 1) == Some(&'\\') => {
+                    i = (i + 3..chars.len()).find(|&j| chars[j] == '\'').map_or(chars.len(), |j| j + 1);
+                }
+                '"' => {
+                    let start = i;
+                    i += 1;
+                    while i < chars.len() && chars[i] != '"' {
+                        if chars[i] == '\\' { i += 1; }
+                        i += 1;
+                    }
+                    let literal: String = chars[start..(i + 1).min(chars.len())].iter().collect();
+                    let line_start = chars[..start].iter().rposition(|&c| c == '\n').map_or(0, |p| p + 1);
+                    let line: String = chars[line_start..start].iter().collect();
+                    if literal.chars().any(|c| is_cjk(&c)) && !line.contains(".expect(") {
+                        found.push(literal);
+                    }
+                    i += 1;
+                }
+                _ => i += 1,
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn user_facing_strings_go_through_the_catalog() {
+        fn sources(dir: &std::path::Path, files: &mut Vec<std::path::PathBuf>) {
+            for entry in std::fs::read_dir(dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    sources(&path, files);
+                } else if path.extension().is_some_and(|e| e == "rs") {
+                    files.push(path);
+                }
+            }
+        }
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut files = vec![src.join("logging.rs"), src.join("main.rs")];
+        for dir in ["config", "models", "sql", "ui"] {
+            sources(&src.join(dir), &mut files);
+        }
+        let offenders: Vec<String> = files
+            .iter()
+            .flat_map(|path| {
+                let source = std::fs::read_to_string(path).unwrap();
+                cjk_literals(&source).into_iter().map(move |literal| format!("{}: {}", path.display(), literal))
+            })
+            .collect();
+        assert_eq!(offenders, Vec::<String>::new(), "应改用 t! 的中文字面量");
+    }
+}
@@ -1,4 +1,7 @@
 pub mod config;
 pub mod db;
+pub mod i18n;
+pub mod logging;
 pub mod models;
+pub mod sql;
 pub mod ui;
@@ -0,0 +1,206 @@
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+use crate::t;
+
+// 环形缓冲保留的最近日志条数
+const CAPACITY: usize = 2000;
+// --log-file 单个文件的大小上限，超过后轮转
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+// 轮转保留的旧文件数（path.1 最新）
+const KEEP_FILES: usize = 5;
+
+// --log-level 的取值
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Level::ERROR,
+            LogLevel::Warn => Level::WARN,
+            LogLevel::Info => Level::INFO,
+            LogLevel::Debug => Level::DEBUG,
+            LogLevel::Trace => Level::TRACE,
+        }
+    }
+}
+
+// 一条日志
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+static BUFFER: OnceLock<Mutex<VecDeque<LogRecord>>> = OnceLock::new();
+
+fn buffer() -> &'static Mutex<VecDeque<LogRecord>> {
+    BUFFER.get_or_init(|| Mutex::new(VecDeque::with_capacity(CAPACITY)))
+}
+
+// 初始化日志：写入内存中的环形缓冲，由 ~ 日志面板显示，不写终端（会弄花备用屏幕）
+// 本程序与 sqlx 记录到 DEBUG，其余依赖只记 INFO 以上
+// 指定 --log-file 时另以 JSON 行写入按大小轮转的文件，级别由 --log-level 决定
+pub fn init(file: Option<&Path>, level: LogLevel) -> Result<()> {
+    let targets = Targets::new()
+        .with_default(Level::INFO)
+        .with_target("sqltui_rs", Level::DEBUG)
+        .with_target("sqlx", Level::DEBUG);
+    let file_layer = match file {
+        Some(path) => {
+            let level = Level::from(level);
+            let writer = RotatingFile::open(path).with_context(|| t!("log.open_failed", path.display()))?;
+            let targets = Targets::new()
+                .with_default(level.min(Level::INFO))
+                .with_target("sqltui_rs", level)
+                .with_target("sqlx", level);
+            Some(fmt::layer().json().with_writer(Mutex::new(writer)).with_filter(targets))
+        }
+        None => None,
+    };
+    let _ = tracing_subscriber::registry()
+        .with(RingLayer.with_filter(targets))
+        .with(file_layer)
+        .try_init();
+    Ok(())
+}
+
+// 不低于 level 的日志，旧的在前
+pub fn records(level: Level) -> Vec<LogRecord> {
+    let buffer = buffer().lock().unwrap_or_else(|e| e.into_inner());
+    buffer.iter().filter(|r| r.level <= level).cloned().collect()
+}
+
+fn push(record: LogRecord) {
+    let mut buffer = buffer().lock().unwrap_or_else(|e| e.into_inner());
+    if buffer.len() == CAPACITY {
+        buffer.pop_front();
+    }
+    buffer.push_back(record);
+}
+
+struct RingLayer;
+
+impl<S: Subscriber> Layer<S> for RingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let mut message = visitor.message;
+        for field in visitor.fields {
+            if !message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&field);
+        }
+        let metadata = event.metadata();
+        push(LogRecord {
+            time: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message,
+        });
+    }
+}
+
+// 收集 message 字段与其余 key=value 字段
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+// 按大小轮转的日志文件：写满 MAX_FILE_SIZE 后 path 依次改名为 path.1、path.2 …，最多保留 KEEP_FILES 个
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path: path.to_path_buf(), file, size })
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for index in (1..KEEP_FILES).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    // fmt 层每条日志整体写入一次，轮转只会发生在两条之间
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_FILE_SIZE {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
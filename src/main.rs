@@ -1,61 +1,146 @@
 use anyhow::Result;
-use tracing::{Level};
-use tracing_subscriber;
-use crossterm::{
-    execute,
-    terminal::{disable_raw_mode, LeaveAlternateScreen},
-};
-use std::io::{self, Write};
-
+use std::io::{self, IsTerminal, Read};
 mod config;
 mod db;
+mod i18n;
+mod logging;
 mod models;
+mod sql;
 mod ui;
 
-use clap::Parser;
-use config::Config;
-use ui::App;
-
-// 全局 panic 处理器
-fn setup_panic_handler() {
-    let original_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |panic_info| {
-        // 恢复终端状态
-        let _ = disable_raw_mode();
-        let _ = execute!(
-            io::stdout(),
-            LeaveAlternateScreen
-        );
-        let _ = io::stdout().flush();
-        
-        // 调用原始的 panic 处理器
-        original_hook(panic_info);
-    }));
-}
+use config::{Command, Config, ConfigAction};
+use db::SshTunnel;
+use models::Snapshot;
+use ui::{App, ConnectForm, ConnectionManager};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 设置 panic 处理器
-    setup_panic_handler();
+    // 设置 panic 处理器（恢复终端）
+    ui::terminal::install_panic_hook();
     
-    // 初始化日志
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     // 解析命令行参数
-    let config = Config::parse();
+    let mut config = Config::from_cli()?;
+    i18n::init(i18n::detect(config.lang));
+    // 初始化日志（写入 ~ 日志面板，以及 --log-file 指定的文件）
+    logging::init(config.log_file.as_deref(), config.log_level)?;
+
+    // 子命令不进入 TUI
+    if let Some(command) = config.command.take() {
+        return run_command(command, &config).await;
+    }
+
+    // -e 或从管道读入 SQL（如 cat report.sql | sqltui-rs ...）：执行后直接退出，不进入 TUI
+    let script = match config.execute.take() {
+        Some(script) => Some(script),
+        None if !io::stdin().is_terminal() => {
+            let mut script = String::new();
+            io::stdin().read_to_string(&mut script)?;
+            Some(script)
+        }
+        None => None,
+    };
+    if let Some(script) = script {
+        config.validate()?;
+        config.prompt_password()?;
+        let _tunnel = SshTunnel::for_config(&mut config)?;
+        return ui::batch::run(&config, &script, config.format).await;
+    }
+
+    // 没有任何连接信息时：有已保存的连接配置则显示连接管理界面，否则显示连接表单
+    if config.unconfigured {
+        let connections = config::profiles::Connections::load()?;
+        if !connections.profiles.is_empty() {
+            match ConnectionManager::new(config, connections).run()? {
+                Some(selected) => config = selected,
+                None => return Ok(()),
+            }
+        }
+    }
+    if config.unconfigured {
+        let Some((config, db)) = ConnectForm::new(config).run().await? else {
+            return Ok(());
+        };
+        let profile = config.profile.clone();
+        let save_password = config.save_password.then(|| config.clone());
+        let mut app = App::connected(config, db).await?;
+        after_connect(profile.as_deref(), save_password.as_ref())?;
+        app.run().await?;
+        return Ok(());
+    }
+
+    // 连接前校验配置
+    config.validate()?;
+    config.prompt_password()?;
+    // 密钥环条目按原始主机区分，需在隧道改写地址之前取出
+    let save_password = config.save_password.then(|| config.clone());
+    // 隧道需存活到程序退出
+    let _tunnel = SshTunnel::for_config(&mut config)?;
     
     // 获取连接信息
-    let (_user, _host, _port) = config.get_connection_info();
-    // info!("正在连接到 MySQL 服务器 {}:{}", host, port);
+    let (user, host, port) = config.get_connection_info();
+    tracing::info!("{}", t!("cli.connecting", config.driver, user, host, port));
 
-    // 创建并运行应用
+    // 创建并运行应用；连接成功后才保存密码
+    let profile = config.profile.clone();
     let mut app = App::new(config).await?;
-    // info!("成功连接到 MySQL 服务器 {}:{}", host, port);
+    after_connect(profile.as_deref(), save_password.as_ref())?;
+    tracing::info!("{}", t!("cli.connected", host, port));
     
     // 运行 TUI
     app.run().await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// 连接成功后：记录连接配置的使用时间；--save-password 时把密码存入系统密钥环
+fn after_connect(profile: Option<&str>, save_password: Option<&Config>) -> Result<()> {
+    if let Some(name) = profile {
+        config::profiles::ProfileUsage::touch(name);
+    }
+    if let Some(config) = save_password {
+        config::credentials::save(config)?;
+    }
+    Ok(())
+}
+
+async fn run_command(command: Command, config: &Config) -> Result<()> {
+    match command {
+        Command::Config { action } => match action {
+            ConfigAction::Export { path } => {
+                let count = config::bundle::export_to(&path)?;
+                println!("{}", t!("cli.exported", count, path.display()));
+            }
+            ConfigAction::Import { path, force } => {
+                let (written, skipped) = config::bundle::import_from(&path, force)?;
+                for name in &written { println!("{}", t!("cli.written", name)); }
+                for name in &skipped { println!("{}", t!("cli.skipped", name)); }
+                println!("{}", t!("cli.imported", written.len(), skipped.len()));
+            }
+            ConfigAction::Check => {
+                config.validate()?;
+                let settings = config::settings::Settings::load()?;
+                settings.validate()?;
+                println!("{}", t!("cli.config_valid", config.driver, config.username, config.host, config.port()));
+                println!("{}", t!("cli.config_file", config::settings::Settings::path().display()));
+            }
+        },
+        Command::Render { query, out, color } => {
+            config.validate()?;
+            let mut config = config.clone();
+            config.prompt_password()?;
+            let _tunnel = SshTunnel::for_config(&mut config)?;
+            let (rows, total) = ui::headless::render_query_to_file(&config, &query, &out, color).await?;
+            if rows < total {
+                println!("{}", t!("cli.rendered_partial", rows, out.display(), total));
+            } else {
+                println!("{}", t!("cli.rendered", rows, out.display()));
+            }
+        }
+        Command::Open { path } => {
+            let snapshot = Snapshot::load(&path)?;
+            let mut app = App::open_snapshot(config.clone(), snapshot, &path)?;
+            app.run().await?;
+        }
+    }
+    Ok(())
+}
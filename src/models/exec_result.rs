@@ -0,0 +1,29 @@
+use crate::t;
+
+// 非查询语句（INSERT/UPDATE/DELETE 等）的执行结果
+#[derive(Debug, Clone, Default)]
+pub struct ExecResult {
+    pub rows_affected: u64,
+    // 本条语句生成的自增 ID（MySQL LAST_INSERT_ID / SQLite rowid）；驱动不支持或未插入时为 None
+    pub last_insert_id: Option<u64>,
+    // 语句产生的警告数（MySQL）
+    pub warnings: u64,
+}
+
+impl ExecResult {
+    pub fn affected(rows_affected: u64) -> Self {
+        Self { rows_affected, ..Self::default() }
+    }
+
+    // 类似 mysql 客户端的摘要，如 "受影响 1 行，插入 ID 42，1 条警告"
+    pub fn summary(&self) -> String {
+        let mut parts = vec![t!("exec.affected", self.rows_affected)];
+        if let Some(id) = self.last_insert_id {
+            parts.push(t!("exec.insert_id", id));
+        }
+        if self.warnings > 0 {
+            parts.push(t!("exec.warnings", self.warnings));
+        }
+        parts.join(t!("exec.separator"))
+    }
+}
@@ -1,7 +1,17 @@
 pub mod database;
 pub mod table;
 pub mod schema;
+pub mod privilege;
+pub mod query_record;
+pub mod snapshot;
+pub mod exec_result;
+pub mod param;
 
 pub use database::Database;
 pub use table::Table;
 pub use schema::SchemaColumn;
+pub use privilege::ColumnPrivileges;
+pub use query_record::QueryRecord;
+pub use snapshot::Snapshot;
+pub use exec_result::ExecResult;
+pub use param::ParamValue;
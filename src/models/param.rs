@@ -0,0 +1,36 @@
+// 绑定到占位符的参数值：按输入推断类型。数字按数值绑定，加引号强制为字符串，NULL 为空值；
+// 带前导零的数字（如 007）按字符串处理，避免丢失
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamValue {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl ParamValue {
+    pub fn parse(input: &str) -> Self {
+        let trimmed = input.trim();
+        if trimmed.eq_ignore_ascii_case("NULL") {
+            return ParamValue::Null;
+        }
+        for quote in ['\'', '"'] {
+            if trimmed.len() >= 2 && trimmed.starts_with(quote) && trimmed.ends_with(quote) {
+                return ParamValue::Text(trimmed[1..trimmed.len() - 1].to_string());
+            }
+        }
+        let digits = trimmed.trim_start_matches(['-', '+']);
+        let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+        let numeric = !trimmed.is_empty()
+            && trimmed.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+        if numeric && !leading_zero {
+            if let Ok(v) = trimmed.parse::<i64>() {
+                return ParamValue::Int(v);
+            }
+            if let Ok(v) = trimmed.parse::<f64>() {
+                return ParamValue::Float(v);
+            }
+        }
+        ParamValue::Text(input.to_string())
+    }
+}
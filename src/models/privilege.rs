@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+// 当前用户对某列的可用权限
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnPrivileges {
+    pub select: bool,
+    pub insert: bool,
+    pub update: bool,
+}
+
+impl ColumnPrivileges {
+
+    pub fn all() -> Self {
+        Self { select: true, insert: true, update: true }
+    }
+
+    pub fn grant(&mut self, privilege: &str) {
+        match privilege.trim().to_uppercase().as_str() {
+            "SELECT" => self.select = true,
+            "INSERT" => self.insert = true,
+            "UPDATE" => self.update = true,
+            "ALL" | "ALL PRIVILEGES" => *self = Self::all(),
+            _ => {}
+        }
+    }
+
+    pub fn merge(&mut self, other: &ColumnPrivileges) {
+        self.select |= other.select;
+        self.insert |= other.insert;
+        self.update |= other.update;
+    }
+
+    // 形如 "S I -"，缺失的权限显示为 -
+    pub fn marks(&self) -> String {
+        format!(
+            "{} {} {}",
+            if self.select { "S" } else { "-" },
+            if self.insert { "I" } else { "-" },
+            if self.update { "U" } else { "-" },
+        )
+    }
+}
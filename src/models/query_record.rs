@@ -0,0 +1,28 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+// 一次 SQL 执行的记录（耗时、行数、标签）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryRecord {
+    pub sql: String,
+    pub tags: Vec<String>,
+    pub executed_at: DateTime<Local>,
+    pub duration_ms: u64,
+    // 查询返回行数或非查询的受影响行数
+    pub rows: u64,
+    pub success: bool,
+}
+
+impl QueryRecord {
+
+    pub fn new(sql: String, tags: Vec<String>, duration_ms: u64, rows: u64, success: bool) -> Self {
+        Self {
+            sql,
+            tags,
+            executed_at: Local::now(),
+            duration_ms,
+            rows,
+            success,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::ColumnPrivileges;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaColumn {
     pub name: String,
@@ -8,6 +10,9 @@ pub struct SchemaColumn {
     pub default_value: Option<String>,
     pub extra: Option<String>,
     pub comment: Option<String>,
+    // 当前用户的列权限；None 表示驱动不支持或尚未查询
+    #[serde(default)]
+    pub privileges: Option<ColumnPrivileges>,
 }
 
 impl SchemaColumn {
@@ -27,6 +32,7 @@ impl SchemaColumn {
             default_value,
             extra,
             comment,
+            privileges: None,
         }
     }
 }
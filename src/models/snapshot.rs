@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use crate::t;
+
+const SNAPSHOT_VERSION: u32 = 1;
+
+// 结果快照：查询、连接元数据与结果集，gzip 压缩的 JSON，可在无数据库连接时只读打开
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub created_at: DateTime<Local>,
+    pub query: String,
+    pub driver: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub database: Option<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Snapshot {
+    pub fn new(query: String, headers: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            created_at: Local::now(),
+            query,
+            driver: String::new(),
+            host: String::new(),
+            port: 0,
+            username: String::new(),
+            database: None,
+            headers,
+            rows,
+        }
+    }
+
+    // 记录结果来自哪个连接
+    pub fn with_connection(mut self, driver: String, host: String, port: u16, username: String, database: Option<String>) -> Self {
+        self.driver = driver;
+        self.host = host;
+        self.port = port;
+        self.username = username;
+        self.database = database;
+        self
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut encoder = GzEncoder::new(BufWriter::new(File::create(path)?), Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let decoder = GzDecoder::new(BufReader::new(File::open(path)?));
+        let snapshot: Self = serde_json::from_reader(decoder)
+            .map_err(|e| anyhow!(t!("snapshot.parse_failed", path.display(), e)))?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            return Err(anyhow!(t!("snapshot.version_too_new", snapshot.version, SNAPSHOT_VERSION)));
+        }
+        Ok(snapshot)
+    }
+}
@@ -0,0 +1,691 @@
+// SQL 文本的轻量级词法辅助（不做完整解析，只识别注释、引号等边界）
+
+use std::collections::BTreeMap;
+
+use crate::t;
+
+// 影响词法分析的方言差异
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    // # 开头为行注释
+    Mysql,
+    // # 为按位异或，? 为 jsonb 运算符，参数写作 $1、$2...（PostgreSQL、Redshift）
+    Postgres,
+    #[default]
+    Other,
+}
+
+impl Dialect {
+    fn is_line_comment(self, chars: &[char], i: usize) -> bool {
+        (chars[i] == '-' && chars.get(i + 1) == Some(&'-')) || (chars[i] == '#' && self == Dialect::Mysql)
+    }
+
+    // 字符串中的反斜杠是否为转义符（MySQL 默认如此；PostgreSQL 标准字符串中为普通字符）
+    fn backslash_escapes(self) -> bool {
+        self == Dialect::Mysql
+    }
+}
+
+// i 处为引号时返回闭合引号之后的位置；未闭合时返回 None。
+// 反斜杠只在支持转义的方言中跳过下一个字符，双写的引号（'it''s'）视为先闭合再重新开始
+fn quote_end(chars: &[char], i: usize, dialect: Dialect) -> Option<usize> {
+    let quote = chars[i];
+    let mut j = i + 1;
+    while j < chars.len() && chars[j] != quote {
+        if chars[j] == '\\' && dialect.backslash_escapes() { j += 1; }
+        j += 1;
+    }
+    (j < chars.len()).then_some(j + 1)
+}
+
+// 跳过前导空白与注释（-- 行注释、MySQL 的 # 行注释、/* */ 块注释，含 /*+ */ 优化器提示），返回其后的正文
+pub fn skip_leading_comments(sql: &str, dialect: Dialect) -> &str {
+    let mut rest = sql;
+    loop {
+        let trimmed = rest.trim_start();
+        if trimmed.starts_with("--") || (trimmed.starts_with('#') && dialect == Dialect::Mysql) {
+            match trimmed.find('\n') {
+                Some(i) => rest = &trimmed[i + 1..],
+                None => return "",
+            }
+        } else if let Some(body) = trimmed.strip_prefix("/*") {
+            match body.find("*/") {
+                Some(i) => rest = &body[i + 2..],
+                None => return "",
+            }
+        } else {
+            return trimmed;
+        }
+    }
+}
+
+// 语句的首个关键字（大写），忽略前导注释与提示
+pub fn first_keyword(sql: &str, dialect: Dialect) -> String {
+    skip_leading_comments(sql, dialect)
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic() || *c == '_')
+        .collect::<String>()
+        .to_uppercase()
+}
+
+// DDL/工具类语句的操作与目标对象，如 ("CREATE TABLE", Some("t1"))；非此类语句返回 None
+pub fn ddl_summary(sql: &str, dialect: Dialect) -> Option<(String, Option<String>)> {
+    const VERBS: &[&str] = &[
+        "CREATE", "ALTER", "DROP", "TRUNCATE", "RENAME", "GRANT", "REVOKE",
+        "OPTIMIZE", "ATTACH", "DETACH", "ANALYZE", "VACUUM", "COMMENT", "SET",
+    ];
+    const OBJECT_WORDS: &[&str] = &[
+        "TABLE", "VIEW", "INDEX", "DATABASE", "SCHEMA", "FUNCTION", "PROCEDURE", "TRIGGER",
+        "SEQUENCE", "MATERIALIZED", "TEMPORARY", "TEMP", "UNIQUE", "OR", "REPLACE", "USER",
+        "ROLE", "DICTIONARY", "EXTENSION", "TYPE", "EVENT", "UNLOGGED", "ON",
+    ];
+    let mut words = skip_leading_comments(sql, dialect).split_whitespace().peekable();
+    let verb = words.next()?.trim_end_matches(';').to_uppercase();
+    if !VERBS.contains(&verb.as_str()) {
+        return None;
+    }
+    let mut action = verb.clone();
+    // GRANT/REVOKE/SET 不标注对象
+    if matches!(verb.as_str(), "GRANT" | "REVOKE" | "SET") {
+        return Some((action, None));
+    }
+    while let Some(word) = words.peek() {
+        let upper = word.to_uppercase();
+        if !OBJECT_WORDS.contains(&upper.as_str()) { break; }
+        action.push(' ');
+        action.push_str(&upper);
+        words.next();
+    }
+    // 跳过 IF [NOT] EXISTS
+    if words.peek().is_some_and(|w| w.eq_ignore_ascii_case("IF")) {
+        words.next();
+        if words.peek().is_some_and(|w| w.eq_ignore_ascii_case("NOT")) { words.next(); }
+        if words.peek().is_some_and(|w| w.eq_ignore_ascii_case("EXISTS")) { words.next(); }
+    }
+    let object = words
+        .next()
+        .map(|w| w.split('(').next().unwrap_or("").trim_end_matches(';').to_string())
+        .filter(|w| !w.is_empty());
+    Some((action, object))
+}
+
+// 光标前的文本是否处于未闭合的 /*+ ... */ 优化器提示中；引号与其他注释中的 /*+ 不算
+pub fn in_optimizer_hint(before_cursor: &str, dialect: Dialect) -> bool {
+    let chars: Vec<char> = before_cursor.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '/' && next == Some('*') {
+            let hint = chars.get(i + 2) == Some(&'+');
+            match (i + 2..chars.len().saturating_sub(1)).find(|&j| chars[j] == '*' && chars[j + 1] == '/') {
+                Some(j) => i = j + 2,
+                None => return hint,
+            }
+        } else if dialect.is_line_comment(&chars, i) {
+            match chars[i..].iter().position(|&ch| ch == '\n') {
+                Some(p) => i += p + 1,
+                None => return false,
+            }
+        } else if matches!(c, '\'' | '"' | '`') {
+            // 未闭合时光标在字符串中
+            match quote_end(&chars, i, dialect) {
+                Some(end) => i = end,
+                None => return false,
+            }
+        } else {
+            i += 1;
+        }
+    }
+    false
+}
+
+// 注释中的 #标签（如 `-- #ticket-1234`、`/* #incident-7 */`），按出现顺序去重
+pub fn extract_tags(sql: &str) -> Vec<String> {
+    let mut comments: Vec<&str> = Vec::new();
+    let mut rest = sql;
+    while !rest.is_empty() {
+        let line_pos = rest.find("--");
+        let block_pos = rest.find("/*");
+        match (line_pos, block_pos) {
+            (Some(l), b) if b.is_none_or(|b| l < b) => {
+                let body = &rest[l + 2..];
+                let end = body.find('\n').unwrap_or(body.len());
+                comments.push(&body[..end]);
+                rest = &body[end..];
+            }
+            (_, Some(b)) => {
+                let body = &rest[b + 2..];
+                let end = body.find("*/").unwrap_or(body.len());
+                comments.push(&body[..end]);
+                rest = &body[(end + 2).min(body.len())..];
+            }
+            _ => break,
+        }
+    }
+    let mut tags: Vec<String> = Vec::new();
+    for comment in comments {
+        for word in comment.split_whitespace() {
+            if let Some(tag) = word.strip_prefix('#') {
+                let tag: String = tag
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == '.')
+                    .collect();
+                if !tag.is_empty() && !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+    }
+    tags
+}
+
+// 聚合函数的 NULL 处理语义，以及包裹 COALESCE 时使用的默认值（COUNT 不会返回 NULL，无需包裹）
+pub fn aggregate_null_semantics(name: &str) -> Option<(&'static str, Option<&'static str>)> {
+    match name.to_uppercase().as_str() {
+        "COUNT" => Some((t!("aggregate.count"), None)),
+        "SUM" => Some((t!("aggregate.sum"), Some("0"))),
+        "AVG" => Some((t!("aggregate.avg"), Some("0"))),
+        "MIN" | "MAX" => Some((t!("aggregate.min_max"), Some("0"))),
+        "GROUP_CONCAT" | "STRING_AGG" => Some((t!("aggregate.string_agg"), Some("''"))),
+        _ => None,
+    }
+}
+
+// 从 open 位置的 '(' 开始寻找匹配的 ')'，跳过引号内内容；返回 ')' 的下标
+pub fn matching_paren(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, &c) in chars.iter().enumerate().skip(open) {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 { return Some(i); }
+                }
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+// 返回结果集的语句（按首个关键字判断）：查询与各类元数据命令统一走结果表格渲染
+pub fn returns_rows(first_keyword: &str) -> bool {
+    matches!(
+        first_keyword,
+        "SELECT" | "WITH" | "VALUES" | "TABLE" | "SHOW" | "DESCRIBE" | "DESC" | "EXPLAIN" | "EXISTS"
+            | "PRAGMA" | "SUMMARIZE" | "FROM"
+    )
+}
+
+// 服务端因语句超时中止执行时的错误（MySQL 3024 / PostgreSQL 57014 / ClickHouse TIMEOUT_EXCEEDED）
+pub fn is_timeout_error(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("maximum statement execution time exceeded")
+        || lower.contains("statement timeout")
+        || lower.contains("timeout_exceeded")
+}
+
+// 事务控制语句
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TxControl {
+    // BEGIN / START TRANSACTION
+    Begin,
+    // COMMIT / ROLLBACK / END / ABORT（ROLLBACK TO SAVEPOINT 不结束事务）
+    End,
+}
+
+// 识别开启/结束事务的语句；BEGIN ... END 语句块（如 T-SQL 的 BEGIN TRY）不算
+pub fn transaction_control(sql: &str, dialect: Dialect) -> Option<TxControl> {
+    let words: Vec<String> = code_words(sql, dialect).into_iter().take(3).map(|w| w.to_uppercase()).collect();
+    let second = words.get(1).map(String::as_str);
+    match words.first().map(String::as_str)? {
+        "BEGIN" => matches!(
+            second,
+            None | Some("WORK" | "TRAN" | "TRANSACTION" | "ISOLATION" | "READ" | "DEFERRED" | "IMMEDIATE" | "EXCLUSIVE")
+        )
+        .then_some(TxControl::Begin),
+        "START" => (second == Some("TRANSACTION")).then_some(TxControl::Begin),
+        "COMMIT" | "END" | "ABORT" => {
+            matches!(second, None | Some("WORK" | "TRAN" | "TRANSACTION" | "AND")).then_some(TxControl::End)
+        }
+        "ROLLBACK" => {
+            let to = words.iter().skip(1).any(|w| w == "TO");
+            (!to && matches!(second, None | Some("WORK" | "TRAN" | "TRANSACTION" | "AND"))).then_some(TxControl::End)
+        }
+        _ => None,
+    }
+}
+
+// 只读模式下允许的语句；不允许时返回应拒绝的关键字（如 "DELETE"）。
+// 查询类语句还检查正文中的写操作关键字（字符串与注释除外），
+// 以拦截 WITH ... DELETE、EXPLAIN ANALYZE UPDATE、SELECT ... INTO、SELECT ... FOR UPDATE 等形式
+pub fn read_only_violation(sql: &str, dialect: Dialect) -> Option<String> {
+    const METADATA: &[&str] = &["SHOW", "DESCRIBE", "DESC", "USE"];
+    const QUERIES: &[&str] = &["SELECT", "WITH", "VALUES", "TABLE", "EXPLAIN"];
+    const WRITES: &[&str] = &[
+        "INSERT", "UPDATE", "DELETE", "MERGE", "UPSERT", "INTO", "CREATE", "DROP", "ALTER", "TRUNCATE", "GRANT", "REVOKE",
+    ];
+    let keyword = first_keyword(sql, dialect);
+    if keyword.is_empty() || METADATA.contains(&keyword.as_str()) {
+        return None;
+    }
+    // BEGIN READ WRITE / START TRANSACTION READ WRITE 会覆盖会话级的只读设置
+    if let Some(control) = transaction_control(sql, dialect) {
+        let words: Vec<String> = code_words(sql, dialect).into_iter().map(|w| w.to_uppercase()).collect();
+        let read_write = words.windows(2).any(|w| w[0] == "READ" && w[1] == "WRITE");
+        return (control == TxControl::Begin && read_write).then(|| "READ WRITE".to_string());
+    }
+    if !QUERIES.contains(&keyword.as_str()) {
+        return Some(keyword);
+    }
+    code_words(sql, dialect)
+        .into_iter()
+        .map(|w| w.to_uppercase())
+        .find(|w| WRITES.contains(&w.as_str()))
+}
+
+// 按分隔符拆分多条语句（忽略引号、注释与 PostgreSQL $$ 引用体中的分隔符，MySQL 的引号内支持反斜杠转义）；
+// 支持 MySQL 客户端的 DELIMITER 命令：独占一行，改变其后语句的分隔符（如 DELIMITER $$）。
+// 去掉首尾空白，丢弃只含注释的片段；同时返回末尾生效的分隔符
+pub fn split_script(sql: &str, delimiter: &str, dialect: Dialect) -> (Vec<String>, String) {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut delimiter: Vec<char> = delimiter.chars().collect();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    // 当前语句在 i 之前只有空白与注释（DELIMITER 命令只能出现在语句开头）
+    let mut at_start = true;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if matches!(c, 'd' | 'D') && at_start {
+            if let Some((new_delimiter, line_len)) = delimiter_command(&chars[i..]) {
+                delimiter = new_delimiter;
+                i += line_len;
+                start = i;
+                continue;
+            }
+        }
+        if chars[i..].starts_with(&delimiter) {
+            statements.push(chars[start..i].iter().collect::<String>());
+            i += delimiter.len();
+            start = i;
+            at_start = true;
+            continue;
+        }
+        if dialect.is_line_comment(&chars, i) {
+            i = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+        } else if c == '/' && next == Some('*') {
+            i = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map_or(chars.len(), |j| j + 2);
+        } else if matches!(c, '\'' | '"' | '`') {
+            at_start = false;
+            i = quote_end(&chars, i, dialect).unwrap_or(chars.len());
+        } else if let Some(end) = dollar_quote_end(&chars, i) {
+            at_start = false;
+            i = end;
+        } else {
+            at_start &= c.is_whitespace();
+            i += 1;
+        }
+    }
+    statements.push(chars[start.min(chars.len())..].iter().collect::<String>());
+    let statements = statements
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !skip_leading_comments(s, dialect).is_empty())
+        .collect();
+    (statements, delimiter.into_iter().collect())
+}
+
+// 行首的 DELIMITER xx：返回新分隔符与该行长度（含换行）
+fn delimiter_command(rest: &[char]) -> Option<(Vec<char>, usize)> {
+    let line_len = rest.iter().position(|&c| c == '\n').map_or(rest.len(), |p| p + 1);
+    let line: String = rest[..line_len].iter().collect();
+    let mut parts = line.split_whitespace();
+    if !parts.next()?.eq_ignore_ascii_case("DELIMITER") {
+        return None;
+    }
+    Some((parts.next()?.chars().collect(), line_len))
+}
+
+// PostgreSQL 美元引用（$$ ... $$ 或 $tag$ ... $tag$）：i 处为起始标记时返回结束标记之后的位置；
+// 紧跟在标识符后的 $（如 a$b）与 $1 参数不算
+fn dollar_quote_end(chars: &[char], i: usize) -> Option<usize> {
+    if chars[i] != '$' || (i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_')) {
+        return None;
+    }
+    let tag_len = chars[i + 1..].iter().position(|&c| c == '$')?;
+    let tag = &chars[i..i + tag_len + 2];
+    if tag[1..tag_len + 1].iter().any(|c| !(c.is_alphanumeric() || *c == '_'))
+        || tag.get(1).is_some_and(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let body = i + tag.len();
+    Some((body..chars.len()).find(|&j| chars[j..].starts_with(tag)).map_or(chars.len(), |j| j + tag.len()))
+}
+
+// 会话变量名：字母或下划线开头，由字母、数字、下划线组成
+pub fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// 把 ${name} 替换为会话变量（\set 定义）的值；引号、注释与 $$ 引用体中的内容原样保留。
+// 引用未定义的变量时返回其名称
+pub fn interpolate_variables(sql: &str, variables: &BTreeMap<String, String>, dialect: Dialect) -> Result<String, String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut expanded = String::with_capacity(sql.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let end = if dialect.is_line_comment(&chars, i) {
+            chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p)
+        } else if c == '/' && next == Some('*') {
+            (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map_or(chars.len(), |j| j + 2)
+        } else if matches!(c, '\'' | '"' | '`') {
+            quote_end(&chars, i, dialect).unwrap_or(chars.len())
+        } else if c == '$' && next == Some('{') {
+            let close = chars[i + 2..].iter().position(|&ch| ch == '}').map(|p| i + 2 + p);
+            let name = close.map(|close| chars[i + 2..close].iter().collect::<String>());
+            match (close, name) {
+                (Some(close), Some(name)) if is_variable_name(&name) => {
+                    expanded.push_str(variables.get(&name).ok_or_else(|| name.clone())?);
+                    i = close + 1;
+                    continue;
+                }
+                _ => i + 2,
+            }
+        } else if let Some(end) = dollar_quote_end(&chars, i) {
+            end
+        } else {
+            i + 1
+        };
+        expanded.extend(&chars[i..end]);
+        i = end;
+    }
+    Ok(expanded)
+}
+
+// 语句中的参数占位符：? 按出现顺序各为一个参数，:name 同名共用一个值
+pub struct Placeholders {
+    // 每处占位符的字节范围及对应的参数下标
+    spans: Vec<(usize, usize, usize)>,
+    // 参数名；? 依次为 "1"、"2"...
+    pub names: Vec<String>,
+}
+
+impl Placeholders {
+    // 把第 i 处占位符改写为 placeholder(i)（如 PostgreSQL 的 $1），并按出现顺序展开参数值
+    pub fn bind<T: Clone>(&self, sql: &str, values: &[T], placeholder: impl Fn(usize) -> String) -> (String, Vec<T>) {
+        let mut rewritten = String::with_capacity(sql.len());
+        let mut last = 0;
+        let mut bound = Vec::with_capacity(self.spans.len());
+        for (i, (start, end, param)) in self.spans.iter().enumerate() {
+            rewritten.push_str(&sql[last..*start]);
+            rewritten.push_str(&placeholder(i));
+            bound.push(values[*param].clone());
+            last = *end;
+        }
+        rewritten.push_str(&sql[last..]);
+        (rewritten, bound)
+    }
+}
+
+// 查找 ? 与 :name 占位符（跳过引号、注释与 $$ 引用体）；:: 类型转换与 := 赋值不算。
+// PostgreSQL 中 ? 是 jsonb 运算符，只认 $1、$2... 参数，同一序号共用一个值。没有占位符时返回 None
+pub fn find_placeholders(sql: &str, dialect: Dialect) -> Option<Placeholders> {
+    let chars: Vec<char> = sql.chars().collect();
+    let offsets: Vec<usize> = sql.char_indices().map(|(i, _)| i).chain([sql.len()]).collect();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut placeholders = Placeholders { spans: Vec::new(), names: Vec::new() };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if dialect.is_line_comment(&chars, i) {
+            i = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+        } else if c == '/' && next == Some('*') {
+            i = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map_or(chars.len(), |j| j + 2);
+        } else if matches!(c, '\'' | '"' | '`') {
+            i = quote_end(&chars, i, dialect).unwrap_or(chars.len());
+        } else if let Some(end) = dollar_quote_end(&chars, i) {
+            i = end;
+        } else if dialect == Dialect::Postgres {
+            if c == '$' && next.is_some_and(|n| n.is_ascii_digit()) && (i == 0 || !is_ident(chars[i - 1])) {
+                let end = (i + 1..chars.len()).find(|&j| !chars[j].is_ascii_digit()).unwrap_or(chars.len());
+                let name: String = chars[i + 1..end].iter().collect();
+                let param = match placeholders.names.iter().position(|n| *n == name) {
+                    Some(param) => param,
+                    None => {
+                        placeholders.names.push(name);
+                        placeholders.names.len() - 1
+                    }
+                };
+                placeholders.spans.push((offsets[i], offsets[end], param));
+                i = end;
+            } else {
+                i += 1;
+            }
+        } else if c == '?' {
+            placeholders.names.push((placeholders.names.len() + 1).to_string());
+            placeholders.spans.push((offsets[i], offsets[i + 1], placeholders.names.len() - 1));
+            i += 1;
+        } else if c == ':'
+            && next.is_some_and(|n| n.is_alphabetic() || n == '_')
+            && (i == 0 || !(is_ident(chars[i - 1]) || chars[i - 1] == ':'))
+        {
+            let end = (i + 1..chars.len()).find(|&j| !is_ident(chars[j])).unwrap_or(chars.len());
+            let name: String = chars[i + 1..end].iter().collect();
+            let param = match placeholders.names.iter().position(|n| *n == name) {
+                Some(param) => param,
+                None => {
+                    placeholders.names.push(name);
+                    placeholders.names.len() - 1
+                }
+            };
+            placeholders.spans.push((offsets[i], offsets[end], param));
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    (!placeholders.spans.is_empty()).then_some(placeholders)
+}
+
+// 正文中的单词（跳过注释、引号与 $$ 引用体内的内容）
+fn code_words(sql: &str, dialect: Dialect) -> Vec<String> {
+    code_words_with_depth(sql, dialect).into_iter().map(|(word, _)| word).collect()
+}
+
+// 正文中的单词及其所在的括号层数（0 为语句顶层，子查询、函数参数内大于 0）
+fn code_words_with_depth(sql: &str, dialect: Dialect) -> Vec<(String, usize)> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let skip_to = if dialect.is_line_comment(&chars, i) {
+            chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p)
+        } else if c == '/' && next == Some('*') {
+            (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map_or(chars.len(), |j| j + 2)
+        } else if matches!(c, '\'' | '"' | '`') {
+            quote_end(&chars, i, dialect).unwrap_or(chars.len())
+        } else if let Some(end) = dollar_quote_end(&chars, i) {
+            end
+        } else {
+            if c.is_alphanumeric() || c == '_' {
+                current.push(c);
+            } else if !current.is_empty() {
+                words.push((std::mem::take(&mut current), depth));
+            }
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            i += 1;
+            continue;
+        };
+        if !current.is_empty() {
+            words.push((std::mem::take(&mut current), depth));
+        }
+        i = skip_to;
+    }
+    if !current.is_empty() {
+        words.push((current, depth));
+    }
+    words
+}
+
+// 交互执行的 SELECT 未写行数限制时追加 suffix（如 " LIMIT 500"）；
+// 已有 LIMIT/FETCH/TOP、SELECT ... INTO 或加锁读取（LIMIT 须写在 FOR UPDATE 之前）时返回 None
+pub fn with_default_limit(sql: &str, suffix: &str, dialect: Dialect) -> Option<String> {
+    if first_keyword(sql, dialect) != "SELECT" {
+        return None;
+    }
+    let words: Vec<String> = code_words(sql, dialect).into_iter().map(|w| w.to_uppercase()).collect();
+    const SKIP: &[&str] = &["LIMIT", "FETCH", "TOP", "INTO", "LOCK"];
+    if words.iter().any(|w| SKIP.contains(&w.as_str()))
+        || words.windows(2).any(|w| w[0] == "FOR" && matches!(w[1].as_str(), "UPDATE" | "SHARE"))
+    {
+        return None;
+    }
+    // 另起一行追加，避免落入末尾的行注释
+    let body = sql.trim_end().trim_end_matches(';').trim_end();
+    Some(format!("{}\n{}", body, suffix.trim_start()))
+}
+
+// 需要确认的破坏性语句：DROP / TRUNCATE / 不带 WHERE 的 DELETE、UPDATE。
+// 返回 (提示说明, 需输入确认的对象名)；DROP 要求输入对象名，其余按 y 确认
+pub fn destructive_action(sql: &str, dialect: Dialect) -> Option<(String, Option<String>)> {
+    match first_keyword(sql, dialect).as_str() {
+        "DROP" => {
+            let (action, object) = ddl_summary(sql, dialect)?;
+            let object = object.map(|o| o.trim_matches(|c| matches!(c, '`' | '"' | '[' | ']')).to_string());
+            Some((format!("{} {}", action, object.as_deref().unwrap_or("")).trim_end().to_string(), object))
+        }
+        "TRUNCATE" => {
+            let (action, object) = ddl_summary(sql, dialect)?;
+            Some((t!("sql.truncate_all", action, object.unwrap_or_default()), None))
+        }
+        keyword @ ("DELETE" | "UPDATE") => {
+            // 只有语句顶层的 WHERE 限定了影响范围，子查询中的 WHERE 不算
+            let words = code_words_with_depth(sql, dialect);
+            if words.iter().any(|(w, depth)| *depth == 0 && w.eq_ignore_ascii_case("WHERE")) {
+                return None;
+            }
+            // DELETE FROM t / UPDATE t SET ...
+            const MODIFIERS: &[&str] = &["FROM", "LOW_PRIORITY", "QUICK", "IGNORE", "ONLY"];
+            let target = skip_leading_comments(sql, dialect)
+                .split_whitespace()
+                .skip(1)
+                .find(|w| !MODIFIERS.iter().any(|m| w.eq_ignore_ascii_case(m)))
+                .map(|w| w.trim_end_matches(';').to_string())
+                .unwrap_or_default();
+            let message = if keyword == "DELETE" { "sql.delete_all" } else { "sql.update_all" };
+            Some((t!(message, keyword, target), None))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(sql: &str, dialect: Dialect) -> Vec<String> {
+        split_script(sql, ";", dialect).0
+    }
+
+    #[test]
+    fn backslash_escapes_only_on_mysql() {
+        assert_eq!(split(r"SELECT 'a\';b'; SELECT 2", Dialect::Mysql), [r"SELECT 'a\';b'", "SELECT 2"]);
+        assert_eq!(split(r"SELECT 'a\'; SELECT 2", Dialect::Postgres), [r"SELECT 'a\'", "SELECT 2"]);
+        assert_eq!(split(r"SELECT 'a\'; SELECT 2", Dialect::Other), [r"SELECT 'a\'", "SELECT 2"]);
+        assert!(!in_optimizer_hint(r"SELECT 'a\' /*+ ", Dialect::Mysql));
+        assert!(in_optimizer_hint(r"SELECT 'a\' /*+ ", Dialect::Postgres));
+        assert!(find_placeholders(r"SELECT '\', ?", Dialect::Mysql).is_none());
+        assert_eq!(find_placeholders(r"SELECT '\', ?", Dialect::Other).map(|p| p.names.len()), Some(1));
+        let variables = BTreeMap::from([("x".to_string(), "1".to_string())]);
+        assert_eq!(interpolate_variables(r"SELECT 'a\'${x}', ${x}", &variables, Dialect::Mysql).unwrap(), r"SELECT 'a\'${x}', 1");
+        assert_eq!(interpolate_variables(r"SELECT 'a\'${x}", &variables, Dialect::Postgres).unwrap(), r"SELECT 'a\'1");
+    }
+
+    #[test]
+    fn doubled_quotes_stay_inside_the_string() {
+        for dialect in [Dialect::Mysql, Dialect::Postgres, Dialect::Other] {
+            assert_eq!(split("SELECT 'it''s;'; SELECT 2", dialect), ["SELECT 'it''s;'", "SELECT 2"]);
+            assert!(find_placeholders("SELECT 'it''s ?'", dialect).is_none());
+        }
+    }
+
+    #[test]
+    fn hash_comments_only_on_mysql() {
+        assert_eq!(split("SELECT 1 # a;b\n", Dialect::Mysql), ["SELECT 1 # a;b"]);
+        assert_eq!(split("SELECT 1 # a;b\n", Dialect::Postgres), ["SELECT 1 # a", "b"]);
+        assert_eq!(first_keyword("# note\nSELECT 1", Dialect::Mysql), "SELECT");
+        assert_eq!(first_keyword("# note\nSELECT 1", Dialect::Postgres), "");
+    }
+
+    #[test]
+    fn dollar_quoted_bodies_are_not_split() {
+        let script = "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql; SELECT $1";
+        assert_eq!(
+            split(script, Dialect::Postgres),
+            ["CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql", "SELECT $1"]
+        );
+        assert_eq!(find_placeholders("SELECT $$ $1 $$, $1", Dialect::Postgres).map(|p| p.names), Some(vec!["1".to_string()]));
+    }
+
+    #[test]
+    fn delimiter_command_changes_the_separator() {
+        let script = "DELIMITER $$\nCREATE PROCEDURE p() BEGIN SELECT 1; END$$\nDELIMITER ;\nSELECT 2;";
+        let (statements, delimiter) = split_script(script, ";", Dialect::Mysql);
+        assert_eq!(statements, ["CREATE PROCEDURE p() BEGIN SELECT 1; END", "SELECT 2"]);
+        assert_eq!(delimiter, ";");
+        let (_, delimiter) = split_script("DELIMITER //\n", ";", Dialect::Mysql);
+        assert_eq!(delimiter, "//");
+    }
+
+    #[test]
+    fn only_a_top_level_where_limits_delete_and_update() {
+        for dialect in [Dialect::Mysql, Dialect::Postgres] {
+            assert!(destructive_action("DELETE FROM t", dialect).is_some());
+            assert!(destructive_action("DELETE FROM t WHERE id IN (SELECT id FROM u WHERE x = 1)", dialect).is_none());
+            assert!(destructive_action("UPDATE t SET a = (SELECT max(b) FROM u WHERE u.id = 1)", dialect).is_some());
+            assert!(destructive_action("UPDATE t SET a = 1 -- WHERE id = 1", dialect).is_some());
+            assert!(destructive_action("UPDATE t SET a = 'WHERE' WHERE id = 1", dialect).is_none());
+        }
+    }
+
+    #[test]
+    fn read_write_transactions_break_read_only() {
+        for dialect in [Dialect::Mysql, Dialect::Postgres] {
+            assert_eq!(read_only_violation("START TRANSACTION READ WRITE", dialect).as_deref(), Some("READ WRITE"));
+            assert_eq!(read_only_violation("BEGIN READ WRITE", dialect).as_deref(), Some("READ WRITE"));
+            assert_eq!(read_only_violation("START TRANSACTION READ ONLY", dialect), None);
+            assert_eq!(read_only_violation("BEGIN", dialect), None);
+            assert_eq!(read_only_violation("COMMIT", dialect), None);
+            assert_eq!(read_only_violation("WITH d AS (DELETE FROM t RETURNING *) SELECT * FROM d", dialect).as_deref(), Some("DELETE"));
+        }
+    }
+}
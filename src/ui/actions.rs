@@ -0,0 +1,145 @@
+use anyhow::Result;
+use std::process::{Command, Stdio};
+
+// 展开后的行操作命令：${列名} 换成环境变量引用，值经环境变量传给 shell，
+// 不参与命令解析，值中的 shell 元字符与 ${...} 都不会被再次展开
+pub struct RowCommand {
+    command: String,
+    env: Vec<(String, String)>,
+}
+
+// ${列名} 所在位置的引号上下文，决定变量引用的写法
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Quote {
+    None,
+    // 只有 sh 有单引号
+    #[cfg(unix)]
+    Single,
+    Double,
+}
+
+// 从左到右扫描一遍模板，将 ${列名} 替换为焦点行对应值的引用；不是列名的 ${...} 原样保留。
+// 引用按所在的引号上下文书写，写在 '...' 或 "..." 中的 ${列名} 同样展开
+pub fn expand_template(template: &str, headers: &[String], row: &[String]) -> RowCommand {
+    let mut command = String::new();
+    let mut env: Vec<(String, String)> = Vec::new();
+    let mut quote = Quote::None;
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        command.push_str(&rest[..start]);
+        quote = scan_quotes(quote, &rest[..start]);
+        let after = &rest[start + 2..];
+        let column = after
+            .find('}')
+            .and_then(|end| headers.iter().position(|h| *h == after[..end]).map(|idx| (idx, end)));
+        match column {
+            Some((idx, end)) => {
+                let name = format!("SQLTUI_COL_{}", idx);
+                if !env.iter().any(|(n, _)| *n == name) {
+                    env.push((name.clone(), row.get(idx).cloned().unwrap_or_default()));
+                }
+                command.push_str(&var_ref(&name, quote));
+                rest = &after[end + 1..];
+            }
+            None => {
+                command.push_str("${");
+                rest = after;
+            }
+        }
+    }
+    command.push_str(rest);
+    RowCommand { command, env }
+}
+
+// sh 的引号：单引号内没有转义，其外的反斜杠转义下一个字符
+#[cfg(unix)]
+fn scan_quotes(mut quote: Quote, text: &str) -> Quote {
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        quote = match (quote, c) {
+            (Quote::Single, '\'') => Quote::None,
+            (Quote::Single, _) => Quote::Single,
+            (_, '\\') => {
+                chars.next();
+                quote
+            }
+            (Quote::None, '\'') => Quote::Single,
+            (Quote::None, '"') => Quote::Double,
+            (Quote::Double, '"') => Quote::None,
+            _ => quote,
+        };
+    }
+    quote
+}
+
+// cmd 只有双引号
+#[cfg(windows)]
+fn scan_quotes(quote: Quote, text: &str) -> Quote {
+    text.chars().filter(|c| *c == '"').fold(quote, |quote, _| if quote == Quote::Double { Quote::None } else { Quote::Double })
+}
+
+// sh 在解析之后才展开双引号中的变量，结果不再分词；单引号中不展开变量，先闭合单引号，引用之后再重新打开
+#[cfg(unix)]
+fn var_ref(name: &str, quote: Quote) -> String {
+    match quote {
+        Quote::None => format!("\"${{{}}}\"", name),
+        Quote::Single => format!("'\"${{{}}}\"'", name),
+        Quote::Double => format!("${{{}}}", name),
+    }
+}
+
+// cmd 的延迟展开（/V:ON 下的 !变量!）发生在解析之后，值中的 & | > 等不会被当作命令
+#[cfg(windows)]
+fn var_ref(name: &str, quote: Quote) -> String {
+    match quote {
+        Quote::Double => format!("!{}!", name),
+        _ => format!("\"!{}!\"", name),
+    }
+}
+
+// 后台启动外部命令，不等待结束，输出丢弃（避免破坏 TUI）
+pub fn spawn_shell(command: &RowCommand) -> Result<()> {
+    #[cfg(unix)]
+    let mut cmd = {
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(&command.command);
+        c
+    };
+    // 原样传给 cmd：默认的参数转义用反斜杠，cmd 并不识别
+    #[cfg(windows)]
+    let mut cmd = {
+        use std::os::windows::process::CommandExt;
+        let mut c = Command::new("cmd");
+        c.raw_arg("/V:ON /C").raw_arg(&command.command);
+        c
+    };
+    cmd.envs(command.env.iter().map(|(k, v)| (k, v)));
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    // 经 sh 执行展开后的命令，返回标准输出
+    fn run(template: &str, value: &str) -> String {
+        let command = expand_template(template, &["v".to_string()], &[value.to_string()]);
+        let output = Command::new("sh").arg("-c").arg(&command.command).envs(command.env).output().unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn expands_column_inside_single_quotes() {
+        let command = expand_template("echo 'id=${v}'", &["v".to_string()], &["1".to_string()]);
+        assert_eq!(command.command, "echo 'id='\"${SQLTUI_COL_0}\"''");
+        assert_eq!(run("printf %s 'id=${v};'", "it's $HOME"), "id=it's $HOME;");
+    }
+
+    #[test]
+    fn expands_column_inside_double_quotes_and_bare() {
+        assert_eq!(run("printf %s \"a ${v} b\"", "x  *  y"), "a x  *  y b");
+        assert_eq!(run("printf %s ${v}", "a b; echo c"), "a b; echo c");
+        assert_eq!(run("printf %s \"it's\" '${v}'", "1"), "it's1");
+    }
+}
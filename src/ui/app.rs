@@ -1,35 +1,67 @@
 use anyhow::Result;
-use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use anyhow::anyhow;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::io::Write;
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout},
     prelude::*,
     Terminal,
 };
-use std::io;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::future::Future;
+use std::io::Stdout;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
 use crate::{
-    config::Config,
-    db::{DbAdapter, new_adapter},
-    ui::components::{Content, Input, Sidebar, StatusBar},
+    config::{history, keymap::{Action, Keymap}, profiles::{Connections, ProfileUsage}, settings::{Settings, SIDEBAR_WIDTH_RANGE}, snippets::{self, ParamHistory, Snippets}, view_prefs::ViewPrefs, Config},
+    db::{DbAdapter, SshTunnel, is_connection_error, new_adapter, offline_adapter},
+    models::{ExecResult, ParamValue, QueryRecord, Snapshot},
+    ui::actions,
+    ui::batch,
+    ui::bench::RunningBench,
+    ui::xquery::{RunningXQuery, XQueryReport},
+    ui::clipboard,
+    ui::export::{self, ExportFormat},
+    ui::meta_command,
+    ui::query_task::{with_timeout, QueryOutcome, ResultStream, RunningQuery, Statement, StreamPoll},
+    ui::components::{ActionMenu, ConfirmDialog, Content, HelpOverlay, HistoryPanel, ImportWizard, Input, LogPanel, ParamForm, ResultList, Sidebar, SnippetForm, StatusBar, TableWatch},
+    ui::components::import_wizard::ImportOutcome,
+    ui::components::help_overlay::HelpOutcome,
+    ui::components::history_panel::HistoryOutcome,
+    ui::components::log_panel::LogOutcome,
+    ui::components::snippet_form::SnippetOutcome,
+    ui::import::{ImportReport, RunningImport},
+    ui::load_data::{self, LoadDataPlan},
+    ui::components::result_list::StatementStatus,
+    ui::components::confirm_dialog::ConfirmOutcome,
+    ui::components::param_form::ParamOutcome,
+    ui::components::status_bar::Health,
+    ui::terminal::{self, panicked, TerminalGuard},
+    ui::theme::{self, Theme},
+    sql::{self, TxControl},
+    t,
 };
 
 use crate::ui::components::content::ContentType;
 use crate::ui::components::input::InputMode;
 
+// 查询结果：(列名, 行)
+type QueryRows = (Vec<String>, Vec<Vec<String>>);
+
 pub struct App {
     // 数据库相关
-    db: Box<dyn DbAdapter>,
+    // 与后台执行语句的任务共享
+    db: Arc<dyn DbAdapter>,
     // 连接配置（用于重建带数据库名的连接池）
     config: Config,
+    // 配置文件中的通用设置
+    settings: Settings,
+    // 命令模式的按键绑定（默认值 + config.toml 的 [keys]）
+    keymap: Keymap,
     
     // UI 组件
     sidebar: Sidebar,
@@ -41,22 +73,123 @@ pub struct App {
     current_db: Option<String>,
     // 表名 -> 列名缓存（用于上下文补全）
     table_columns: HashMap<String, Vec<String>>,
+    // 结果行的外部命令菜单
+    row_action_menu: Option<ActionMenu>,
+    // 后台执行中的语句及其结果通道
+    running_query: Option<RunningQuery>,
+    query_seq: u64,
+    query_tx: UnboundedSender<(u64, QueryOutcome)>,
+    query_rx: UnboundedReceiver<(u64, QueryOutcome)>,
+    // 当前结果尚未取回的行（结果编号, 流），滚动到末尾附近时继续拉取
+    result_stream: Option<(u64, ResultStream)>,
+    // 刚完成的语句摘要与执行耗时，下一次绘制后补上渲染耗时显示
+    pending_timing: Option<(String, Duration)>,
+    // 当前结果自动追加的行数限制，取满时提示已截断
+    result_limit: Option<usize>,
+    // 一次提交的多条语句及各自的结果
+    batch: Option<ResultList>,
+    // 当前语句分隔符，DELIMITER 命令修改（默认 ;）
+    delimiter: String,
+    // 当前连接上有未结束的事务
+    tx_open: bool,
+    // 关闭时执行语句前隐式开启事务，需显式 COMMIT（\autocommit off）
+    autocommit: bool,
+    // 有未结束的事务时已提示过一次退出
+    quit_warned: bool,
+    // 等待填写参数的语句（表单, 语句, 是否垂直输出）
+    param_form: Option<(ParamForm, String, bool)>,
+    // 各语句上次填写的参数值，重跑时预填
+    param_memory: HashMap<String, Vec<String>>,
+    // \set 定义的会话变量，SQL 中以 ${name} 引用
+    variables: BTreeMap<String, String>,
+    // 待在外部编辑器中打开的文本（\e、Ctrl+X Ctrl+E），由主循环挂起界面后处理
+    editor_request: Option<String>,
+    // 已按下 Ctrl+X，等待组合键的第二个键
+    ctrl_x_pending: bool,
+    // \import 的导入向导与后台执行中的导入
+    import_wizard: Option<ImportWizard>,
+    running_import: Option<RunningImport>,
+    // \loaddata 待确认的语句与后台执行中的 mysql 客户端
+    pending_load: Option<(ConfirmDialog, String)>,
+    running_load: Option<tokio::task::JoinHandle<Result<String>>>,
+    // 语句库的选择菜单（菜单, 各项对应的名称）
+    snippet_menu: Option<(ActionMenu, Vec<String>)>,
+    // 填写 {{参数}} 的表单与语句模板
+    snippet_form: Option<(SnippetForm, String)>,
+    // F2 打开的历史面板
+    history_panel: Option<HistoryPanel>,
+    // ? 打开的帮助浮层
+    help_overlay: Option<HelpOverlay>,
+    // ~ 打开的日志面板
+    log_panel: Option<LogPanel>,
+    // 上次绘制时主内容区、侧边栏、结果区与输入栏的位置，供鼠标事件定位
+    main_area: Rect,
+    sidebar_area: Rect,
+    content_area: Rect,
+    input_area: Rect,
+    // 侧边栏占主内容区的宽度（百分比），可用鼠标拖动分隔线调整
+    sidebar_percent: u16,
+    // 正在拖动侧边栏与内容区之间的分隔线
+    dragging_divider: bool,
+    // 隐藏侧边栏，结果与表结构占满整个宽度
+    sidebar_hidden: bool,
+    // 主循环的终端；run 期间存在，前台加载等待时也在其上绘制进度
+    screen: Option<TerminalGuard>,
+    // 前台加载等待期间到达的事件，加载结束后由主循环按序处理
+    deferred_events: VecDeque<Event>,
+    // \bench 后台执行中的压测
+    running_bench: Option<RunningBench>,
+    // \xquery 后台执行中的跨库查询
+    running_xquery: Option<RunningXQuery>,
+    // 等待确认的破坏性 \xquery：(确认框, 目标库, 语句)
+    pending_xquery: Option<(ConfirmDialog, Vec<String>, String)>,
+    // \o 指定的输出文件：之后的查询结果在界面显示的同时写入该文件
+    output_file: Option<(PathBuf, std::fs::File)>,
+    // 等待确认的破坏性语句（确认框, 语句, 是否垂直输出）
+    pending_confirm: Option<(ConfirmDialog, Vec<String>, bool)>,
+    // 本次会话的执行记录（耗时/行数/标签）
+    query_log: Vec<QueryRecord>,
+    // 持久化历史的文件，与启动时从中载入的条数（之后的为本次会话的新命令）
+    history_path: PathBuf,
+    history_loaded: usize,
+    // 按表保存的视图偏好（列宽等）
+    view_prefs: ViewPrefs,
+    // 当前结果对应的表（"<库>.<表>"）；临时查询结果为 None，不持久化偏好
+    data_view_key: Option<String>,
+    // 当前结果对应的 SQL（用于 \snapshot）
+    last_query: Option<String>,
+    // 当前浏览的表及每页行数；临时查询结果为 None
+    data_table: Option<(String, usize)>,
+    // 当前浏览的页（从 0 开始）、表的估计行数，以及各表（"<库>.<表>"）上次浏览的页
+    data_page: usize,
+    data_row_estimate: Option<u64>,
+    table_pages: HashMap<String, usize>,
+    // 表数据的主键排序：None 为默认顺序，Some(true) 为降序（最后 N 行）
+    data_order_desc: Option<bool>,
+    // \watch-table 监视中的表（定时统计行数）
+    table_watch: Option<TableWatch>,
+    // \watch 定时重新执行的查询
+    query_watch: Option<QueryWatch>,
+    // \connect 建立的 SSH 隧道（启动时的隧道由 main 持有）
+    tunnel: Option<SshTunnel>,
+    // 空闲时探测连接的间隔（None 为不探测，如只读快照）与上次探测时间
+    ping_interval: Option<Duration>,
+    last_ping: Option<Instant>,
+    // 后台进行中的连接探测
+    running_ping: Option<tokio::task::JoinHandle<Health>>,
+    // 表监视的后台 COUNT(*)，未结束时不发起下一次采样
+    running_count: Option<tokio::task::JoinHandle<Result<QueryRows>>>,
 }
 
 impl App {
     pub async fn new(config: Config) -> Result<Self> {
         let db = new_adapter(&config).await?;
+        Self::connected(config, db).await
+    }
 
-        let mut app = Self {
-            db,
-            config: config.clone(),
-            sidebar: Sidebar::new(),
-            content: Content::new(),
-            status_bar: StatusBar::new(),
-            input: Input::new(),
-            current_db: None,
-            table_columns: HashMap::new(),
-        };
+    // 使用已建立的连接（如启动连接表单中建立的）初始化界面
+    pub async fn connected(config: Config, db: Box<dyn DbAdapter>) -> Result<Self> {
+        let mut app = Self::with_adapter(config, db)?;
 
         // 初始化数据
         app.load_databases().await?;
@@ -65,15 +198,245 @@ impl App {
 
         // 启动时显示帮助页
         app.content.set_content_type(ContentType::Help);
-        app.content.set_content(app.get_help_content());
+        app.content.set_content(app.welcome_content());
+
+        Ok(app)
+    }
 
+    // 只读打开结果快照：不连接数据库，直接展示快照中的结果
+    pub fn open_snapshot(config: Config, snapshot: Snapshot, path: &Path) -> Result<Self> {
+        let mut app = Self::with_adapter(config, offline_adapter())?;
+        app.ping_interval = None;
+        app.status_bar.set_driver(&snapshot.driver);
+        app.status_bar.set_username(format!("{}@{}:{}", snapshot.username, snapshot.host, snapshot.port));
+        app.status_bar.set_server_version(t!("app.snapshot_version", snapshot.created_at.format("%Y-%m-%d %H:%M:%S")));
+        app.status_bar.set_current_db(snapshot.database.clone());
+        app.status_bar.set_status(t!("app.snapshot_opened", path.display()));
+        app.input.add_to_history(snapshot.query.clone());
+        app.last_query = Some(snapshot.query);
+        app.content.set_table_data(snapshot.headers, snapshot.rows);
         Ok(app)
     }
 
+    fn with_adapter(config: Config, db: Box<dyn DbAdapter>) -> Result<Self> {
+        let settings = Settings::load()?;
+        settings.validate()?;
+        let keymap = Keymap::new(&settings.keys, settings.vim_keys)?;
+        theme::init(Theme::from_settings(&settings.theme)?);
+        let ping_interval = (settings.ping_interval > 0).then(|| Duration::from_secs(settings.ping_interval));
+        let view_prefs = ViewPrefs::load();
+        let sidebar_percent = view_prefs
+            .sidebar_width
+            .unwrap_or(settings.sidebar_width)
+            .clamp(*SIDEBAR_WIDTH_RANGE.start(), *SIDEBAR_WIDTH_RANGE.end());
+        let mut status_bar = StatusBar::new();
+        status_bar.set_read_only(config.read_only);
+        let (query_tx, query_rx) = unbounded_channel();
+        let history_path = history::path(&config);
+        let mut input = Input::new();
+        input.load_history(history::load_for(&history_path));
+        let history_loaded = input.history_entries().len();
+
+        Ok(Self {
+            db: Arc::from(db),
+            config: config.clone(),
+            settings,
+            keymap,
+            sidebar: Sidebar::new(),
+            content: Content::new(),
+            status_bar,
+            input,
+            current_db: None,
+            table_columns: HashMap::new(),
+            row_action_menu: None,
+            running_query: None,
+            query_seq: 0,
+            query_tx,
+            query_rx,
+            result_stream: None,
+            result_limit: None,
+            pending_timing: None,
+            batch: None,
+            delimiter: ";".to_string(),
+            tx_open: false,
+            autocommit: true,
+            quit_warned: false,
+            param_form: None,
+            param_memory: HashMap::new(),
+            variables: BTreeMap::new(),
+            editor_request: None,
+            ctrl_x_pending: false,
+            output_file: None,
+            import_wizard: None,
+            running_import: None,
+            pending_load: None,
+            running_load: None,
+            running_bench: None,
+            running_xquery: None,
+            pending_xquery: None,
+            history_panel: None,
+            help_overlay: None,
+            log_panel: None,
+            main_area: Rect::default(),
+            sidebar_area: Rect::default(),
+            content_area: Rect::default(),
+            input_area: Rect::default(),
+            sidebar_percent,
+            dragging_divider: false,
+            sidebar_hidden: false,
+            screen: None,
+            deferred_events: VecDeque::new(),
+            snippet_menu: None,
+            snippet_form: None,
+            pending_confirm: None,
+            query_log: Vec::new(),
+            history_path,
+            history_loaded,
+            view_prefs,
+            data_view_key: None,
+            last_query: None,
+            data_table: None,
+            data_page: 0,
+            data_row_estimate: None,
+            table_pages: HashMap::new(),
+            data_order_desc: None,
+            table_watch: None,
+            query_watch: None,
+            tunnel: None,
+            ping_interval,
+            last_ping: None,
+            running_ping: None,
+            running_count: None,
+        })
+    }
+
+    // 查询因连接断开失败时按当前配置重建连接；返回 true 表示已重连且可以安全重试一次。
+    // 写语句可能在断开前已经生效，重试会重复执行，只重连不重试，由用户决定
+    async fn reconnect_after(&mut self, err: &anyhow::Error, sql: &str) -> bool {
+        // 内存库重建即丢失数据，不自动重连
+        if !is_connection_error(err) || self.db.in_memory() {
+            return false;
+        }
+        match new_adapter(&self.config).await {
+            Ok(db) => {
+                self.db = Arc::from(db);
+                self.last_ping = None;
+                self.running_ping = None;
+                // 事务随断开的连接一起丢失，不能在新连接上重试事务中的语句
+                if self.tx_open {
+                    self.set_tx_open(false);
+                    self.status_bar.set_status(t!("app.reconnected_tx_lost").to_string());
+                    return false;
+                }
+                if sql::read_only_violation(sql, self.db.dialect()).is_some() {
+                    self.status_bar.set_status(t!("app.reconnected_not_retried").to_string());
+                    return false;
+                }
+                self.status_bar.set_status(t!("app.reconnected").to_string());
+                true
+            }
+            Err(e) => {
+                self.status_bar.set_status(t!("app.reconnect_failed", e));
+                false
+            }
+        }
+    }
+
+    // 前台执行查询（如浏览表数据）：受 \timeout 限制，连接断开时重连并重试一次
+    async fn run_query(&mut self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        // 事务中的语句共用一条连接：等待后台语句会卡住界面，未取完的结果流也会占住连接
+        if self.tx_open && self.running_query.is_some() {
+            return Err(anyhow!(t!("app.tx_statement_running")));
+        }
+        self.stop_result_stream();
+        let timeout = self.config.statement_timeout();
+        let db = self.db.clone();
+        let result = self.with_spinner(t!("loading.query").to_string(), with_timeout(timeout, db.execute_query_raw(query))).await;
+        if let Err(e) = &result {
+            if self.reconnect_after(e, query).await {
+                let db = self.db.clone();
+                return self.with_spinner(t!("loading.query").to_string(), with_timeout(timeout, db.execute_query_raw(query))).await;
+            }
+        }
+        result
+    }
+
+    // 等待 fut 完成；超过 LOADING_DELAY 仍未完成时按 SPINNER_TICK 在主循环的终端上重绘，
+    // 内容区标题显示进度动画与已用时间，慢服务器上界面不再像卡住一样（fut 不能借用 self）。
+    // 期间按 Ctrl+C 放弃等待并请求服务端中止语句，其余事件留待加载结束后处理
+    async fn with_spinner<T>(&mut self, label: String, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        tokio::pin!(fut);
+        let started = Instant::now();
+        let mut ticker = tokio::time::interval_at(tokio::time::Instant::from_std(started + LOADING_DELAY), SPINNER_TICK);
+        let mut shown = false;
+        let result = loop {
+            tokio::select! {
+                result = &mut fut => break result,
+                _ = ticker.tick() => {
+                    if !terminal::is_active() {
+                        continue;
+                    }
+                    if self.take_cancel_key() {
+                        // 先请求中止再丢弃 fut：丢弃时会清除记录的服务端 ID
+                        if let Err(e) = self.db.cancel_running().await {
+                            tracing::warn!(error = %e, "{}", t!("log.cancel_failed"));
+                        }
+                        break Err(anyhow!(t!("app.cancelled")));
+                    }
+                    if !shown {
+                        shown = true;
+                        self.content.set_loading(Some((label.clone(), started)));
+                    }
+                    let _ = self.draw();
+                }
+            }
+        };
+        if shown {
+            self.content.set_loading(None);
+        }
+        result
+    }
+
+    // 读取已到达的事件：遇到 Ctrl+C 返回 true，其余事件暂存给主循环
+    fn take_cancel_key(&mut self) -> bool {
+        while event::poll(Duration::ZERO).unwrap_or(false) {
+            match event::read() {
+                Ok(Event::Key(key)) if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return true;
+                }
+                Ok(event) => self.deferred_events.push_back(event),
+                Err(_) => break,
+            }
+        }
+        false
+    }
+
+    // 在主循环的终端上操作；守卫暂时取出，闭包中可以借用 self
+    fn with_screen(&mut self, f: impl FnOnce(&mut Self, &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()>) -> Result<()> {
+        let Some(mut screen) = self.screen.take() else { return Ok(()); };
+        let result = f(self, screen.terminal_mut());
+        self.screen = Some(screen);
+        result
+    }
+
+    fn draw(&mut self) -> Result<()> {
+        self.with_screen(|app, terminal| {
+            terminal.draw(|f| app.ui(f))?;
+            Ok(())
+        })
+    }
+
     async fn rebuild_pool_for_database(&mut self, database_name: Option<String>) -> Result<()> {
+        // 能在现有连接上切换的（如 DuckDB 内存库）不重连，避免丢失会话中的数据
+        if let Some(name) = database_name.as_deref() {
+            if self.db.switch_database(name).await? {
+                self.config.database = database_name;
+                return Ok(());
+            }
+        }
         // 更新配置中的数据库名
         self.config.database = database_name;
-        self.db = new_adapter(&self.config).await?;
+        self.db = Arc::from(new_adapter(&self.config).await?);
         Ok(())
     }
 
@@ -86,79 +449,211 @@ impl App {
             r.store(false, Ordering::SeqCst);
         })?;
 
-        // 设置终端
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        // 主循环
-        let result = self.run_app(&mut terminal, running).await;
-
-        // 无论成功还是失败，都要恢复终端状态
-        self.cleanup_terminal(&mut terminal)?;
-
+        // 设置终端；守卫在离开作用域（包括出错与 panic 展开）时恢复终端
+        let result = {
+            self.screen = Some(TerminalGuard::new()?);
+            if self.settings.mouse {
+                terminal::enable_mouse_capture()?;
+            }
+            let result = self.run_app(running).await;
+            // 守卫 Drop 时恢复终端
+            self.screen = None;
+            result
+        };
+        // 终端恢复后保存历史，失败时提示但不影响退出
+        if let Err(e) = self.save_history() {
+            eprintln!("{}", t!("app.history_save_to_failed", self.history_path.display(), e));
+        }
         result
     }
-    
-    fn cleanup_terminal<B: Backend + io::Write>(&self, terminal: &mut Terminal<B>) -> Result<()> {
-        // 使用更安全的清理方式，忽略所有错误
-        self.safe_cleanup_terminal(terminal);
-        Ok(())
-    }
-    
-    fn safe_cleanup_terminal<B: Backend + io::Write>(&self, terminal: &mut Terminal<B>) {
-        // 强制刷新输出
-        let _ = ratatui::backend::Backend::flush(terminal.backend_mut());
-        
-        // 确保显示光标
-        let _ = terminal.show_cursor();
-        
-        // 退出备用屏幕
-        let _ = execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen
-        );
-        
-        // 恢复终端模式
-        let _ = disable_raw_mode();
-        
-        // 再次强制刷新确保所有输出都被处理
-        let _ = ratatui::backend::Backend::flush(terminal.backend_mut());
-        
-        // 额外清理：直接操作 stdout
-        let _ = execute!(
-            io::stdout(),
-            LeaveAlternateScreen
-        );
-        let _ = io::stdout().flush();
+
+    fn save_history(&self) -> Result<()> {
+        let entries = self.input.history_entries();
+        history::append(&self.history_path, &entries[self.history_loaded.min(entries.len())..])
     }
 
-    async fn run_app<B: Backend + io::Write>(&mut self, terminal: &mut Terminal<B>, running: Arc<AtomicBool>) -> Result<()> {
+    async fn run_app(&mut self, running: Arc<AtomicBool>) -> Result<()> {
+        if let Some(path) = self.config.init_file.take() {
+            self.run_sql_file(&path, false).await;
+        }
         loop {
             // 检查是否收到退出信号
             if !running.load(Ordering::SeqCst) {
                 break;
             }
+            // 其他任务 panic 后终端已被恢复，不再继续绘制
+            if panicked() {
+                return Err(anyhow!(t!("app.task_panicked")));
+            }
             
-            terminal.draw(|f| self.ui(f))?;
+            // 收取后台语句的结果
+            while let Ok((id, outcome)) = self.query_rx.try_recv() {
+                self.finish_query(id, outcome).await;
+            }
+            self.poll_import();
+            self.poll_load_data().await;
+            self.poll_bench();
+            self.poll_xquery();
+            self.poll_ping().await;
+            self.poll_table_watch().await;
+            self.pull_result_rows().await;
+
+            let draw_started = Instant::now();
+            self.draw()?;
+            if let Some((summary, elapsed)) = self.pending_timing.take() {
+                // 与 mysql/psql 一致在执行后给出行数与耗时：执行（含取回首批行）+ 首次渲染
+                let text = t!(
+                    "app.query_timing",
+                    summary,
+                    format!("{:.3}", elapsed.as_secs_f64()),
+                    draw_started.elapsed().as_millis()
+                );
+                self.content.set_footer(Some(text.clone()));
+                self.status_bar.set_status(text);
+                self.draw()?;
+            }
+
+            // 按节拍轮询事件，没有按键时也定期重绘并收取后台结果：空闲时每 TICK 一次，
+            // 语句与后台任务执行期间按 SPINNER_TICK 推进进度动画；超时则执行到期的表监视、结果监视与连接探测
+            let watch_wait = self.table_watch.as_ref().filter(|_| self.running_count.is_none()).map(|w| w.time_until_tick());
+            let query_watch_wait = self.query_watch.as_ref().map(|w| w.time_until_tick());
+            let ping_wait = self.time_until_ping();
+            let background = self.running_import.is_some()
+                || self.running_load.is_some()
+                || self.running_bench.is_some()
+                || self.running_xquery.is_some()
+                || self.running_count.is_some();
+            let tick = if self.running_query.is_some() || background || self.content.needs_more_rows() {
+                SPINNER_TICK
+            } else {
+                TICK
+            };
+            let wait = watch_wait.into_iter().chain(query_watch_wait).chain(ping_wait).fold(tick, Duration::min);
+            if self.deferred_events.is_empty() && !event::poll(wait)? {
+                if watch_wait == Some(wait) {
+                    self.tick_table_watch();
+                }
+                if query_watch_wait == Some(wait) {
+                    self.tick_query_watch().await;
+                }
+                if ping_wait == Some(wait) {
+                    self.ping_server();
+                }
+                continue;
+            }
 
-            if let Event::Key(key) = event::read()? {
-                if self.handle_key_event(key).await? {
+            // 处理完已到达的全部事件再重绘，连续的滚轮、拖动与粘贴不必逐个重绘
+            loop {
+                let event = match self.deferred_events.pop_front() {
+                    Some(event) => event,
+                    None => event::read()?,
+                };
+                let quit = match event {
+                    Event::Key(key) => self.handle_key_event(key).await?,
+                    Event::Mouse(mouse) => {
+                        self.handle_mouse_event(mouse).await?;
+                        false
+                    }
+                    // 终端尺寸变化：按新尺寸重建缓冲区并整屏重绘
+                    Event::Resize(_, _) => {
+                        self.with_screen(|_, terminal| Ok(terminal.autoresize()?))?;
+                        false
+                    }
+                    _ => false,
+                };
+                if quit {
+                    return Ok(());
+                }
+                if let Some(text) = self.editor_request.take() {
+                    self.with_screen(|app, terminal| app.edit_externally(terminal, text))?;
+                }
+                if self.deferred_events.is_empty() && !event::poll(Duration::ZERO)? {
                     break;
                 }
             }
         }
-        
-        // 在退出前清理终端
-        self.cleanup_terminal(terminal)?;
         Ok(())
     }
 
+    // 挂起界面，在 $VISUAL / $EDITOR（默认 vi）中编辑文本，保存退出后载入 SQL 输入框待执行
+    fn edit_externally<B: Backend>(&mut self, terminal: &mut Terminal<B>, text: String) -> Result<()> {
+        let path = std::env::temp_dir().join(format!("sqltui-{}.sql", std::process::id()));
+        if let Err(e) = std::fs::write(&path, &text) {
+            self.status_bar.set_status(t!("app.temp_write_failed", e));
+            return Ok(());
+        }
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .unwrap_or_else(|_| "vi".to_string());
+        // 编辑器命令可带参数，如 "code --wait"
+        let mut parts = editor.split_whitespace();
+        let program = parts.next().unwrap_or("vi").to_string();
+        let args: Vec<String> = parts.map(str::to_string).collect();
+        let status = terminal::suspend(terminal, || {
+            std::process::Command::new(&program).args(&args).arg(&path).status()
+        })?;
+        let edited = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+        match (status, edited) {
+            (Ok(status), Ok(edited)) if status.success() => {
+                self.input.set_mode(InputMode::Sql);
+                self.input.set_input(edited.trim_end());
+                self.status_bar.set_status(t!("app.editor_loaded").to_string());
+            }
+            (Ok(status), Err(e)) if status.success() => {
+                self.status_bar.set_status(t!("app.editor_read_failed", e));
+            }
+            (Ok(status), _) => {
+                self.status_bar.set_status(t!("app.editor_failed", status));
+            }
+            (Err(e), _) => self.status_bar.set_status(t!("app.editor_spawn_failed", program, e)),
+        }
+        Ok(())
+    }
+
+    // 语句或后台任务执行期间连接本身在用，不探测；上次探测未结束时也不重复发起
+    fn time_until_ping(&self) -> Option<Duration> {
+        let interval = self.ping_interval?;
+        let busy = self.running_query.is_some()
+            || self.running_import.is_some()
+            || self.running_load.is_some()
+            || self.running_bench.is_some()
+            || self.running_xquery.is_some();
+        if busy || self.running_ping.is_some() {
+            return None;
+        }
+        Some(match self.last_ping {
+            Some(t) => interval.saturating_sub(t.elapsed()),
+            None => Duration::ZERO,
+        })
+    }
+
+    // 在后台探测连接，不阻塞界面；超时视为断开
+    fn ping_server(&mut self) {
+        let db = self.db.clone();
+        self.running_ping = Some(tokio::spawn(async move {
+            let started = Instant::now();
+            match tokio::time::timeout(Duration::from_secs(5), db.ping()).await {
+                Ok(Ok(())) => Health::Alive(started.elapsed()),
+                _ => Health::Down,
+            }
+        }));
+    }
+
+    // 收取探测结果并在状态栏显示往返耗时
+    async fn poll_ping(&mut self) {
+        if !self.running_ping.as_ref().is_some_and(|task| task.is_finished()) {
+            return;
+        }
+        let Some(task) = self.running_ping.take() else { return; };
+        let health = task.await.unwrap_or(Health::Down);
+        self.status_bar.set_health(Some(health));
+        self.last_ping = Some(Instant::now());
+    }
+
     fn is_at_root(&self) -> bool {
-        // 根目录：显示数据库列表且为帮助页面
-        self.sidebar.get_show_databases() && matches!(self.content.get_content_type(), ContentType::Help)
+        // 根目录：侧边栏未展开数据库且为帮助页面
+        !self.sidebar.is_expanded() && matches!(self.content.get_content_type(), ContentType::Help)
     }
 
     fn ui(&mut self, f: &mut Frame) {
@@ -172,33 +667,92 @@ impl App {
             .split(f.area());
 
         // 主内容区
+        let constraints = if self.sidebar_hidden {
+            [Constraint::Length(0), Constraint::Min(0)]
+        } else {
+            [
+                Constraint::Percentage(self.sidebar_percent), // 侧边栏
+                Constraint::Percentage(100 - self.sidebar_percent), // 内容区
+            ]
+        };
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(30), // 侧边栏
-                Constraint::Percentage(70), // 内容区
-            ])
+            .constraints(constraints)
             .split(chunks[1]);
+        self.main_area = chunks[1];
+        self.sidebar_area = main_chunks[0];
+        self.input_area = chunks[2];
 
         // 渲染组件
         self.status_bar.render(f, chunks[0]);
-        self.sidebar.render(f, main_chunks[0]);
-        self.content.render(f, main_chunks[1]);
+        if !self.sidebar_hidden {
+            self.sidebar.render(f, main_chunks[0]);
+        }
+        // 多语句执行时在结果上方列出各条语句的状态
+        let content_area = match self.batch.as_mut() {
+            Some(batch) => {
+                let parts = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(batch.height()), Constraint::Min(0)])
+                    .split(main_chunks[1]);
+                batch.render(f, parts[0]);
+                parts[1]
+            }
+            None => main_chunks[1],
+        };
+        self.content_area = content_area;
+        match &self.table_watch {
+            Some(watch) => watch.render(f, content_area),
+            None => self.content.render(f, content_area),
+        }
+        if let Some(menu) = self.row_action_menu.as_mut() {
+            menu.render(f, main_chunks[1]);
+        }
+        if let Some((menu, _)) = self.snippet_menu.as_mut() {
+            menu.render(f, main_chunks[1]);
+        }
+        if let Some((form, _)) = &self.snippet_form {
+            form.render(f, main_chunks[1]);
+        }
+        if let Some((dialog, _, _)) = &self.pending_confirm {
+            dialog.render(f, main_chunks[1]);
+        }
+        if let Some((form, _, _)) = &self.param_form {
+            form.render(f, main_chunks[1]);
+        }
+        if let Some(wizard) = &self.import_wizard {
+            wizard.render(f, main_chunks[1]);
+        }
+        if let Some((dialog, _)) = &self.pending_load {
+            dialog.render(f, main_chunks[1]);
+        }
+        if let Some((dialog, _, _)) = &self.pending_xquery {
+            dialog.render(f, main_chunks[1]);
+        }
+        if let Some(panel) = self.history_panel.as_mut() {
+            panel.render(f, main_chunks[1]);
+        }
+        if let Some(panel) = self.log_panel.as_mut() {
+            panel.render(f, main_chunks[1]);
+        }
+        if let Some(help) = self.help_overlay.as_mut() {
+            help.render(f, f.area());
+        }
 
         // 实时弹出建议浮框：不预留空间，直接覆盖在主内容区底部
-        if self.input.get_mode() == &InputMode::SQL && self.input.is_showing_suggestions() {
+        if self.input.get_mode() == &InputMode::Sql && self.input.is_showing_suggestions() {
             let suggestions = self.input.current_suggestions();
             if !suggestions.is_empty() {
                 let desired: u16 = std::cmp::min(suggestions.len() as u16 + 2, 8);
                 let max_h: u16 = main_chunks[1].height; // 仅覆盖在内容区内部
                 let height: u16 = std::cmp::max(1, std::cmp::min(desired, max_h));
 
-                // 根据光标列，计算浮框 x 偏移，尽量靠近光标
-                let screen_width = f.area().width as usize;
-                let cursor_col = self.input.cursor_display_column();
-                let cursor_col_u16 = (cursor_col as u16).min(f.area().width.saturating_sub(10));
-                let popup_width: u16 = (screen_width as u16).min(60); // 限宽
-                let x = cursor_col_u16.saturating_sub(2).min(main_chunks[1].x + main_chunks[1].width - popup_width);
+                // 根据光标显示列（输入框左边框 + 显示宽度），计算浮框 x 偏移，尽量靠近光标
+                let screen_width = f.area().width;
+                let popup_width: u16 = screen_width.min(60); // 限宽
+                let cursor_x = (chunks[2].x as usize + 1 + self.input.cursor_display_column())
+                    .min(u16::MAX as usize) as u16;
+                let x = cursor_x.saturating_sub(2).min(screen_width.saturating_sub(popup_width));
 
                 let y: u16 = main_chunks[1].y + main_chunks[1].height.saturating_sub(height);
                 let popup_area = ratatui::layout::Rect {
@@ -208,6 +762,44 @@ impl App {
                     height,
                 };
                 self.input.render_suggestions_popup(f, popup_area);
+
+                // 当前高亮项若为关键字/函数，在浮框上方显示签名与说明
+                if let Some(doc) = self.input.get_current_suggestion().and_then(|s| self.db.keyword_doc(&s)) {
+                    let doc_height: u16 = 4;
+                    if y >= main_chunks[1].y + doc_height {
+                        let doc_area = ratatui::layout::Rect {
+                            x,
+                            y: y - doc_height,
+                            width: popup_width,
+                            height: doc_height,
+                        };
+                        self.input.render_doc_popup(f, doc_area, doc.signature, doc.description);
+                    }
+                }
+            }
+        }
+
+        // 光标位于聚合函数上且无建议浮框时，提示其 NULL 处理语义
+        if self.input.get_mode() == &InputMode::Sql && !self.input.is_showing_suggestions() {
+            if let Some((name, _, _)) = self.input.aggregate_at_cursor() {
+                if let Some((semantics, default)) = sql::aggregate_null_semantics(&name) {
+                    let area = main_chunks[1];
+                    let height: u16 = 4;
+                    if area.height >= height {
+                        let hint_area = ratatui::layout::Rect {
+                            x: area.x,
+                            y: area.y + area.height - height,
+                            width: area.width.min(80),
+                            height,
+                        };
+                        let action = match default {
+                            Some(d) => t!("app.coalesce_hint", name, d),
+                            None => t!("app.coalesce_not_needed", name),
+                        };
+                        let title = format!("{}: {}", name, semantics);
+                        self.input.render_doc_popup(f, hint_area, &title, &action);
+                    }
+                }
             }
         }
 
@@ -215,8 +807,257 @@ impl App {
     }
 
     async fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
+        // 语句执行中 Ctrl+C 取消执行而不是退出
+        if self.running_query.is_some() && key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.cancel_query();
+            return Ok(false);
+        }
+        // 导入进行中 Ctrl+C 停止导入
+        if self.running_import.is_some() && key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            if let Some(import) = self.running_import.take() {
+                self.status_bar.set_status(t!("app.import_stopped", import.table, import.done));
+                import.cancel();
+            }
+            return Ok(false);
+        }
+        // 压测进行中 Ctrl+C 停止
+        if self.running_bench.is_some() && key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            if let Some(bench) = self.running_bench.take() {
+                self.status_bar.set_status(t!("app.bench_stopped", bench.done, bench.total));
+                bench.cancel();
+            }
+            return Ok(false);
+        }
+        // 跨库查询进行中 Ctrl+C 停止，尚未执行的库跳过
+        if self.running_xquery.is_some() && key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            if let Some(xquery) = self.running_xquery.take() {
+                self.content.set_loading(None);
+                self.status_bar.set_status(t!("app.xquery_stopped", xquery.done, xquery.total));
+                xquery.cancel();
+            }
+            return Ok(false);
+        }
+        // 破坏性语句的确认框独占按键
+        if let Some((dialog, _, _)) = self.pending_confirm.as_mut() {
+            match dialog.handle_key(key) {
+                ConfirmOutcome::Pending => {}
+                ConfirmOutcome::Cancelled => {
+                    self.pending_confirm = None;
+                    self.status_bar.set_status(t!("app.cancelled").to_string());
+                }
+                ConfirmOutcome::Confirmed => {
+                    if let Some((_, statements, vertical)) = self.pending_confirm.take() {
+                        self.execute_sql(statements, vertical).await;
+                    }
+                }
+            }
+            return Ok(false);
+        }
+        // 参数表单同样独占按键
+        if let Some((form, _, _)) = self.param_form.as_mut() {
+            match form.handle_key(key) {
+                ParamOutcome::Pending => {}
+                ParamOutcome::Cancelled => {
+                    self.param_form = None;
+                    self.status_bar.set_status(t!("app.cancelled").to_string());
+                }
+                ParamOutcome::Submitted(values) => {
+                    if let Some((_, statement, vertical)) = self.param_form.take() {
+                        self.run_with_params(statement, values, vertical).await;
+                    }
+                }
+            }
+            return Ok(false);
+        }
+
+        if let Some((dialog, _, _)) = self.pending_xquery.as_mut() {
+            match dialog.handle_key(key) {
+                ConfirmOutcome::Pending => {}
+                ConfirmOutcome::Cancelled => {
+                    self.pending_xquery = None;
+                    self.status_bar.set_status(t!("app.cancelled").to_string());
+                }
+                ConfirmOutcome::Confirmed => {
+                    if let Some((_, databases, query)) = self.pending_xquery.take() {
+                        self.start_xquery(databases, query);
+                    }
+                }
+            }
+            return Ok(false);
+        }
+
+        if let Some((dialog, _)) = self.pending_load.as_mut() {
+            match dialog.handle_key(key) {
+                ConfirmOutcome::Pending => {}
+                ConfirmOutcome::Cancelled => {
+                    self.pending_load = None;
+                    self.status_bar.set_status(t!("app.import_cancelled").to_string());
+                }
+                ConfirmOutcome::Confirmed => {
+                    if let Some((_, statement)) = self.pending_load.take() {
+                        let config = self.config.clone();
+                        let database = self.current_db.clone();
+                        self.status_bar.set_status(t!("app.load_data_running").to_string());
+                        self.running_load = Some(tokio::spawn(async move {
+                            load_data::run(&config, database.as_deref(), &statement).await
+                        }));
+                    }
+                }
+            }
+            return Ok(false);
+        }
+        // 导入向导同样独占按键
+        if let Some(wizard) = self.import_wizard.as_mut() {
+            match wizard.handle_key(key) {
+                ImportOutcome::Pending => {}
+                ImportOutcome::Cancelled => {
+                    self.import_wizard = None;
+                    self.status_bar.set_status(t!("app.import_cancelled").to_string());
+                }
+                ImportOutcome::Start(job) => {
+                    self.import_wizard = None;
+                    self.status_bar.set_status(t!("app.import_started", job.table, job.rows.len()));
+                    self.running_import = Some(RunningImport::spawn(self.db.clone(), job));
+                }
+            }
+            return Ok(false);
+        }
+
+        // 帮助浮层独占按键（输入即筛选）
+        if let Some(help) = self.help_overlay.as_mut() {
+            if let HelpOutcome::Closed = help.handle_key(key) {
+                self.help_overlay = None;
+            }
+            return Ok(false);
+        }
+        // 日志面板独占按键
+        if let Some(panel) = self.log_panel.as_mut() {
+            if let LogOutcome::Closed = panel.handle_key(key) {
+                self.log_panel = None;
+            }
+            return Ok(false);
+        }
+        // 历史面板独占按键；F2 在任意模式下打开
+        if let Some(panel) = self.history_panel.as_mut() {
+            match panel.handle_key(key) {
+                HistoryOutcome::Pending => {}
+                HistoryOutcome::Closed => self.history_panel = None,
+                HistoryOutcome::Load(sql) => {
+                    self.history_panel = None;
+                    self.input.set_mode(InputMode::Sql);
+                    self.input.set_input(&sql);
+                }
+                HistoryOutcome::Run(sql) => {
+                    self.history_panel = None;
+                    self.input.set_mode(InputMode::Sql);
+                    self.input.set_input(&sql);
+                    self.input.hide_suggestions();
+                    if let Err(e) = self.handle_sql_command().await {
+                        self.content.set_content_type(ContentType::Error);
+                        self.content.set_content(t!("content.sql_error", e));
+                    }
+                }
+            }
+            return Ok(false);
+        }
+        // 语句库菜单独占按键
+        if let Some((menu, names)) = self.snippet_menu.as_mut() {
+            match key.code {
+                KeyCode::Up => menu.previous(),
+                KeyCode::Down => menu.next(),
+                KeyCode::Esc | KeyCode::Char('q') => self.snippet_menu = None,
+                KeyCode::Enter => {
+                    let name = menu.selected().and_then(|i| names.get(i)).cloned();
+                    self.snippet_menu = None;
+                    if let Some(name) = name {
+                        self.use_snippet(&name);
+                    }
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+        if let Some((form, _)) = self.snippet_form.as_mut() {
+            match form.handle_key(key) {
+                SnippetOutcome::Pending => {}
+                SnippetOutcome::Cancelled => self.snippet_form = None,
+                SnippetOutcome::Submitted(values) => {
+                    if let Some((_, template)) = self.snippet_form.take() {
+                        self.run_snippet(&template, values).await;
+                    }
+                }
+            }
+            return Ok(false);
+        }
+        // 历史面板、帮助、日志与侧边栏开关绑定在功能键或组合键上时在任意模式下可用，绑定在字符键上时仅命令模式
+        match self.keymap.global_action(&key) {
+            Some(Action::History) => {
+                self.history_panel = Some(HistoryPanel::new(&self.query_log, self.input.history_entries()));
+                return Ok(false);
+            }
+            Some(Action::Help) => {
+                self.help_overlay = Some(HelpOverlay::new(&self.keymap));
+                return Ok(false);
+            }
+            Some(Action::Logs) => {
+                self.log_panel = Some(LogPanel::default());
+                return Ok(false);
+            }
+            Some(Action::ToggleSidebar) => {
+                self.toggle_sidebar();
+                return Ok(false);
+            }
+            _ => {}
+        }
+
+        // 结果内查找：输入查找词，Enter 定位，Esc 取消
+        if self.input.get_mode() == &InputMode::Search {
+            self.handle_search_key(key);
+            return Ok(false);
+        }
+
+        // 侧边栏过滤：边输入边缩小列表，Enter 打开选中项，Esc 清除过滤
+        if self.input.get_mode() == &InputMode::Filter {
+            self.handle_filter_key(key).await?;
+            return Ok(false);
+        }
+
         // 如果在SQL模式下，只处理特定的键
-        if self.input.get_mode() == &InputMode::SQL {
+        if self.input.get_mode() == &InputMode::Sql {
+            // Ctrl+X Ctrl+E：在外部编辑器中编辑当前输入
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            if std::mem::take(&mut self.ctrl_x_pending) && ctrl && key.code == KeyCode::Char('e') {
+                self.editor_request = Some(self.input.get_input().to_string());
+                return Ok(false);
+            }
+            if ctrl && key.code == KeyCode::Char('x') {
+                self.ctrl_x_pending = true;
+                return Ok(false);
+            }
+            // Ctrl+R 反向查找历史：输入即筛选，Enter 执行匹配的命令，Tab/方向键载入编辑，Esc 取消
+            if ctrl && key.code == KeyCode::Char('r') {
+                self.input.start_reverse_search();
+                return Ok(false);
+            }
+            if self.input.is_reverse_searching() {
+                match key.code {
+                    KeyCode::Esc => self.input.cancel_reverse_search(),
+                    KeyCode::Char('g') if ctrl => self.input.cancel_reverse_search(),
+                    KeyCode::Backspace => self.input.reverse_search_pop(),
+                    KeyCode::Char(c) if !ctrl => self.input.reverse_search_push(c),
+                    KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End => {
+                        self.input.accept_reverse_search();
+                    }
+                    // 载入后按普通 Enter 执行
+                    KeyCode::Enter => {
+                        self.input.accept_reverse_search();
+                    }
+                    _ => {}
+                }
+                if key.code != KeyCode::Enter {
+                    return Ok(false);
+                }
+            }
             match key.code {
                 KeyCode::Esc => {
                     // 优先关闭建议框，其次退出 SQL 模式
@@ -248,7 +1089,7 @@ impl App {
                         Err(e) => {
                             // SQL 执行失败时显示错误，但不退出程序
                             self.content.set_content_type(ContentType::Error);
-                            self.content.set_content(format!("SQL 执行错误: {}", e));
+                            self.content.set_content(t!("content.sql_error", e));
                         }
                     }
                 }
@@ -258,10 +1099,7 @@ impl App {
                     } else {
                         // 上箭头键：历史记录向上
                         if let Some(history_command) = self.input.get_history_up() {
-                            self.input.clear();
-                            for ch in history_command.chars() {
-                                self.input.add_char(ch);
-                            }
+                            self.input.set_input(&history_command);
                         }
                     }
                 }
@@ -271,10 +1109,7 @@ impl App {
                     } else {
                         // 下箭头键：历史记录向下
                         if let Some(history_command) = self.input.get_history_down() {
-                            self.input.clear();
-                            for ch in history_command.chars() {
-                                self.input.add_char(ch);
-                            }
+                            self.input.set_input(&history_command);
                         } else {
                             // 如果到达历史记录末尾，清空输入
                             self.input.clear();
@@ -335,6 +1170,12 @@ impl App {
                         match ch {
                             'b' | 'B' => { self.input.move_word_left(); }
                             'f' | 'F' => { self.input.move_word_right(); }
+                            'c' | 'C' => {
+                                // 将光标处的聚合函数包裹为 COALESCE(..., 默认值)
+                                if !self.input.wrap_aggregate_in_coalesce() {
+                                    self.input.add_char(ch);
+                                }
+                            }
                             _ => { self.input.add_char(ch); }
                         }
                         self.update_context_suggestions_async().await;
@@ -355,28 +1196,32 @@ impl App {
             return Ok(false);
         }
 
-        // 在CMD模式下处理所有快捷键
-        match key.code {
-            KeyCode::Char('q') => {
-                // 仅在根目录退出；其他情况下等价于 Esc 返回上一级
+        // 行操作菜单打开时独占按键
+        if self.row_action_menu.is_some() {
+            self.handle_row_action_key(key);
+            return Ok(false);
+        }
+
+        // 在CMD模式下处理所有快捷键（按键经 keymap 映射为操作）
+        let Some(action) = self.keymap.action(&key) else { return Ok(false); };
+        let table_data = matches!(self.content.get_content_type(), ContentType::TableData);
+        match action {
+            Action::Quit => {
+                // 仅在根目录退出；其他情况下等价于返回上一级
                 if self.is_at_root() {
-                    return Ok(true);
+                    return Ok(self.confirm_quit().await);
                 } else {
                     self.handle_escape().await?;
                 }
             }
-            KeyCode::Char('c') => {
-                // 只有 Ctrl+C 才退出
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    return Ok(true);
-                }
-                // 否则继续处理其他逻辑
+            Action::ForceQuit => {
+                return Ok(self.confirm_quit().await);
             }
-            KeyCode::Esc => {
-                // Esc 键用于层级导航
+            Action::Back => {
+                // 层级导航
                 self.handle_escape().await?;
             }
-            KeyCode::Up => {
+            Action::Up => {
                 // 根据内容类型处理滚动
                 match self.content.get_content_type() {
                     ContentType::TableSchema => {
@@ -390,7 +1235,7 @@ impl App {
                     }
                 }
             }
-            KeyCode::Down => {
+            Action::Down => {
                 // 根据内容类型处理滚动
                 match self.content.get_content_type() {
                     ContentType::TableSchema => {
@@ -404,74 +1249,504 @@ impl App {
                     }
                 }
             }
-            KeyCode::Left => {
-                // 如果在表数据模式下，处理水平滚动
-                if matches!(self.content.get_content_type(), ContentType::TableData) {
-                    self.content.scroll_data_left();
+            Action::PageUp | Action::PageDown | Action::Top | Action::Bottom if table_data => {
+                // 结果整页滚动与首/末行跳转
+                // 浏览表数据时，在已加载行的首/末页继续翻页则加载上一页/下一页
+                let browsing = self.data_table.is_some() && !self.content.is_derived();
+                match action {
+                    Action::PageUp if browsing && self.content.at_data_top() && self.data_page > 0 => {
+                        self.change_data_page(-1).await?;
+                    }
+                    Action::PageDown if browsing && self.content.at_data_bottom() => {
+                        self.change_data_page(1).await?;
+                    }
+                    Action::PageUp => self.content.page_data_up(),
+                    Action::PageDown => self.content.page_data_down(),
+                    Action::Top => self.content.scroll_data_top(),
+                    _ => self.content.scroll_data_bottom(),
                 }
             }
-            KeyCode::Right => {
-                // 如果在表数据模式下，处理水平滚动
-                if matches!(self.content.get_content_type(), ContentType::TableData) {
-                    self.content.scroll_data_right();
+            Action::PrevPage | Action::NextPage => {
+                // 表数据上一页/下一页；多语句执行后切换各条语句的结果
+                let delta = if action == Action::NextPage { 1 } else { -1 };
+                if table_data && self.data_table.is_some() {
+                    self.change_data_page(delta).await?;
+                } else if self.batch.is_some() {
+                    self.show_batch_entry(delta);
                 }
             }
-            KeyCode::Enter => {
-                self.handle_enter().await?;
+            Action::Search if table_data => {
+                // 结果内查找
+                self.input.clear();
+                self.input.set_mode(InputMode::Search);
             }
-            KeyCode::Char(' ') => {
-                // 在SQL模式下，空格键直接添加到输入中
-                if self.input.get_mode() == &InputMode::SQL {
-                    self.input.add_char(' ');
-                } else {
-                    self.handle_space().await?;
+            Action::Search if !self.sidebar_hidden => {
+                // 侧边栏过滤，以当前过滤词开始
+                let filter = self.sidebar.filter().to_string();
+                self.input.clear();
+                self.input.set_mode(InputMode::Filter);
+                for ch in filter.chars() {
+                    self.input.add_char(ch);
                 }
             }
-            KeyCode::Char('d') => {
-                self.handle_database_detail().await?;
-            }
-            KeyCode::Char('t') => {
-                self.handle_table_detail().await?;
+            Action::SearchNext | Action::SearchPrev if table_data => {
+                // 下一个/上一个匹配行
+                if let Some(term) = self.content.search_term().map(|t| t.to_string()) {
+                    if !self.content.search_next(action == Action::SearchNext) {
+                        self.status_bar.set_status(t!("status.not_found", term));
+                    }
+                }
             }
-            KeyCode::Char('s') => {
-                self.handle_switch_database().await?;
+            // 表数据模式下水平滚动
+            Action::Left if table_data => self.content.scroll_data_left(),
+            Action::Right if table_data => self.content.scroll_data_right(),
+            // 侧边栏树：← 回到所属数据库或收起，→ 展开数据库
+            Action::Left => {
+                let moved = self.sidebar.select_parent();
+                if !moved && self.sidebar.current_db_selected() && self.sidebar.is_expanded() {
+                    self.sidebar.set_expanded(false);
+                }
             }
-                KeyCode::Char(':') => {
-                    // 进入SQL模式
-                    self.input.set_mode(InputMode::SQL);
-                    // 更新当前数据库信息
-                    self.input.set_current_db(self.current_db.clone());
-                    // 注入关键字（来自适配器）
-                    let kws: Vec<String> = self.db.keywords().iter().map(|s| s.to_string()).collect();
-                    self.input.set_keywords(kws);
-                    // 重置历史记录索引
-                    self.input.reset_history_index();
-                    // 初始显示建议（关键字热词）
-                    self.input.show_suggestions();
+            Action::Right => {
+                let expanded = self.sidebar.current_db_selected() && self.sidebar.is_expanded();
+                if self.sidebar.get_selected_database().is_some() && !expanded {
+                    self.handle_enter().await?;
                 }
-            _ => {}
+            }
+            Action::Open => {
+                self.handle_enter().await?;
+            }
+            Action::Browse => {
+                self.handle_space().await?;
+            }
+            Action::DatabaseDetail => {
+                self.handle_database_detail().await?;
+            }
+            Action::TableDetail => {
+                self.handle_table_detail().await?;
+            }
+            Action::SwitchDatabase => {
+                self.handle_switch_database().await?;
+            }
+            // 客户端合并重复行
+            Action::Dedup if table_data => self.content.dedup_rows(),
+            // 结果统计摘要
+            Action::Stats if table_data => self.content.toggle_stats(),
+            // 时间列相对时间显示
+            Action::RelativeTime if table_data => self.content.toggle_relative_time(),
+            // 焦点单元格详情（完整值/绝对时间）
+            Action::CellDetail if table_data => self.content.toggle_cell_popup(),
+            Action::NarrowColumn | Action::WidenColumn if table_data => {
+                // 调整焦点列宽度
+                let delta = if action == Action::WidenColumn { 2 } else { -2 };
+                self.adjust_column_width(delta);
+            }
+            Action::NarrowColumn | Action::WidenColumn => {
+                // 结果视图以外调整侧边栏宽度
+                let delta = if action == Action::WidenColumn { 5 } else { -5 };
+                self.adjust_sidebar_width(delta);
+            }
+            Action::PrimaryKeyOrder if table_data => {
+                // 浏览表数据时切换前 N 行 / 最后 N 行（按主键）
+                self.toggle_primary_key_order().await?;
+            }
+            // 焦点行的外部命令菜单
+            Action::RowActions if table_data => self.open_row_action_menu(),
+            // 复制到剪贴板：单元格、整行、整个结果（TSV / Markdown）
+            Action::YankCell | Action::YankRow | Action::YankResult | Action::YankMarkdown if table_data => {
+                self.yank(action);
+            }
+            // 焦点行复制为 INSERT 语句
+            Action::CopyInsert if table_data => self.copy_focused_insert(),
+            Action::Export if table_data => {
+                // 导出当前结果：预填 \export 命令，可修改格式与路径后回车
+                let name = self.data_table.as_ref().map_or("result", |(table, _)| table.as_str());
+                let path = format!("{}-{}.csv", name, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+                self.input.set_mode(InputMode::Sql);
+                self.input.set_input(&format!("\\export csv {}", path));
+            }
+            Action::Snippets => self.open_snippet_menu(),
+            Action::History => {
+                self.history_panel = Some(HistoryPanel::new(&self.query_log, self.input.history_entries()));
+            }
+            Action::Help => self.help_overlay = Some(HelpOverlay::new(&self.keymap)),
+            Action::Logs => self.log_panel = Some(LogPanel::default()),
+            Action::ToggleSidebar => self.toggle_sidebar(),
+            // 焦点列（最左可见列）的不同值及计数
+            Action::Distinct if table_data => {
+                let shown = self.content.distinct_focused_column();
+                if !shown {
+                    self.status_bar.set_status(t!("app.distinct_derived").to_string());
+                }
+            }
+            Action::SqlMode => self.enter_sql_mode(),
+            _ => {}
         }
         Ok(false)
     }
 
+    fn enter_sql_mode(&mut self) {
+        // 进入SQL模式
+        self.input.set_mode(InputMode::Sql);
+        // 更新当前数据库信息
+        self.input.set_current_db(self.current_db.clone());
+        // 注入关键字（来自适配器）
+        let kws: Vec<String> = self.db.keywords().iter().map(|s| s.to_string()).collect();
+        self.input.set_keywords(kws);
+        // 重置历史记录索引
+        self.input.reset_history_index();
+        // 初始显示建议（关键字热词）
+        self.input.show_suggestions();
+    }
+
+    // 是否有弹窗或浮层独占输入
+    fn modal_open(&self) -> bool {
+        self.help_overlay.is_some()
+            || self.log_panel.is_some()
+            || self.history_panel.is_some()
+            || self.snippet_menu.is_some()
+            || self.snippet_form.is_some()
+            || self.row_action_menu.is_some()
+            || self.pending_confirm.is_some()
+            || self.param_form.is_some()
+            || self.import_wizard.is_some()
+            || self.pending_load.is_some()
+            || self.pending_xquery.is_some()
+    }
+
+    // 鼠标：点击侧边栏选择（再点已选中的项即打开）、点击结果行设为焦点行、点击输入栏进入 SQL 模式、
+    // 滚轮滚动侧边栏与表结构/数据、拖动侧边栏与内容区之间的分隔线调整宽度；弹窗打开时忽略
+    async fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        if self.modal_open() {
+            return Ok(());
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // 分隔线：侧边栏右边框与内容区左边框
+                let divider = self.sidebar_area.right().saturating_sub(1);
+                let in_main = mouse.row >= self.main_area.y && mouse.row < self.main_area.bottom();
+                if in_main && !self.sidebar_hidden && (mouse.column == divider || mouse.column == divider + 1) {
+                    self.dragging_divider = true;
+                } else if self.sidebar_area.contains(position) {
+                    if let Some(index) = self.sidebar.item_at(position) {
+                        if self.sidebar.selected_index() == Some(index) {
+                            self.handle_enter().await?;
+                        } else {
+                            self.sidebar.select(index);
+                        }
+                    }
+                } else if self.content_area.contains(position) {
+                    self.content.click_row(position);
+                } else if self.input_area.contains(position) && self.input.get_mode() == &InputMode::Command {
+                    self.enter_sql_mode();
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_divider => {
+                let offset = u32::from(mouse.column.saturating_sub(self.main_area.x));
+                let percent = offset * 100 / u32::from(self.main_area.width.max(1));
+                self.sidebar_percent = (percent as u16).clamp(*SIDEBAR_WIDTH_RANGE.start(), *SIDEBAR_WIDTH_RANGE.end());
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragging_divider => {
+                self.dragging_divider = false;
+                self.save_sidebar_width();
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let up = mouse.kind == MouseEventKind::ScrollUp;
+                if self.sidebar_area.contains(position) {
+                    if up { self.sidebar.previous_item() } else { self.sidebar.next_item() }
+                } else if self.content_area.contains(position) {
+                    match self.content.get_content_type() {
+                        ContentType::TableSchema if up => self.content.scroll_schema_up(),
+                        ContentType::TableSchema => self.content.scroll_schema_down(),
+                        ContentType::TableData if up => self.content.scroll_data_up(),
+                        ContentType::TableData => self.content.scroll_data_down(),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.input.clear();
+                self.input.set_mode(InputMode::Command);
+            }
+            KeyCode::Enter => {
+                let term = self.input.get_input().to_string();
+                self.input.clear();
+                self.input.set_mode(InputMode::Command);
+                if !self.content.search(&term) && !term.is_empty() {
+                    self.status_bar.set_status(t!("status.not_found", term));
+                }
+            }
+            KeyCode::Backspace => self.input.delete_char(),
+            KeyCode::Left => self.input.move_cursor_left(),
+            KeyCode::Right => self.input.move_cursor_right(),
+            KeyCode::Char(ch) => self.input.add_char(ch),
+            _ => {}
+        }
+    }
+
+    async fn handle_filter_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => {
+                self.input.clear();
+                self.input.set_mode(InputMode::Command);
+                self.sidebar.set_filter("");
+                return Ok(());
+            }
+            KeyCode::Enter => {
+                self.input.clear();
+                self.input.set_mode(InputMode::Command);
+                return self.handle_enter().await;
+            }
+            KeyCode::Up => self.sidebar.previous_item(),
+            KeyCode::Down => self.sidebar.next_item(),
+            KeyCode::Backspace => self.input.delete_char(),
+            KeyCode::Left => self.input.move_cursor_left(),
+            KeyCode::Right => self.input.move_cursor_right(),
+            KeyCode::Char(ch) => self.input.add_char(ch),
+            _ => return Ok(()),
+        }
+        if !matches!(key.code, KeyCode::Up | KeyCode::Down) {
+            let filter = self.input.get_input().to_string();
+            self.sidebar.set_filter(&filter);
+        }
+        Ok(())
+    }
+
+    fn adjust_column_width(&mut self, delta: i32) {
+        let Some((column, width)) = self.content.adjust_focused_column_width(delta) else { return; };
+        let mut status = t!("app.column_width", column, width);
+        if let Some(key) = &self.data_view_key {
+            self.view_prefs.set_column_width(key, &column, width);
+            if let Err(e) = self.view_prefs.save() {
+                status = t!("app.view_prefs_save_failed", status, e);
+            }
+        }
+        self.status_bar.set_status(status);
+    }
+
+    fn toggle_sidebar(&mut self) {
+        self.sidebar_hidden = !self.sidebar_hidden;
+        let status = if self.sidebar_hidden {
+            match self.keymap.keys_label("toggle_sidebar") {
+                Some(keys) => t!("app.sidebar_hidden_keys", keys),
+                None => t!("app.sidebar_hidden").to_string(),
+            }
+        } else {
+            t!("app.sidebar_shown").to_string()
+        };
+        self.status_bar.set_status(status);
+    }
+
+    fn adjust_sidebar_width(&mut self, delta: i16) {
+        self.sidebar_hidden = false;
+        let width = self.sidebar_percent.saturating_add_signed(delta);
+        self.sidebar_percent = width.clamp(*SIDEBAR_WIDTH_RANGE.start(), *SIDEBAR_WIDTH_RANGE.end());
+        self.save_sidebar_width();
+    }
+
+    // 记住侧边栏宽度，下次启动沿用
+    fn save_sidebar_width(&mut self) {
+        let mut status = t!("app.sidebar_width", self.sidebar_percent);
+        self.view_prefs.sidebar_width = Some(self.sidebar_percent);
+        if let Err(e) = self.view_prefs.save() {
+            status = t!("app.view_prefs_save_failed", status, e);
+        }
+        self.status_bar.set_status(status);
+    }
+
+    // \save 名称 [语句]：把语句（省略时为最近执行的一条 SQL）存入语句库，同名覆盖
+    fn save_snippet(&mut self, command: &str) {
+        let rest = meta_command::strip_command(command, "\\save").unwrap_or("");
+        let (name, sql) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        if name.is_empty() {
+            self.status_bar.set_status(t!("app.save_usage").to_string());
+            return;
+        }
+        let sql = match sql.trim() {
+            "" => {
+                // 跳过刚写入历史的本条 \save 与其他元命令
+                let recent = self.input.history_entries().iter().rev().skip(1).find(|c| !c.trim_start().starts_with('\\'));
+                match recent {
+                    Some(sql) => sql.trim().to_string(),
+                    None => {
+                        self.status_bar.set_status(t!("app.save_nothing").to_string());
+                        return;
+                    }
+                }
+            }
+            sql => sql.to_string(),
+        };
+        let mut snippets = match Snippets::load() {
+            Ok(snippets) => snippets,
+            Err(e) => {
+                self.status_bar.set_status(e.to_string());
+                return;
+            }
+        };
+        let replaced = snippets.insert(name, sql);
+        match snippets.save() {
+            Ok(()) if replaced => self.status_bar.set_status(t!("app.snippet_updated", name)),
+            Ok(()) => self.status_bar.set_status(t!("app.snippet_saved", name, name)),
+            Err(e) => self.status_bar.set_status(t!("app.snippet_save_failed", e)),
+        }
+    }
+
+    // \snip 名称：把语句载入输入框；不带名称时打开选择菜单；\snip -d 名称 删除
+    fn handle_snip(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\snip").unwrap_or(""));
+        match args.as_slice() {
+            [] => self.open_snippet_menu(),
+            [name] => self.use_snippet(name),
+            [flag, name] if flag == "-d" => {
+                let result = Snippets::load().and_then(|mut snippets| {
+                    let removed = snippets.remove(name);
+                    snippets.save().map(|_| removed)
+                });
+                match result {
+                    Ok(true) => self.status_bar.set_status(t!("app.snippet_deleted", name)),
+                    Ok(false) => self.status_bar.set_status(t!("app.snippet_missing", name)),
+                    Err(e) => self.status_bar.set_status(t!("app.snippet_update_failed", e)),
+                }
+            }
+            _ => self.status_bar.set_status(t!("app.snip_usage").to_string()),
+        }
+    }
+
+    fn open_snippet_menu(&mut self) {
+        let snippets = match Snippets::load() {
+            Ok(snippets) => snippets,
+            Err(e) => {
+                self.status_bar.set_status(e.to_string());
+                return;
+            }
+        };
+        if snippets.snippets.is_empty() {
+            self.status_bar.set_status(t!("app.snippets_empty", Snippets::path().display()));
+            return;
+        }
+        let width = snippets.snippets.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+        let items = snippets
+            .snippets
+            .iter()
+            .map(|(name, snippet)| {
+                let preview = snippet.description.clone().unwrap_or_else(|| snippet.sql.split_whitespace().collect::<Vec<_>>().join(" "));
+                let preview: String = preview.chars().take(60).collect();
+                format!("{:<w$}  {}", name, preview, w = width)
+            })
+            .collect();
+        let names = snippets.snippets.keys().cloned().collect();
+        self.snippet_menu = Some((ActionMenu::new(t!("app.snippets_title"), items), names));
+    }
+
+    // 载入语句库中的语句到 SQL 输入框，回车执行；含 {{参数}} 时先弹出表单逐个填写，填完直接执行
+    fn use_snippet(&mut self, name: &str) {
+        match Snippets::load().map(|snippets| snippets.get(name).map(|s| s.sql.clone())) {
+            Ok(Some(sql)) if !snippets::template_params(&sql).is_empty() => {
+                let history = ParamHistory::load();
+                let form = SnippetForm::new(name.to_string(), snippets::template_params(&sql), |param| history.get(param).to_vec());
+                self.snippet_form = Some((form, sql));
+            }
+            Ok(Some(sql)) => {
+                self.input.set_mode(InputMode::Sql);
+                self.input.hide_suggestions();
+                self.input.set_input(&sql);
+                self.status_bar.set_status(t!("app.snippet_loaded", name));
+            }
+            Ok(None) => self.status_bar.set_status(t!("app.snippet_missing_hint", name)),
+            Err(e) => self.status_bar.set_status(e.to_string()),
+        }
+    }
+
+    // 按填写的值展开语句模板并执行；记住用过的值供下次翻看
+    async fn run_snippet(&mut self, template: &str, values: Vec<(String, String)>) {
+        let mut history = ParamHistory::load();
+        for (name, value) in &values {
+            history.record(name, value);
+        }
+        if let Err(e) = history.save() {
+            self.status_bar.set_status(t!("app.param_memory_failed", e));
+        }
+        let sql = snippets::expand_template(template, &values.into_iter().collect());
+        self.input.set_mode(InputMode::Sql);
+        self.input.hide_suggestions();
+        self.input.set_input(&sql);
+        if let Err(e) = self.handle_sql_command().await {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("content.sql_error", e));
+        }
+    }
+
+    fn open_row_action_menu(&mut self) {
+        if self.settings.row_actions.is_empty() {
+            self.status_bar.set_status(t!("app.row_actions_empty", Settings::path().display()));
+            return;
+        }
+        let names = self.settings.row_actions.iter().map(|a| a.name.clone()).collect();
+        self.row_action_menu = Some(ActionMenu::new(t!("app.row_actions_title"), names));
+    }
+
+    fn handle_row_action_key(&mut self, key: KeyEvent) {
+        let Some(menu) = self.row_action_menu.as_mut() else { return; };
+        match key.code {
+            KeyCode::Up => menu.previous(),
+            KeyCode::Down => menu.next(),
+            KeyCode::Esc | KeyCode::Char('q') => self.row_action_menu = None,
+            KeyCode::Enter => {
+                let selected = menu.selected();
+                self.row_action_menu = None;
+                let Some(action) = selected.and_then(|i| self.settings.row_actions.get(i)) else { return; };
+                let Some((headers, row)) = self.content.focused_row() else { return; };
+                let command = actions::expand_template(&action.command, headers, row);
+                let status = match actions::spawn_shell(&command) {
+                    Ok(()) => t!("app.row_action_done", action.name),
+                    Err(e) => t!("app.row_action_failed", action.name, e),
+                };
+                self.status_bar.set_status(status);
+            }
+            _ => {}
+        }
+    }
+
     async fn handle_escape(&mut self) -> Result<()> {
+        // 先停止表监视与查询的定时刷新
+        if let Some(watch) = self.table_watch.take() {
+            self.status_bar.set_status(t!("app.table_watch_stopped_named", watch.table()));
+            return Ok(());
+        }
+        if let Some(watch) = self.query_watch.take() {
+            self.status_bar.set_status(t!("app.query_watch_stopped_runs", watch.runs));
+            return Ok(());
+        }
+        // 经过去重/DISTINCT 的结果先还原
+        if self.content.is_derived() {
+            self.content.restore_result();
+            return Ok(());
+        }
         match self.content.get_content_type() {
             ContentType::TableSchema | ContentType::TableData => {
                 // 从表结构/数据返回表列表
                 self.content.set_content_type(ContentType::Tables);
-                self.content.set_content(format!(
-                    "数据库 '{}' 中有 {} 个表，请选择一个表查看其结构\n\n[HINT] Enter 查看结构 | t 详情 | s 返回数据库列表",
+                self.content.set_content(t!(
+                    "app.tables_hint",
                     self.current_db.as_deref().unwrap_or(""),
                     self.sidebar.get_tables_count()
                 ));
             }
             ContentType::Tables => {
                 // 从表列表返回数据库列表
-                self.sidebar.set_show_databases(true);
+                self.sidebar.set_expanded(false);
                 self.current_db = None;
                 self.status_bar.set_current_db(None);
                 self.content.set_content_type(ContentType::Help);
-                self.content.set_content(self.get_help_content());
+                self.content.set_content(self.welcome_content());
             }
             _ => {}
         }
@@ -479,46 +1754,55 @@ impl App {
     }
 
     async fn handle_enter(&mut self) -> Result<()> {
-        if self.sidebar.get_show_databases() {
-            if let Some(db) = self.sidebar.get_selected_database() {
-                let db_name = db.name.clone();
-                self.current_db = Some(db_name.clone());
-                self.status_bar.set_current_db(Some(db_name.clone()));
-                self.sidebar.set_show_databases(false);
-                self.sidebar.set_current_db(Some(db_name.clone()));
-                self.content.set_content_type(ContentType::Database);
-                self.content.set_content(format!("正在切换到数据库 '{}'...", db_name));
-                
-                // 重建连接池以设置默认数据库，避免 USE 的预处理限制
-                if let Err(e) = self.rebuild_pool_for_database(Some(db_name.clone())).await {
-                    self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("切换数据库失败: {}", e));
-                    return Ok(());
-                }
-                
-                self.content.set_content(format!("正在加载数据库 '{}' 的表...", db_name));
-                if let Err(e) = self.load_tables().await {
-                    self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("加载表列表失败: {}", e));
-                }
+        // 当前数据库节点：展开/收起
+        if self.sidebar.current_db_selected() {
+            let expanded = !self.sidebar.is_expanded();
+            self.sidebar.set_expanded(expanded);
+            if expanded && self.sidebar.get_tables_count() == 0 {
+                self.load_tables().await?;
             }
-        } else {
-            if let Some(table) = self.sidebar.get_selected_table() {
-                let table_name = table.name.clone();
-                self.content.set_content_type(ContentType::TableSchema);
-                self.content.set_content("正在加载表结构...".to_string());
-                self.content.reset_schema_scroll(); // 重置滚动位置
-                if let Err(e) = self.load_table_schema(table_name).await {
-                    self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("加载表结构失败: {}", e));
-                }
+            return Ok(());
+        }
+        if let Some(db) = self.sidebar.get_selected_database() {
+            let db_name = db.name.clone();
+            if self.tx_open {
+                self.status_bar.set_status(t!("app.tx_switch_blocked").to_string());
+                return Ok(());
+            }
+            self.current_db = Some(db_name.clone());
+            self.status_bar.set_current_db(Some(db_name.clone()));
+            self.sidebar.set_current_db(Some(db_name.clone()));
+            self.sidebar.set_expanded(true);
+            self.content.set_content_type(ContentType::Database);
+            self.content.set_content(t!("app.switching_database", db_name));
+            
+            // 重建连接池以设置默认数据库，避免 USE 的预处理限制
+            if let Err(e) = self.rebuild_pool_for_database(Some(db_name.clone())).await {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.switch_database_failed", e));
+                return Ok(());
+            }
+            
+            self.content.set_content(t!("app.loading_tables", db_name));
+            if let Err(e) = self.load_tables().await {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.load_tables_failed", e));
+            }
+        } else if let Some(table) = self.sidebar.get_selected_table() {
+            let table_name = table.name.clone();
+            self.content.set_content_type(ContentType::TableSchema);
+            self.content.set_content(t!("app.loading_schema").to_string());
+            self.content.reset_schema_scroll(); // 重置滚动位置
+            if let Err(e) = self.load_table_schema(table_name).await {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.load_schema_failed", e));
             }
         }
         Ok(())
     }
 
     async fn update_context_suggestions_async(&mut self) {
-        if self.input.get_mode() != &InputMode::SQL { 
+        if self.input.get_mode() != &InputMode::Sql { 
             return; 
         }
         let input = self.input.get_input();
@@ -540,6 +1824,19 @@ impl App {
         let before_token: String = chars[..start].iter().collect();
         let before_token_lower = before_token.to_lowercase();
 
+        // 优化器提示 /*+ ... */ 内：补全驱动支持的提示名
+        if sql::in_optimizer_hint(&before_full, self.db.dialect()) {
+            let token_lower = token.to_lowercase();
+            let hints: Vec<String> = self.db
+                .optimizer_hints()
+                .iter()
+                .filter(|h| token_lower.is_empty() || h.to_lowercase().starts_with(&token_lower))
+                .map(|h| h.to_string())
+                .collect();
+            self.input.set_external_suggestions(hints);
+            return;
+        }
+
         // 规则：
         // use -> 数据库列表；
         // from/join/desc/describe -> 表列表；
@@ -560,7 +1857,7 @@ impl App {
                 .map(|d| d.name.clone())
                 .filter(|name| {
                     let lower = name.to_lowercase();
-                    let is_system = system_dbs.iter().any(|s| s.eq_ignore_ascii_case(&name));
+                    let is_system = system_dbs.iter().any(|s| s.eq_ignore_ascii_case(name));
                     !is_system && (prefix_lower.is_empty() || lower.starts_with(&prefix_lower))
                 })
                 .collect();
@@ -576,7 +1873,7 @@ impl App {
             let mut last_len: usize = 0;
             for t in ["from ", "join ", "desc ", "describe "] {
                 if let Some(p) = before_full_lower.rfind(t) {
-                    if last_pos.map_or(true, |prev| p > prev) { last_pos = Some(p); last_len = t.len(); }
+                    if last_pos.is_none_or(|prev| p > prev) { last_pos = Some(p); last_len = t.len(); }
                 }
             }
             if let Some(p) = last_pos {
@@ -611,7 +1908,7 @@ impl App {
             let mut last_len: usize = 0;
             for t in where_triggers {
                 if let Some(p) = before_full_lower.rfind(t) {
-                    if last_pos.map_or(true, |prev| p > prev) { last_pos = Some(p); last_len = t.len(); }
+                    if last_pos.is_none_or(|prev| p > prev) { last_pos = Some(p); last_len = t.len(); }
                 }
             }
             if let Some(p) = last_pos {
@@ -677,16 +1974,22 @@ impl App {
     }
 
     async fn handle_space(&mut self) -> Result<()> {
-        if !self.sidebar.get_show_databases() {
-            if let Some(table) = self.sidebar.get_selected_table() {
-                let table_name = table.name.clone();
-                self.content.set_content_type(ContentType::TableData);
-                self.content.set_content("正在加载表数据...".to_string());
-                self.content.reset_data_scroll(); // 重置数据滚动位置
-                if let Err(e) = self.load_table_data(table_name, 10).await {
-                    self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("加载表数据失败: {}", e));
-                }
+        if let Some(table) = self.sidebar.get_selected_table() {
+            let table_name = table.name.clone();
+            // 统计信息中的行数为估计值（InnoDB 等），为 0 时视为未知
+            self.data_row_estimate = table.rows.filter(|n| *n > 0);
+            // 回到该表上次浏览的页
+            let page = self
+                .current_db
+                .as_ref()
+                .and_then(|db| self.table_pages.get(&ViewPrefs::table_key(db, &table_name)).copied())
+                .unwrap_or(0);
+            self.content.set_content_type(ContentType::TableData);
+            self.content.set_content(t!("app.loading_data").to_string());
+            self.content.reset_data_scroll(); // 重置数据滚动位置
+            if let Err(e) = self.load_table_data(table_name, self.settings.page_size, page, None).await {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.load_data_failed", e));
             }
         }
         Ok(())
@@ -694,11 +1997,11 @@ impl App {
 
     async fn handle_database_detail(&mut self) -> Result<()> {
         if let Some(db) = self.sidebar.get_selected_database() {
-            let detail = format!(
-                "数据库详情:\n名称: {}\n字符集: {}\n排序规则: {}\n表数量: {}",
+            let detail = t!(
+                "app.database_detail",
                 db.name,
-                db.charset.as_deref().unwrap_or("未知"),
-                db.collation.as_deref().unwrap_or("未知"),
+                db.charset.as_deref().unwrap_or(t!("app.unknown")),
+                db.collation.as_deref().unwrap_or(t!("app.unknown")),
                 db.table_count.unwrap_or(0)
             );
             self.content.set_content_type(ContentType::Database);
@@ -709,13 +2012,13 @@ impl App {
 
     async fn handle_table_detail(&mut self) -> Result<()> {
         if let Some(table) = self.sidebar.get_selected_table() {
-            let detail = format!(
-                "表详情:\n名称: {}\n注释: {}\n行数: {}\n大小: {} MB\n引擎: {}",
+            let detail = t!(
+                "app.table_detail",
                 table.name,
-                table.comment.as_deref().unwrap_or("无"),
+                table.comment.as_deref().unwrap_or(t!("app.none")),
                 table.rows.unwrap_or(0),
                 table.size.unwrap_or(0),
-                table.engine.as_deref().unwrap_or("未知")
+                table.engine.as_deref().unwrap_or(t!("app.unknown"))
             );
             self.content.set_content_type(ContentType::Tables);
             self.content.set_content(detail);
@@ -725,17 +2028,19 @@ impl App {
 
     async fn handle_switch_database(&mut self) -> Result<()> {
         if self.current_db.is_some() {
-            self.sidebar.set_show_databases(true);
+            self.sidebar.set_expanded(false);
             self.current_db = None;
             self.status_bar.set_current_db(None);
             self.content.set_content_type(ContentType::Help);
-            self.content.set_content(self.get_help_content());
+            self.content.set_content(self.welcome_content());
         }
         Ok(())
     }
 
     async fn handle_sql_command(&mut self) -> Result<bool> {
         let raw_command = self.input.get_input().to_string();
+        // \replace 作用的历史条目需在写入本条命令前确定
+        let history_target = self.input.history_target_index();
         
         // 添加到历史记录
         self.input.add_to_history(raw_command.clone());
@@ -751,25 +2056,58 @@ impl App {
         let mut use_vertical = false;
         let mut command = raw_command.clone();
         {
-            let trimmed = raw_command.trim_end();
             // 兼容尾随 ; 与空白：如  "SELECT 1;  \\G" 或 "SELECT 1 \\g"
-            // 先去掉末尾空白
-            let mut end = trimmed.len();
-            // 反向跳过空白
-            while end > 0 && trimmed.as_bytes()[end - 1].is_ascii_whitespace() { end -= 1; }
-            // 处理可选的 ;
-            if end > 0 && trimmed.as_bytes()[end - 1] == b';' {
-                end -= 1;
-                while end > 0 && trimmed.as_bytes()[end - 1].is_ascii_whitespace() { end -= 1; }
+            let trimmed = raw_command.trim_end();
+            let trimmed = trimmed.strip_suffix(';').unwrap_or(trimmed).trim_end();
+            // 按字符串后缀匹配 \\G 或 \\g，末尾为多字节字符时也不会切在字符中间
+            if let Some(base) = trimmed.strip_suffix("\\G").or_else(|| trimmed.strip_suffix("\\g")) {
+                use_vertical = true;
+                // 去除尾部标记并还原命令
+                command = base.trim_end().trim_end_matches(';').trim_end().to_string();
+            }
+        }
+
+        // config.toml 中的别名：\名称 展开为当前驱动对应的语句，其后的文本原样追加
+        if let Some(rest) = command.trim_start().strip_prefix('\\') {
+            let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if let Some(alias) = self.settings.alias(name, &self.config.driver) {
+                command = format!("{} {}", alias.sql.trim_end().trim_end_matches(';'), args.trim()).trim_end().to_string();
+            }
+        }
+
+        // 展开 ${name} 会话变量
+        if !command.trim_start().starts_with('\\') && command.contains("${") {
+            match sql::interpolate_variables(&command, &self.variables, self.db.dialect()) {
+                Ok(expanded) => command = expanded,
+                Err(name) => {
+                    self.content.set_content_type(ContentType::Error);
+                    self.content.set_content(t!("app.undefined_variable", name, name));
+                    return Ok(false);
+                }
+            }
+        }
+
+        // MySQL 客户端的 source 文件.sql 等同于 \i
+        if let Some(path) = meta_command::strip_command(&command, "source").filter(|p| !p.is_empty()) {
+            let path = path.trim_end_matches(';').trim().to_string();
+            self.run_sql_file(Path::new(&path), use_vertical).await;
+            return Ok(false);
+        }
+
+        // 按当前分隔符拆分语句（处理 DELIMITER 行与 $$ 引用体）；多条语句（stmt1; stmt2）按条执行，
+        // 需先于 USE 检查，避免只识别出开头的 USE
+        if !command.trim_start().starts_with('\\') {
+            let (mut statements, delimiter) = sql::split_script(&command, &self.delimiter, self.db.dialect());
+            if delimiter != self.delimiter {
+                self.status_bar.set_status(t!("app.delimiter_set", delimiter));
+                self.delimiter = delimiter;
             }
-            // 尝试匹配以 \\G 或 \\g 结尾
-            if end >= 2 {
-                let tail = &trimmed[end - 2..end];
-                if tail == "\\G" || tail == "\\g" {
-                    use_vertical = true;
-                    // 去除尾部标记并还原命令
-                    let base = &trimmed[..end - 2].trim_end();
-                    command = base.trim_end_matches(';').trim_end().to_string();
+            match statements.len() {
+                0 => return Ok(false),
+                1 => command = statements.remove(0),
+                _ => {
+                    self.submit_sql(statements, use_vertical).await;
+                    return Ok(false);
                 }
             }
         }
@@ -782,128 +2120,1826 @@ impl App {
 
         match command.as_str() {
             "\\h" | "\\help" => {
-                self.content.set_content_type(ContentType::Help);
-                self.content.set_content(self.get_help_content());
+                self.help_overlay = Some(HelpOverlay::new(&self.keymap));
             }
             "exit" | "quit" | "\\q" | "\\quit" => {
                 // 退出程序
-                return Ok(true);
+                return Ok(self.confirm_quit().await);
             }
-            _ => {
-                // 根据首个关键字判断是查询类还是非查询类
-                let first_word = command
-                    .trim_start()
-                    .split_whitespace()
-                    .next()
-                    .unwrap_or("")
-                    .to_uppercase();
-
-                let is_query = matches!(
-                    first_word.as_str(),
-                    "SELECT" | "SHOW" | "DESCRIBE" | "DESC" | "EXPLAIN"
-                );
-
-                if is_query {
-                    match self.db.execute_query_raw(&command).await {
-                        Ok((headers, rows)) => {
-                            if rows.is_empty() {
-                                self.content.set_content_type(ContentType::Database);
-                                self.content.set_content("查询执行成功，无结果".to_string());
-                            } else {
-                                if use_vertical {
-                                    self.content.set_table_data_vertical(headers, rows);
-                                } else {
-                                    self.content.set_table_data(headers, rows);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            self.content.set_content_type(ContentType::Error);
-                            self.content.set_content(format!("SQL 错误: {}", e));
-                        }
-                    }
-                } else {
-                    match self.db.execute_non_query(&command).await {
-                        Ok(affected) => {
-                            self.content.set_content_type(ContentType::Database);
-                            self.content.set_content(format!("执行成功，受影响行数: {}", affected));
-                        }
-                        Err(e) => {
-                            self.content.set_content_type(ContentType::Error);
-                            self.content.set_content(format!("SQL 错误: {}", e));
-                        }
+            cmd if meta_command::strip_command(cmd, "\\e").is_some() => {
+                // 编辑上一条语句（不含 \e 本身）
+                let previous = self
+                    .input
+                    .history_entries()
+                    .iter()
+                    .rev()
+                    .find(|c| meta_command::strip_command(c, "\\e").is_none())
+                    .cloned();
+                self.editor_request = Some(previous.unwrap_or_default());
+            }
+            cmd if meta_command::strip_command(cmd, "\\i").is_some() => {
+                let args = meta_command::split_args(meta_command::strip_command(cmd, "\\i").unwrap_or(""));
+                match args.as_slice() {
+                    [path] => self.run_sql_file(Path::new(path), use_vertical).await,
+                    _ => {
+                        self.content.set_content_type(ContentType::Error);
+                        self.content.set_content(t!("app.include_usage").to_string());
                     }
                 }
             }
+            cmd if meta_command::strip_command(cmd, "\\set").is_some() => {
+                self.handle_set(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\unset").is_some() => {
+                self.handle_unset(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\export").is_some() => {
+                self.handle_export(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\import").is_some() => {
+                self.handle_import(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\loaddata").is_some() => {
+                self.handle_load_data(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\dump").is_some() => {
+                self.handle_dump(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\schema").is_some() => {
+                self.handle_schema(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\inserts").is_some() => {
+                self.handle_inserts(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\o").is_some() => {
+                self.handle_output(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\autocommit").is_some() => {
+                self.handle_autocommit(cmd).await;
+            }
+            "\\tags" => {
+                self.show_tag_summary();
+            }
+            cmd if meta_command::strip_command(cmd, "\\connect").is_some()
+                || meta_command::strip_command(cmd, "\\c").is_some() => {
+                self.handle_connect(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\timeout").is_some() => {
+                self.handle_timeout(cmd).await;
+            }
+            cmd if meta_command::strip_command(cmd, "\\xquery").is_some() => {
+                self.handle_xquery(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\watch-table").is_some() => {
+                self.start_table_watch(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\save").is_some() => {
+                self.save_snippet(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\snip").is_some() => {
+                self.handle_snip(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\bench").is_some() => {
+                self.start_bench(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\watch").is_some() => {
+                self.start_query_watch(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\snapshot").is_some() => {
+                self.save_snapshot(cmd);
+            }
+            cmd if meta_command::strip_command(cmd, "\\replace").is_some()
+                || meta_command::strip_command(cmd, "\\replace!").is_some() => {
+                self.handle_replace(cmd, history_target);
+            }
+            _ => {
+                self.submit_sql(vec![command.clone()], use_vertical).await;
+            }
         }
         Ok(false)
     }
 
-    async fn load_databases(&mut self) -> Result<()> {
-        let databases = self.db.get_databases().await?;
-        self.sidebar.set_databases(databases);
-        Ok(())
+    // 只读检查与破坏性语句确认后执行；多条语句逐条检查，任一条被拒绝则整体不执行
+    async fn submit_sql(&mut self, statements: Vec<String>, use_vertical: bool) {
+        if statements.iter().any(|statement| self.reject_in_read_only(statement)) {
+            return;
+        }
+        if !self.config.no_confirm {
+            if let Some((message, expected)) = statements.iter().find_map(|s| sql::destructive_action(s, self.db.dialect())) {
+                self.pending_confirm = Some((ConfirmDialog::new(message, expected), statements, use_vertical));
+                return;
+            }
+        }
+        self.execute_sql(statements, use_vertical).await;
     }
 
-    async fn load_tables(&mut self) -> Result<()> {
-        if let Some(db_name) = &self.current_db {
-            match self.db.get_tables(db_name).await {
-                Ok(tables) => {
-                    self.sidebar.set_tables(tables);
-                    self.content.set_content_type(ContentType::Tables);
-                    self.content.set_content(format!("数据库 '{}' 的表列表", db_name));
+    // 在后台任务中执行 SQL；界面继续响应，结果由 finish_query 展示。多条语句依次执行
+    async fn execute_sql(&mut self, mut statements: Vec<String>, use_vertical: bool) {
+        if self.running_query.is_some() {
+            self.input.set_input(&statements.join(&format!("{}\n", self.delimiter)));
+            self.status_bar.set_status(t!("app.query_still_running").to_string());
+            return;
+        }
+        if statements.len() > 1 {
+            self.batch = Some(ResultList::new(statements, use_vertical));
+            self.advance_batch().await;
+            return;
+        }
+        self.batch = None;
+        let Some(statement) = statements.pop() else { return; };
+        if let Some(kind) = self.transaction_kind(&statement) {
+            let started = Instant::now();
+            match self.run_transaction_control(&statement, kind).await {
+                Ok(message) => {
+                    self.content.set_content_type(ContentType::Database);
+                    self.content.set_content(t!("app.message_elapsed", message, started.elapsed().as_millis()));
+                    self.status_bar.set_status(message);
                 }
                 Err(e) => {
                     self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("加载表列表失败: {}", e));
+                    self.content.set_content(t!("app.sql_error", e));
                 }
             }
+            return;
         }
-        Ok(())
+        // 含 ? / :name 占位符时先填写参数
+        if self.db.supports_params() {
+            if let Some(placeholders) = sql::find_placeholders(&statement, self.db.dialect()) {
+                let form = ParamForm::new(placeholders.names, self.param_memory.get(&statement).cloned());
+                self.param_form = Some((form, statement, use_vertical));
+                return;
+            }
+        }
+        if let Err(e) = self.ensure_transaction().await {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.begin_failed", e));
+            return;
+        }
+        self.start_query(Statement::plain(statement), use_vertical, false);
     }
 
-    async fn load_table_schema(&mut self, table_name: String) -> Result<()> {
-        if let Some(db_name) = &self.current_db {
-            match self.db.get_table_schema(db_name, &table_name).await {
-                Ok((columns, comment)) => {
-                    // 先写入缓存再更新 UI
-                    let col_names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
-                    self.table_columns.insert(table_name.clone(), col_names);
-                    self.content.set_table_name(table_name);
-                    self.content.set_table_schema(columns, comment);
-                }
-                Err(e) => {
-                    self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("加载表结构失败: {}", e));
-                }
-            }
+    // 把占位符改写为驱动的写法并绑定参数后执行，记住本次取值供重跑时预填
+    async fn run_with_params(&mut self, statement: String, values: Vec<String>, vertical: bool) {
+        let Some(placeholders) = sql::find_placeholders(&statement, self.db.dialect()) else { return; };
+        let params: Vec<ParamValue> = values.iter().map(|v| ParamValue::parse(v)).collect();
+        let db = self.db.clone();
+        let (query, params) = placeholders.bind(&statement, &params, |i| db.param_placeholder(i));
+        self.param_memory.insert(statement, values);
+        if let Err(e) = self.ensure_transaction().await {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.begin_failed", e));
+            return;
         }
-        Ok(())
+        self.start_query(Statement { sql: query, params }, vertical, false);
     }
 
-    async fn load_table_data(&mut self, table_name: String, limit: usize) -> Result<()> {
-        if let Some(_db_name) = &self.current_db {
-            // 由于已经执行了 USE 命令，可以直接使用表名
-            let ident = self.db.quote_ident(&table_name);
-            let query = format!("SELECT * FROM {} LIMIT {}", ident, limit);
-            match self.db.execute_query_raw(&query).await {
-                Ok((headers, rows)) => {
-                    if rows.is_empty() {
+    // 支持事务模式的驱动上识别 BEGIN / COMMIT / ROLLBACK，其余驱动按普通语句执行
+    fn transaction_kind(&self, statement: &str) -> Option<TxControl> {
+        if self.db.supports_transactions() {
+            sql::transaction_control(statement, self.db.dialect())
+        } else {
+            None
+        }
+    }
+
+    // 在前台执行事务控制语句并更新事务状态，返回结果说明
+    async fn run_transaction_control(&mut self, statement: &str, kind: TxControl) -> Result<String> {
+        let timeout = self.config.statement_timeout();
+        let db = self.db.clone();
+        match kind {
+            TxControl::Begin => {
+                with_timeout(timeout, db.begin_transaction(statement)).await?;
+                self.set_tx_open(true);
+                Ok(t!("app.tx_begun").to_string())
+            }
+            TxControl::End => {
+                let result = with_timeout(timeout, db.end_transaction(statement)).await;
+                // 提交失败时连接已关闭（单连接驱动由服务端决定），都视为事务结束
+                self.set_tx_open(false);
+                result?;
+                let committed = matches!(sql::first_keyword(statement, self.db.dialect()).as_str(), "COMMIT" | "END");
+                Ok(if committed { t!("app.tx_committed") } else { t!("app.tx_rolled_back") }.to_string())
+            }
+        }
+    }
+
+    // 自动提交关闭且不在事务中时，执行语句前先开启事务
+    async fn ensure_transaction(&mut self) -> Result<()> {
+        if self.autocommit || self.tx_open || !self.db.supports_transactions() {
+            return Ok(());
+        }
+        let db = self.db.clone();
+        with_timeout(self.config.statement_timeout(), db.begin_transaction(db.begin_statement())).await?;
+        self.set_tx_open(true);
+        Ok(())
+    }
+
+    fn set_tx_open(&mut self, open: bool) {
+        self.tx_open = open;
+        self.quit_warned = false;
+        self.status_bar.set_transaction(open, self.autocommit);
+    }
+
+    // \autocommit [on|off]：不带参数时显示当前状态
+    async fn handle_autocommit(&mut self, command: &str) {
+        let arg = meta_command::strip_command(command, "\\autocommit").unwrap_or("").trim().to_lowercase();
+        let autocommit = match arg.as_str() {
+            "" => {
+                let state = if self.autocommit { "on" } else { "off" };
+                let tx = if self.tx_open { t!("app.tx_open_suffix") } else { "" };
+                self.status_bar.set_status(format!("autocommit: {}{}", state, tx));
+                return;
+            }
+            "on" | "1" | "true" => true,
+            "off" | "0" | "false" => false,
+            _ => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.autocommit_usage").to_string());
+                return;
+            }
+        };
+        if !autocommit && !self.db.supports_transactions() {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.tx_unsupported", self.db.driver_name()));
+            return;
+        }
+        self.autocommit = autocommit;
+        self.status_bar.set_transaction(self.tx_open, self.autocommit);
+        let message = match (autocommit, self.tx_open) {
+            (true, true) => t!("app.autocommit_on_open"),
+            (true, false) => "autocommit: on",
+            (false, _) => t!("app.autocommit_off"),
+        };
+        self.status_bar.set_status(message.to_string());
+    }
+
+    // \i / source / --init-file：读取文件、展开会话变量并按语句拆分（文件内可用 DELIMITER），
+    // 经只读检查与危险语句确认后逐条执行，结果列表显示进度
+    async fn run_sql_file(&mut self, path: &Path, vertical: bool) {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.read_failed", path.display(), e));
+                return;
+            }
+        };
+        let text = match sql::interpolate_variables(&text, &self.variables, self.db.dialect()) {
+            Ok(text) => text,
+            Err(name) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.file_undefined_variable", path.display(), name));
+                return;
+            }
+        };
+        let (statements, _) = sql::split_script(&text, ";", self.db.dialect());
+        if statements.is_empty() {
+            self.status_bar.set_status(t!("app.file_empty", path.display()));
+            return;
+        }
+        self.status_bar.set_status(t!("app.file_running", path.display(), statements.len()));
+        self.submit_sql(statements, vertical).await;
+    }
+
+    // \set [name [value]]：不带参数时列出全部变量；值为其后全部参数，用空格连接
+    fn handle_set(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\set").unwrap_or(""));
+        let Some((name, value)) = args.split_first() else {
+            if self.variables.is_empty() {
+                self.content.set_content_type(ContentType::Database);
+                self.content.set_content(t!("app.variables_empty").to_string());
+                return;
+            }
+            let headers = vec!["name".to_string(), "value".to_string()];
+            let rows = self.variables.iter().map(|(k, v)| vec![k.clone(), v.clone()]).collect();
+            self.show_query_result(headers, rows, false);
+            return;
+        };
+        if !sql::is_variable_name(name) {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.variable_name_invalid", name));
+            return;
+        }
+        let value = value.join(" ");
+        self.status_bar.set_status(t!("app.variable_set", name, value));
+        self.variables.insert(name.clone(), value);
+    }
+
+    // \o 文件：之后的查询结果同时写入文件（覆盖已有内容）；\o 不带参数停止写入
+    fn handle_output(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\o").unwrap_or(""));
+        match args.as_slice() {
+            [] => match self.output_file.take() {
+                Some((path, _)) => self.status_bar.set_status(t!("app.output_stopped", path.display())),
+                None => self.status_bar.set_status(t!("app.output_none").to_string()),
+            },
+            [path] => {
+                let path = PathBuf::from(path);
+                match std::fs::File::create(&path) {
+                    Ok(file) => {
+                        self.status_bar.set_status(t!("app.output_started", path.display()));
+                        self.output_file = Some((path, file));
+                    }
+                    Err(e) => {
+                        self.content.set_content_type(ContentType::Error);
+                        self.content.set_content(t!("app.open_failed", path.display(), e));
+                    }
+                }
+            }
+            _ => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.output_usage").to_string());
+            }
+        }
+    }
+
+    // \export [格式] 文件：把当前结果集（含客户端后处理）写入文件
+    async fn handle_export(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\export").unwrap_or(""));
+        if let [keyword, table, rest @ ..] = args.as_slice() {
+            if keyword.eq_ignore_ascii_case("table") {
+                self.export_table(table, rest).await;
+                return;
+            }
+        }
+        let Some((headers, rows)) = self.content.result() else {
+            self.status_bar.set_status(t!("app.export_nothing").to_string());
+            return;
+        };
+        let written = export::parse_args(&args)
+            .and_then(|(format, path)| export::write_file(format, path, headers, rows, &[]).map(|_| (path, rows.len())));
+        match written {
+            Ok((path, count)) => {
+                // 分批加载的结果只含已取回的行
+                let partial = if self.result_stream.is_some() { t!("app.export_partial") } else { "" };
+                self.status_bar.set_status(t!("app.exported_rows", count, partial, path.display()));
+            }
+            Err(e) => self.status_bar.set_status(t!("app.export_failed", e)),
+        }
+    }
+
+    fn yank(&mut self, action: Action) {
+        let (text, what) = match action {
+            Action::YankResult | Action::YankMarkdown => {
+                let Some((headers, rows)) = self.content.result() else { return; };
+                if action == Action::YankMarkdown {
+                    (export::format_markdown(headers, rows), t!("app.copy_markdown", rows.len()))
+                } else {
+                    (export::format_tsv(headers, rows), t!("app.copy_tsv", rows.len()))
+                }
+            }
+            Action::YankRow => {
+                let Some((_, row)) = self.content.focused_row() else { return; };
+                (row.join("\t"), t!("app.copy_row").to_string())
+            }
+            _ => {
+                let Some((header, value)) = self.content.focused_cell() else { return; };
+                (value.to_string(), t!("app.copy_cell", header))
+            }
+        };
+        match clipboard::copy(&text) {
+            Ok(via) => self.status_bar.set_status(t!("app.copied", what, via)),
+            Err(e) => self.status_bar.set_status(t!("app.copy_failed", e)),
+        }
+    }
+
+    // \import 文件 表名：读取文件与表结构后打开导入向导
+    async fn handle_import(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\import").unwrap_or(""));
+        let [path, table] = args.as_slice() else {
+            self.status_bar.set_status(t!("app.import_usage").to_string());
+            return;
+        };
+        if self.reject_write_command("\\import") {
+            return;
+        }
+        // 事务中失败的 COPY 会使整个事务中止，之后改用的 INSERT 也会全部失败
+        if self.tx_open {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.tx_switch_blocked").to_string());
+            return;
+        }
+        if self.running_import.is_some() {
+            self.status_bar.set_status(t!("app.import_running").to_string());
+            return;
+        }
+        let Some(db_name) = self.current_db.clone() else {
+            self.status_bar.set_status(t!("app.select_database_first").to_string());
+            return;
+        };
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.status_bar.set_status(t!("app.read_failed", path, e));
+                return;
+            }
+        };
+        let columns = match self.db.get_table_schema(&db_name, table).await {
+            Ok((columns, _)) if !columns.is_empty() => columns.into_iter().map(|c| c.name).collect(),
+            Ok(_) => {
+                self.status_bar.set_status(t!("app.table_missing", table));
+                return;
+            }
+            Err(e) => {
+                self.status_bar.set_status(t!("app.table_schema_failed", table, e));
+                return;
+            }
+        };
+        self.import_wizard = Some(ImportWizard::new(path.clone(), table.clone(), text, columns));
+    }
+
+    // \loaddata 文件 表名 [--no-header]：生成 LOAD DATA LOCAL INFILE 并在确认后经 mysql 客户端执行（仅 MySQL）
+    async fn handle_load_data(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\loaddata").unwrap_or(""));
+        let no_header = args.iter().any(|a| a == "--no-header");
+        let args: Vec<&String> = args.iter().filter(|a| *a != "--no-header").collect();
+        let [path, table] = args.as_slice() else {
+            self.status_bar.set_status(t!("app.loaddata_usage").to_string());
+            return;
+        };
+        if self.reject_write_command("\\loaddata") {
+            return;
+        }
+        if self.db.driver_name() != "MySQL" {
+            self.status_bar.set_status(t!("app.loaddata_mysql_only").to_string());
+            return;
+        }
+        if self.running_load.is_some() {
+            self.status_bar.set_status(t!("app.loaddata_running").to_string());
+            return;
+        }
+        let plan = match LoadDataPlan::detect(Path::new(path.as_str()), table, no_header) {
+            Ok(plan) => plan,
+            Err(e) => {
+                self.status_bar.set_status(e.to_string());
+                return;
+            }
+        };
+        if let Err(e) = load_data::check_server(self.db.as_ref()).await {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(e.to_string());
+            return;
+        }
+        let statement = plan.statement(self.db.as_ref(), &self.config.charset);
+        self.content.set_content_type(ContentType::Database);
+        self.content.set_content(t!(
+            "app.loaddata_plan",
+            statement,
+            if plan.delimiter == '\t' { t!("app.tab").to_string() } else { plan.delimiter.to_string() },
+            if plan.header { t!("app.header_skipped") } else { t!("app.no") },
+            if plan.crlf { "CRLF" } else { "LF" },
+        ));
+        let message = t!("app.loaddata_confirm", plan.path.display(), table);
+        self.pending_load = Some((ConfirmDialog::new(message, None), statement));
+    }
+
+    async fn poll_load_data(&mut self) {
+        if !self.running_load.as_ref().is_some_and(|task| task.is_finished()) {
+            return;
+        }
+        let Some(task) = self.running_load.take() else { return; };
+        match task.await {
+            Ok(Ok(summary)) => {
+                self.status_bar.set_status(summary.clone());
+                self.content.set_content_type(ContentType::Database);
+                self.content.set_content(summary);
+            }
+            Ok(Err(e)) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.loaddata_failed", e));
+            }
+            Err(e) => self.status_bar.set_status(t!("app.loaddata_task_failed", e)),
+        }
+    }
+
+    // \bench 次数 语句：在后台把语句执行 N 次并丢弃结果，报告耗时分布与吞吐
+    fn start_bench(&mut self, command: &str) {
+        let rest = meta_command::strip_command(command, "\\bench").unwrap_or("");
+        let (times, statement) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let statement = statement.trim().trim_end_matches(self.delimiter.as_str()).trim().to_string();
+        let times = match times.parse::<usize>() {
+            Ok(n) if n > 0 && !statement.is_empty() => n,
+            _ => {
+                self.status_bar.set_status(t!("app.bench_usage").to_string());
+                return;
+            }
+        };
+        if self.running_bench.is_some() || self.running_query.is_some() {
+            self.status_bar.set_status(t!("app.statement_running").to_string());
+            return;
+        }
+        if self.tx_open {
+            self.status_bar.set_status(t!("app.bench_in_tx").to_string());
+            return;
+        }
+        if self.reject_in_read_only(&statement) {
+            return;
+        }
+        // 重复执行破坏性语句没有意义，且无法逐次确认
+        if let Some((message, _)) = sql::destructive_action(&statement, self.db.dialect()) {
+            self.status_bar.set_status(t!("app.bench_destructive", message));
+            return;
+        }
+        self.stop_result_stream();
+        self.running_bench = Some(RunningBench::spawn(self.db.clone(), statement, times, self.config.statement_timeout()));
+    }
+
+    // 收取压测进度；完成后在内容区显示耗时分布
+    fn poll_bench(&mut self) {
+        let Some(bench) = self.running_bench.as_mut() else { return; };
+        let Some(result) = bench.poll() else {
+            let text = t!("app.bench_progress", bench.done, bench.total);
+            self.status_bar.set_status(text);
+            return;
+        };
+        let done = bench.done;
+        self.running_bench = None;
+        match result {
+            Ok(report) => {
+                let (headers, rows) = report.table();
+                let summary = t!("app.bench_done", report.timings.len(), report.rows);
+                self.show_query_result(headers, rows, false);
+                self.content.set_result_note(Some(report.sql.clone()));
+                self.content.set_footer(Some(summary.clone()));
+                self.status_bar.set_status(summary);
+            }
+            Err(e) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.bench_failed", done + 1, e));
+            }
+        }
+    }
+
+    // 收取导入进度；完成后展示结果与失败的行
+    fn poll_import(&mut self) {
+        let Some(import) = self.running_import.as_mut() else { return; };
+        let Some(report) = import.poll() else {
+            let text = t!("app.import_progress", import.table, import.done, import.total);
+            self.status_bar.set_status(text);
+            return;
+        };
+        let Some(import) = self.running_import.take() else { return; };
+        let ImportReport { inserted, errors, copy_error } = report;
+        let mut summary = t!("app.import_done", import.table, inserted, errors.len());
+        if let Some(e) = copy_error {
+            summary.push_str(&t!("app.import_copy_failed", e));
+        }
+        self.status_bar.set_status(summary.clone());
+        if errors.is_empty() {
+            self.content.set_content_type(ContentType::Database);
+            self.content.set_content(summary);
+        } else {
+            let headers = vec![t!("app.col_line").to_string(), t!("app.col_error").to_string()];
+            let rows = errors.into_iter().map(|(line, e)| vec![line.to_string(), e]).collect();
+            self.show_query_result(headers, rows, false);
+            self.content.set_result_note(Some(summary));
+        }
+    }
+
+    // \dump 表名 [文件]：建表语句加分批 INSERT 的全部数据写入 SQL 文件（默认 <表名>.sql），作为轻量备份
+    async fn handle_dump(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\dump").unwrap_or(""));
+        let (table, path) = match args.as_slice() {
+            [table] => (table.clone(), PathBuf::from(format!("{}.sql", table))),
+            [table, path] => (table.clone(), PathBuf::from(path)),
+            _ => {
+                self.status_bar.set_status(t!("app.dump_usage").to_string());
+                return;
+            }
+        };
+        let Some(db_name) = self.current_db.clone() else {
+            self.status_bar.set_status(t!("app.select_database_first").to_string());
+            return;
+        };
+        let ddl = match self.db.table_ddl(&db_name, &table).await {
+            Ok(ddl) => ddl,
+            Err(e) => {
+                self.status_bar.set_status(t!("app.ddl_failed", table, e));
+                return;
+            }
+        };
+        // 按列类型与可空性写出值：文本列中的数字仍加引号，NOT NULL 列中的 'NULL' 不会变成空值
+        let schema = match self.db.get_table_schema(&db_name, &table).await {
+            Ok((columns, _)) => columns,
+            Err(_) => Vec::new(),
+        };
+        let query = format!("SELECT * FROM {}", self.db.quote_table(&table));
+        let (headers, rows) = match self.db.execute_query_raw(&query).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.status_bar.set_status(t!("app.dump_read_failed", table, e));
+                return;
+            }
+        };
+        let mut text = t!(
+            "app.dump_header",
+            db_name,
+            table,
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            rows.len(),
+            ddl.trim_end().trim_end_matches(';'),
+        );
+        if !rows.is_empty() {
+            text.push_str(&export::format_inserts(self.db.as_ref(), &table, &headers, &rows, self.db.max_insert_rows(), &schema));
+        }
+        match std::fs::write(&path, text) {
+            Ok(()) => self.status_bar.set_status(t!("app.dumped", table, rows.len(), path.display())),
+            Err(e) => self.status_bar.set_status(t!("app.write_failed", path.display(), e)),
+        }
+    }
+
+    // \schema [文件]：当前数据库全部表与视图的建表语句写入一个 SQL 文件（默认 schema.sql）；
+    // 表在前、视图在后，便于按顺序执行重建。取不到定义的对象以注释记录并跳过
+    async fn handle_schema(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\schema").unwrap_or(""));
+        let path = match args.as_slice() {
+            [] => PathBuf::from("schema.sql"),
+            [path] => PathBuf::from(path),
+            _ => {
+                self.status_bar.set_status(t!("app.schema_usage").to_string());
+                return;
+            }
+        };
+        let Some(db_name) = self.current_db.clone() else {
+            self.status_bar.set_status(t!("app.select_database_first").to_string());
+            return;
+        };
+        let mut names: Vec<String> = match self.db.get_tables(&db_name).await {
+            Ok(tables) => tables.into_iter().map(|t| t.name).collect(),
+            Err(e) => {
+                self.status_bar.set_status(t!("app.read_tables_failed", e));
+                return;
+            }
+        };
+        match self.db.get_views(&db_name).await {
+            Ok(views) => names.extend(views.into_iter().filter(|v| !names.contains(v)).collect::<Vec<_>>()),
+            Err(e) => {
+                self.status_bar.set_status(t!("app.read_views_failed", e));
+                return;
+            }
+        }
+        let (mut tables, mut others, mut skipped) = (Vec::new(), Vec::new(), Vec::new());
+        for name in &names {
+            match self.db.table_ddl(&db_name, name).await {
+                Ok(ddl) => {
+                    let ddl = format!("{};\n", ddl.trim_end().trim_end_matches(';'));
+                    // SHOW CREATE TABLE 等对视图也会返回 CREATE VIEW，按语句区分以保证视图排在所依赖的表之后
+                    if ddl.trim_start().to_uppercase().starts_with("CREATE TABLE") {
+                        tables.push(ddl);
+                    } else {
+                        others.push(ddl);
+                    }
+                }
+                Err(e) => skipped.push(t!("app.schema_skipped", name, e.to_string().replace('\n', " "))),
+            }
+        }
+        let mut text = t!(
+            "app.schema_header",
+            db_name,
+            self.db.driver_name(),
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        );
+        let count = tables.len() + others.len();
+        for ddl in tables.into_iter().chain(others) {
+            text.push_str(&ddl);
+            text.push('\n');
+        }
+        text.extend(skipped.iter().map(String::as_str));
+        match std::fs::write(&path, text) {
+            Ok(()) if skipped.is_empty() => self.status_bar.set_status(t!("app.schema_exported", count, path.display())),
+            Ok(()) => self.status_bar.set_status(t!(
+                "app.schema_exported_partial",
+                count,
+                path.display(),
+                skipped.len()
+            )),
+            Err(e) => self.status_bar.set_status(t!("app.write_failed", path.display(), e)),
+        }
+    }
+
+    // 生成 INSERT 的目标表：未指定时取正在浏览的表
+    fn insert_target(&self, table: Option<&String>) -> Option<String> {
+        table.cloned().or_else(|| self.data_table.as_ref().map(|(table, _)| table.clone()))
+    }
+
+    fn copy_focused_insert(&mut self) {
+        let Some(table) = self.insert_target(None) else {
+            self.status_bar.set_status(t!("app.insert_target_unknown").to_string());
+            return;
+        };
+        let Some((headers, row)) = self.content.focused_row() else { return; };
+        let text = export::format_inserts(self.db.as_ref(), &table, headers, &[row.to_vec()], 1, &[]);
+        match clipboard::copy(&text) {
+            Ok(via) => self.status_bar.set_status(t!("app.insert_copied", via)),
+            Err(e) => self.status_bar.set_status(t!("app.copy_failed", e)),
+        }
+    }
+
+    // \inserts [表名] [文件]：把当前结果转换为 INSERT 语句，指定文件时写入文件，否则复制到剪贴板
+    fn handle_inserts(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\inserts").unwrap_or(""));
+        if args.len() > 2 {
+            self.status_bar.set_status(t!("app.inserts_usage").to_string());
+            return;
+        }
+        let Some(table) = self.insert_target(args.first()) else {
+            self.status_bar.set_status(t!("app.inserts_table_unknown").to_string());
+            return;
+        };
+        let Some((headers, rows)) = self.content.result() else {
+            self.status_bar.set_status(t!("app.inserts_nothing").to_string());
+            return;
+        };
+        let count = rows.len();
+        let text = export::format_inserts(self.db.as_ref(), &table, headers, rows, 1, &[]);
+        let result = match args.get(1) {
+            Some(path) => std::fs::write(path, &text)
+                .map(|_| path.clone())
+                .map_err(|e| anyhow!(t!("app.write_failed", path, e))),
+            None => clipboard::copy(&text).map(|via| via.to_string()),
+        };
+        match result {
+            Ok(target) => self.status_bar.set_status(t!("app.inserts_done", count, target)),
+            Err(e) => self.status_bar.set_status(t!("app.inserts_failed", e)),
+        }
+    }
+
+    // \export table 表名 [格式] 文件：导出当前库中整张表（不受结果行数限制），列类型取自表结构
+    async fn export_table(&mut self, table: &str, args: &[String]) {
+        let (format, path) = match export::parse_args(args) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.status_bar.set_status(t!("app.export_table_usage", e));
+                return;
+            }
+        };
+        let column_types = match self.current_db.as_deref() {
+            Some(db_name) => match self.db.get_table_schema(db_name, table).await {
+                Ok((columns, _)) => columns.into_iter().map(|c| c.data_type).collect(),
+                Err(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        let query = format!("SELECT * FROM {}", self.db.quote_table(table));
+        // PostgreSQL 导出 CSV 时由服务端经 COPY 直接输出，不经客户端逐行转换
+        if format == ExportFormat::Csv && self.db.supports_copy() {
+            match self.db.copy_out_csv(&query, path).await {
+                Ok(bytes) => self.status_bar.set_status(t!(
+                    "app.copy_exported",
+                    table,
+                    path.display(),
+                    format!("{:.1}", bytes as f64 / 1_048_576.0)
+                )),
+                Err(e) => self.status_bar.set_status(t!("app.export_failed", e)),
+            }
+            return;
+        }
+        let result = self.db.execute_query_raw(&query).await.and_then(|(headers, rows)| {
+            // 列顺序与表结构不一致时（如视图）不使用结构中的类型
+            let column_types = if column_types.len() == headers.len() { column_types } else { Vec::new() };
+            export::write_file(format, path, &headers, &rows, &column_types).map(|_| rows.len())
+        });
+        match result {
+            Ok(count) => self.status_bar.set_status(t!("app.table_exported", table, count, path.display())),
+            Err(e) => self.status_bar.set_status(t!("app.export_failed", e)),
+        }
+    }
+
+    // 把一段结果文本追加到 \o 的输出文件；写入失败时停止写入并提示
+    fn tee_output(&mut self, text: &str) {
+        use std::io::Write;
+        let Some((path, file)) = self.output_file.as_mut() else { return; };
+        if let Err(e) = file.write_all(text.as_bytes()).and_then(|_| file.write_all(b"\n")) {
+            self.status_bar.set_status(t!("app.output_write_failed", path.display(), e));
+            self.output_file = None;
+        }
+    }
+
+    // \unset name：删除会话变量
+    fn handle_unset(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\unset").unwrap_or(""));
+        let [name] = args.as_slice() else {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.unset_usage").to_string());
+            return;
+        };
+        let status = match self.variables.remove(name) {
+            Some(_) => t!("app.variable_removed", name),
+            None => t!("app.variable_undefined", name),
+        };
+        self.status_bar.set_status(status);
+    }
+
+    // 有未结束的事务时第一次退出只提示；再次退出则回滚后退出
+    async fn confirm_quit(&mut self) -> bool {
+        if !self.tx_open {
+            return true;
+        }
+        if !self.quit_warned {
+            self.quit_warned = true;
+            self.status_bar.set_status(t!("status.tx_quit_warning").to_string());
+            return false;
+        }
+        let _ = self.db.end_transaction("ROLLBACK").await;
+        true
+    }
+
+    // 执行多语句中的下一条；USE 需整体切换连接池、事务控制需更新事务状态，均在前台处理
+    async fn advance_batch(&mut self) {
+        loop {
+            let Some(batch) = self.batch.as_mut() else { return; };
+            let vertical = batch.vertical;
+            let Some(statement) = batch.start_next() else {
+                let (ok, total) = batch.progress();
+                self.status_bar.set_status(t!("app.batch_done", ok, total));
+                return;
+            };
+            let started = Instant::now();
+            let status = if let Some(db_name) = self.parse_use_command(&statement) {
+                match self.handle_use_database(db_name.clone()).await {
+                    Ok(()) if self.current_db.as_deref() == Some(db_name.as_str()) => {
+                        StatementStatus::Done(t!("app.switched_to", db_name))
+                    }
+                    Ok(()) => StatementStatus::Failed(t!("app.switch_to_failed", db_name)),
+                    Err(e) => StatementStatus::Failed(e.to_string()),
+                }
+            } else if let Some(kind) = self.transaction_kind(&statement) {
+                match self.run_transaction_control(&statement, kind).await {
+                    Ok(message) => StatementStatus::Done(message),
+                    Err(e) => StatementStatus::Failed(e.to_string()),
+                }
+            } else {
+                match self.ensure_transaction().await {
+                    Ok(()) => {
+                        self.start_query(Statement::plain(statement), vertical, false);
+                        return;
+                    }
+                    Err(e) => StatementStatus::Failed(t!("app.begin_failed", e)),
+                }
+            };
+            if let Some(batch) = self.batch.as_mut() {
+                batch.finish_current(status, started.elapsed(), None);
+            }
+        }
+    }
+
+    // 切换查看多语句中上一条/下一条的结果
+    fn show_batch_entry(&mut self, delta: isize) {
+        let Some(batch) = self.batch.as_mut() else { return; };
+        let vertical = batch.vertical;
+        let Some(entry) = batch.select_offset(delta) else { return; };
+        match entry.result.clone() {
+            Some((headers, rows)) => {
+                self.last_query = Some(entry.sql.clone());
+                self.show_query_result(headers, rows, vertical);
+            }
+            None => {
+                let text = entry.describe();
+                self.content.set_content_type(ContentType::Database);
+                self.content.set_content(text);
+            }
+        }
+    }
+
+    // 停止拉取结果的剩余行，释放其占用的连接
+    fn stop_result_stream(&mut self) {
+        self.result_stream = None;
+        self.content.set_more_rows(false);
+    }
+
+    fn start_query(&mut self, statement: Statement, vertical: bool, retried: bool) {
+        // 新语句开始时停止拉取上一个结果的剩余行，释放连接
+        self.stop_result_stream();
+        self.query_seq += 1;
+        let mut running = RunningQuery::spawn(
+            self.query_seq,
+            self.db.clone(),
+            statement,
+            vertical,
+            self.config.statement_timeout(),
+            (self.settings.default_limit > 0).then_some(self.settings.default_limit),
+            self.query_tx.clone(),
+        );
+        running.retried = retried;
+        self.status_bar.set_running(Some(running.started));
+        self.content.set_loading(Some((t!("loading.query").to_string(), running.started)));
+        self.running_query = Some(running);
+    }
+
+    fn cancel_query(&mut self) {
+        if let Some(running) = self.running_query.take() {
+            if let Some(batch) = self.batch.as_mut() {
+                batch.finish_current(StatementStatus::Failed(t!("app.cancelled_short").to_string()), running.started.elapsed(), None);
+            }
+            let elapsed = running.started.elapsed().as_millis();
+            // 先请求服务端中止语句，释放其持有的锁，再停止等待结果：任务被丢弃时会清除记录的服务端 ID。
+            // 不支持的驱动只停止等待结果；迟到的结果按 id 丢弃
+            let db = self.db.clone();
+            tokio::spawn(async move {
+                if let Err(e) = db.cancel_running().await {
+                    tracing::warn!(error = %e, "{}", t!("log.cancel_failed"));
+                }
+                running.cancel();
+            });
+            self.status_bar.set_running(None);
+            self.content.set_loading(None);
+            self.status_bar.set_status(t!("app.cancelled_after", elapsed));
+        }
+    }
+
+    // 后台语句完成：连接断开时重连并重试一次，否则记录并展示结果
+    async fn finish_query(&mut self, id: u64, outcome: QueryOutcome) {
+        if self.running_query.as_ref().map(|r| r.id) != Some(id) {
+            return;
+        }
+        let Some(running) = self.running_query.take() else { return; };
+        self.status_bar.set_running(None);
+        self.content.set_loading(None);
+        if let Some(e) = outcome.error() {
+            if !running.retried && self.reconnect_after(e, &running.sql).await {
+                self.start_query(Statement { sql: running.sql, params: running.params }, running.vertical, true);
+                return;
+            }
+        }
+
+        let command = running.sql.as_str();
+        let started = running.started;
+        let elapsed = started.elapsed();
+        let tags = sql::extract_tags(command);
+        let batch_status = self.batch.as_ref().map(|_| match &outcome {
+            QueryOutcome::Rows(Ok((_, rows)), rest) => StatementStatus::Rows { count: rows.len(), more: rest.is_some() },
+            QueryOutcome::Affected(Ok(exec)) => StatementStatus::Done(exec.summary()),
+            QueryOutcome::Rows(Err(e), _) | QueryOutcome::Affected(Err(e)) => StatementStatus::Failed(e.to_string()),
+        });
+        // 多语句时保留每条的结果集，供 [ / ] 切换回看
+        let batch_result = match (&self.batch, &outcome) {
+            (Some(_), QueryOutcome::Rows(Ok((headers, rows)), _)) if !rows.is_empty() => {
+                Some((headers.clone(), rows.clone()))
+            }
+            _ => None,
+        };
+        if self.output_file.is_some() {
+            let text = match &outcome {
+                QueryOutcome::Rows(Ok((headers, rows)), rest) => {
+                    let mut text = t!("app.tee_rows", batch::format_table(headers, rows), rows.len());
+                    if rest.is_some() {
+                        text.push_str(t!("app.tee_partial"));
+                    }
+                    text
+                }
+                QueryOutcome::Affected(Ok(exec)) => format!("{}\n", exec.summary()),
+                QueryOutcome::Rows(Err(e), _) | QueryOutcome::Affected(Err(e)) => t!("app.tee_error", e),
+            };
+            self.tee_output(&text);
+        }
+        match outcome {
+            QueryOutcome::Rows(result, rest) => {
+                let rows_count = result.as_ref().map_or(0, |(_, rows)| rows.len() as u64);
+                self.record_query(command, tags, started, rows_count, result.is_ok());
+                match result {
+                    Ok((headers, rows)) => {
+                        if rows.is_empty() && sql::ddl_summary(command, self.db.dialect()).is_some() {
+                            // 以查询方式执行却无结果集的 DDL/工具类语句按执行摘要展示
+                            self.show_execution_summary(command, started, None);
+                            self.show_warnings();
+                        } else if headers.is_empty() || rows.is_empty() {
+                            self.content.set_content_type(ContentType::Database);
+                            self.content.set_content(t!("app.query_no_result").to_string());
+                            self.show_warnings();
+                            self.pending_timing = Some((t!("app.empty_result").to_string(), elapsed));
+                        } else {
+                            let more = if rest.is_some() { "+" } else { "" };
+                            self.pending_timing = Some((t!("app.rows_returned", rows.len(), more), elapsed));
+                            self.show_query_result(headers, rows, running.vertical);
+                            self.last_query = Some(command.to_string());
+                            self.result_limit = running.auto_limit;
+                            match rest {
+                                Some(stream) => {
+                                    self.content.set_more_rows(true);
+                                    self.result_stream = Some((self.content.result_generation(), stream));
+                                }
+                                None => {
+                                    self.note_truncation();
+                                    self.show_warnings();
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        self.content.set_content_type(ContentType::Error);
+                        self.content.set_content(t!("app.sql_error", e));
+                    }
+                }
+            }
+            QueryOutcome::Affected(result) => {
+                let affected_count = result.as_ref().map_or(0, |r| r.rows_affected);
+                self.record_query(command, tags, started, affected_count, result.is_ok());
+                match result {
+                    Ok(exec) => {
+                        self.show_execution_summary(command, started, Some(&exec));
+                        self.show_warnings();
+                        self.pending_timing = Some((exec.summary(), elapsed));
+                    }
+                    Err(e) => {
+                        self.content.set_content_type(ContentType::Error);
+                        self.content.set_content(t!("app.sql_error", e));
+                    }
+                }
+            }
+        }
+
+        if let (Some(batch), Some(status)) = (self.batch.as_mut(), batch_status) {
+            batch.finish_current(status, elapsed, batch_result);
+            self.advance_batch().await;
+        }
+    }
+
+    // 滚动接近已加载行的末尾时取下一批；结果已被替换或离开结果视图则丢弃流
+    async fn pull_result_rows(&mut self) {
+        let Some((generation, stream)) = self.result_stream.as_mut() else { return; };
+        if *generation != self.content.result_generation()
+            || !matches!(self.content.get_content_type(), ContentType::TableData)
+        {
+            self.result_stream = None;
+            self.content.set_more_rows(false);
+            return;
+        }
+        if !self.content.needs_more_rows() {
+            return;
+        }
+        match stream.poll().await {
+            StreamPoll::Pending => {}
+            StreamPoll::Rows(rows) => self.content.append_rows(rows),
+            StreamPoll::Done(result) => {
+                self.result_stream = None;
+                self.content.set_more_rows(false);
+                match result {
+                    Ok(()) => {
+                        self.note_truncation();
+                        self.show_warnings();
+                    }
+                    Err(e) => self.status_bar.set_status(t!("app.fetch_rest_failed", e)),
+                }
+            }
+        }
+    }
+
+    // 语句产生的警告（如截断插入、已弃用语法）显示在结果下方
+    fn show_warnings(&mut self) {
+        let warnings = self.db.take_warnings();
+        if !warnings.is_empty() {
+            self.content.set_warnings(warnings);
+        }
+    }
+
+    // 自动追加的行数限制取满时，结果可能不完整
+    fn note_truncation(&mut self) {
+        let Some(limit) = self.result_limit else { return; };
+        let fetched = self.content.result().map_or(0, |(_, rows)| rows.len());
+        if fetched >= limit {
+            let note = t!("app.auto_limit_note", limit);
+            self.content.set_result_note(Some(note.clone()));
+            self.status_bar.set_status(note);
+        }
+    }
+
+    // 只读模式下拦截写操作；返回 true 表示已拒绝
+    fn reject_in_read_only(&mut self, command: &str) -> bool {
+        if !self.config.read_only {
+            return false;
+        }
+        let Some(keyword) = sql::read_only_violation(command, self.db.dialect()) else {
+            return false;
+        };
+        self.content.set_content_type(ContentType::Error);
+        self.content.set_content(t!("app.read_only_statement", keyword));
+        true
+    }
+
+    // 只读模式下拦截会写入数据的元命令（如 \import）；返回 true 表示已拒绝
+    fn reject_write_command(&mut self, name: &str) -> bool {
+        if !self.config.read_only {
+            return false;
+        }
+        self.content.set_content_type(ContentType::Error);
+        self.content.set_content(t!("app.read_only_command", name));
+        true
+    }
+
+    // \replace old new：在回看的（或最近一条）历史命令中替换全部匹配并载入输入框；
+    // \replace! 同时改写该历史条目
+    fn handle_replace(&mut self, command: &str, history_target: Option<usize>) {
+        let (args, rewrite_history) = match meta_command::strip_command(command, "\\replace!") {
+            Some(rest) => (meta_command::split_args(rest), true),
+            None => (meta_command::split_args(meta_command::strip_command(command, "\\replace").unwrap_or("")), false),
+        };
+        if args.len() != 2 || args[0].is_empty() {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.replace_usage").to_string());
+            return;
+        }
+        let Some(index) = history_target else {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.replace_nothing").to_string());
+            return;
+        };
+        let Some(original) = self.input.history_entry(index).cloned() else { return; };
+        let count = original.matches(args[0].as_str()).count();
+        let replaced = original.replace(args[0].as_str(), &args[1]);
+        if rewrite_history {
+            self.input.replace_history_entry(index, replaced.clone());
+        }
+        self.input.set_input(&replaced);
+        self.status_bar.set_status(t!("app.replaced", count));
+    }
+
+    // \connect host[:port] [db]：沿用当前用户名、密码等参数连接到其他服务器；
+    // \c <配置名>：连接到 connections.toml 中的命名配置。新连接失败时保留当前连接
+    async fn handle_connect(&mut self, command: &str) {
+        let rest = meta_command::strip_command(command, "\\connect")
+            .or_else(|| meta_command::strip_command(command, "\\c"))
+            .unwrap_or("");
+        let args = meta_command::split_args(rest);
+        if args.is_empty() || args.len() > 2 {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.connect_usage").to_string());
+            return;
+        }
+        if self.tx_open {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.tx_switch_blocked").to_string());
+            return;
+        }
+        let mut target = self.config.clone();
+        let profile = match Connections::load() {
+            Ok(connections) if args.len() == 1 => connections.profiles.get(&args[0]).cloned(),
+            _ => None,
+        };
+        match profile {
+            Some(profile) => {
+                // 命名配置中未填写的字段回到默认值，而不是沿用当前连接
+                target.port = None;
+                target.database = None;
+                target.password.clear();
+                target.ssh = None;
+                target.ssh_key = None;
+                profile.apply_all(&mut target);
+                target.profile = Some(args[0].clone());
+                target.fill_password();
+            }
+            None => {
+                let (host, port) = match args[0].rsplit_once(':') {
+                    Some((host, port)) if !host.contains(':') || host.ends_with(']') => match port.parse::<u16>() {
+                        Ok(port) => (host.trim_start_matches('[').trim_end_matches(']').to_string(), Some(port)),
+                        Err(_) => {
+                            self.content.set_content_type(ContentType::Error);
+                            self.content.set_content(t!("app.invalid_port", port));
+                            return;
+                        }
+                    },
+                    _ => (args[0].clone(), None),
+                };
+                target.host = host;
+                target.port = port;
+                target.database = args.get(1).cloned();
+                target.profile = None;
+            }
+        }
+        if let Err(e) = target.validate() {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.connect_failed", e));
+            return;
+        }
+
+        let label = match &target.profile {
+            Some(name) => name.clone(),
+            None => format!("{}:{}", target.host, target.port()),
+        };
+        self.status_bar.set_status(t!("app.connecting", label));
+        let connected = match SshTunnel::for_config(&mut target) {
+            Ok(tunnel) => new_adapter(&target).await.map(|db| (db, tunnel)),
+            Err(e) => Err(e),
+        };
+        let (db, tunnel) = match connected {
+            Ok(connected) => connected,
+            Err(e) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.connect_kept", label, e));
+                return;
+            }
+        };
+        if let Some(name) = &target.profile {
+            ProfileUsage::touch(name);
+        }
+
+        // 替换连接并重置与旧连接相关的状态
+        self.db = Arc::from(db);
+        self.tunnel = tunnel;
+        self.last_ping = None;
+        self.running_ping = None;
+        self.current_db = target.database.clone();
+        // 切换到新连接的历史：先保存旧连接本次会话的命令
+        let history_path = history::path(&target);
+        if history_path != self.history_path {
+            if let Err(e) = self.save_history() {
+                self.status_bar.set_status(t!("app.history_save_failed", e));
+            }
+            self.input.load_history(history::load_for(&history_path));
+            self.history_loaded = self.input.history_entries().len();
+            self.history_path = history_path;
+        }
+        self.config = target;
+        self.table_columns.clear();
+        self.table_watch = None;
+        if let Some(task) = self.running_count.take() {
+            task.abort();
+        }
+        self.query_watch = None;
+        self.batch = None;
+        self.data_view_key = None;
+        self.data_table = None;
+        self.table_pages.clear();
+        self.last_query = None;
+        self.status_bar.set_current_db(self.current_db.clone());
+        self.status_bar.set_read_only(self.config.read_only);
+        self.sidebar.set_current_db(self.current_db.clone());
+        self.input.set_current_db(self.current_db.clone());
+        let _ = self.load_server_version().await;
+        let _ = self.set_username().await;
+        if let Err(e) = self.load_databases().await {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.load_databases_failed", e));
+            return;
+        }
+        self.sidebar.set_expanded(self.current_db.is_some());
+        if self.current_db.is_some() {
+            let _ = self.load_tables().await;
+        } else {
+            self.content.set_content_type(ContentType::Help);
+            self.content.set_content(self.welcome_content());
+        }
+        self.status_bar.set_status(t!("app.connected", label));
+    }
+
+    // \timeout [秒|off]：查看或修改语句超时；会话级设置在新连接上生效，因此修改后重建连接
+    async fn handle_timeout(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\timeout").unwrap_or(""));
+        let Some(arg) = args.first() else {
+            let current = match self.config.statement_timeout() {
+                Some(secs) => t!("app.seconds", secs),
+                None => t!("app.unlimited").to_string(),
+            };
+            self.status_bar.set_status(t!("app.timeout_current", current));
+            return;
+        };
+        let timeout = match arg.as_str() {
+            "off" | "0" => None,
+            value => match value.parse::<u64>() {
+                Ok(secs) => Some(secs),
+                Err(_) => {
+                    self.content.set_content_type(ContentType::Error);
+                    self.content.set_content(t!("app.timeout_usage").to_string());
+                    return;
+                }
+            },
+        };
+        if self.tx_open {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.tx_switch_blocked").to_string());
+            return;
+        }
+        let previous = std::mem::replace(&mut self.config.timeout, timeout);
+        // 内存库不重建连接（会丢失数据），新超时只在客户端计时生效
+        let rebuilt = match self.db.in_memory() {
+            true => Ok(None),
+            false => new_adapter(&self.config).await.map(Some),
+        };
+        match rebuilt {
+            Ok(db) => {
+                if let Some(db) = db {
+                    self.db = Arc::from(db);
+                }
+                let status = match timeout {
+                    Some(secs) => t!("app.timeout_set", secs),
+                    None => t!("app.timeout_cleared").to_string(),
+                };
+                self.status_bar.set_status(status);
+            }
+            Err(e) => {
+                self.config.timeout = previous;
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.timeout_reconnect_failed", e));
+            }
+        }
+    }
+
+    // \xquery <库1,库2,...|*> <SQL>：在后台依次在多个数据库上执行同一语句，查询结果按列名对齐后堆叠，首列为来源库；
+    // 其余语句报告各库受影响的行数。破坏性语句对全部目标库确认一次
+    fn handle_xquery(&mut self, command: &str) {
+        let args = meta_command::strip_command(command, "\\xquery").unwrap_or("");
+        let (targets, query) = match args.split_once(char::is_whitespace) {
+            Some((targets, query)) if !query.trim().is_empty() => (targets, query.trim()),
+            _ => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.xquery_usage").to_string());
+                return;
+            }
+        };
+        let query = query.trim_end_matches(self.delimiter.as_str()).trim().to_string();
+        if self.running_xquery.is_some() || self.running_query.is_some() {
+            self.status_bar.set_status(t!("app.statement_running").to_string());
+            return;
+        }
+        if self.reject_in_read_only(&query) {
+            return;
+        }
+        let databases: Vec<String> = if targets == "*" {
+            let system_dbs = self.db.system_databases();
+            self.sidebar
+                .get_databases_ref()
+                .iter()
+                .map(|db| db.name.clone())
+                .filter(|name| !system_dbs.contains(&name.as_str()))
+                .collect()
+        } else {
+            targets.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect()
+        };
+        if databases.is_empty() {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.xquery_no_target").to_string());
+            return;
+        }
+        if !self.config.no_confirm {
+            if let Some((message, expected)) = sql::destructive_action(&query, self.db.dialect()) {
+                let message = t!("app.xquery_confirm", message, databases.len(), databases.join(", "));
+                self.pending_xquery = Some((ConfirmDialog::new(message, expected), databases, query));
+                return;
+            }
+        }
+        self.start_xquery(databases, query);
+    }
+
+    fn start_xquery(&mut self, databases: Vec<String>, query: String) {
+        self.stop_result_stream();
+        let returns_rows = sql::returns_rows(&sql::first_keyword(&query, self.db.dialect()));
+        let label = t!("loading.xquery", databases.len());
+        self.content.set_loading(Some((label, Instant::now())));
+        self.running_xquery = Some(RunningXQuery::spawn(self.config.clone(), databases, query, returns_rows));
+    }
+
+    // 收取跨库查询进度；完成后展示堆叠的结果
+    fn poll_xquery(&mut self) {
+        let Some(xquery) = self.running_xquery.as_mut() else { return; };
+        let Some(report) = xquery.poll() else {
+            let text = t!("app.xquery_progress", xquery.done, xquery.total);
+            self.status_bar.set_status(text);
+            return;
+        };
+        let Some(xquery) = self.running_xquery.take() else { return; };
+        self.content.set_loading(None);
+        let XQueryReport { headers, rows, failures } = report;
+        if rows.is_empty() && !failures.is_empty() {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.xquery_all_failed", failures.join("\n")));
+            return;
+        }
+        let status = if failures.is_empty() {
+            t!("app.xquery_done", xquery.total, rows.len())
+        } else {
+            t!("app.xquery_partial", failures.len(), failures.join("; "))
+        };
+        self.show_query_result(headers, rows, false);
+        self.last_query = Some(xquery.query);
+        self.status_bar.set_status(status);
+    }
+
+    // \watch-table [表名] [间隔秒]：定时统计表行数并绘制写入速率；\watch-table off 停止
+    fn start_table_watch(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\watch-table").unwrap_or(""));
+        if args.first().is_some_and(|a| a == "off") {
+            self.table_watch = None;
+            self.status_bar.set_status(t!("app.table_watch_stopped").to_string());
+            return;
+        }
+        let table = match args.first() {
+            Some(table) => table.clone(),
+            None => match self.sidebar.get_selected_table() {
+                Some(table) => table.name.clone(),
+                None => {
+                    self.content.set_content_type(ContentType::Error);
+                    self.content.set_content(t!("app.watch_table_usage").to_string());
+                    return;
+                }
+            },
+        };
+        let seconds = match args.get(1).map(|s| s.parse::<u64>()) {
+            None => 2,
+            Some(Ok(n)) if n > 0 => n,
+            Some(_) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.interval_invalid").to_string());
+                return;
+            }
+        };
+        self.status_bar.set_status(t!("app.table_watch_started", table, seconds));
+        if let Some(task) = self.running_count.take() {
+            task.abort();
+        }
+        self.table_watch = Some(TableWatch::new(table, Duration::from_secs(seconds)));
+    }
+
+    // 在后台统计行数，受 \timeout 限制；大表上较慢的 COUNT(*) 不阻塞界面
+    fn tick_table_watch(&mut self) {
+        let Some(table) = self.table_watch.as_ref().map(|w| w.table().to_string()) else { return; };
+        let query = format!("SELECT COUNT(*) FROM {}", self.db.quote_table(&table));
+        if self.tx_open && self.running_query.is_some() {
+            return;
+        }
+        self.stop_result_stream();
+        let db = self.db.clone();
+        let timeout = self.config.statement_timeout();
+        self.running_count = Some(tokio::spawn(async move { with_timeout(timeout, db.execute_query_raw(&query)).await }));
+    }
+
+    // 收取后台统计结果；监视已停止时丢弃
+    async fn poll_table_watch(&mut self) {
+        if !self.running_count.as_ref().is_some_and(|task| task.is_finished()) {
+            return;
+        }
+        let Some(task) = self.running_count.take() else { return; };
+        let result = task.await.unwrap_or_else(|e| Err(e.into()));
+        let Some(watch) = self.table_watch.as_mut() else { return; };
+        match result {
+            Ok((_, rows)) => match rows.first().and_then(|r| r.first()).and_then(|v| v.parse::<u64>().ok()) {
+                Some(count) => watch.record(count),
+                None => watch.record_error(t!("app.count_unparsable").to_string()),
+            },
+            Err(e) => watch.record_error(e.to_string()),
+        }
+    }
+
+    // \watch [间隔秒]：每隔 N 秒（默认 2）重新执行当前结果的查询，原地刷新并标出变化的单元格；
+    // \watch off 或 Esc 停止
+    fn start_query_watch(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\watch").unwrap_or(""));
+        if args.first().is_some_and(|a| a == "off") {
+            self.query_watch = None;
+            self.status_bar.set_status(t!("app.query_watch_stopped").to_string());
+            return;
+        }
+        let seconds = match args.first().map(|s| s.parse::<u64>()) {
+            None => 2,
+            Some(Ok(n)) if n > 0 => n,
+            Some(_) => {
+                self.status_bar.set_status(t!("app.watch_usage").to_string());
+                return;
+            }
+        };
+        let sql = match (&self.last_query, self.content.get_content_type()) {
+            (Some(sql), ContentType::TableData) => sql.clone(),
+            _ => {
+                self.status_bar.set_status(t!("app.watch_needs_query").to_string());
+                return;
+            }
+        };
+        // 只定时重跑只读查询：DELETE/INSERT ... RETURNING 等同样返回结果集，但每次刷新都会再写一次
+        let dialect = self.db.dialect();
+        if let Some(keyword) = sql::read_only_violation(&sql, dialect) {
+            self.status_bar.set_status(t!("app.watch_not_read_only", keyword));
+            return;
+        }
+        if let Some((action, _)) = sql::destructive_action(&sql, dialect) {
+            self.status_bar.set_status(t!("app.watch_not_read_only", action));
+            return;
+        }
+        self.status_bar.set_status(t!("app.query_watch_started", seconds));
+        self.query_watch = Some(QueryWatch { sql, interval: Duration::from_secs(seconds), last_run: Instant::now(), runs: 0 });
+    }
+
+    async fn tick_query_watch(&mut self) {
+        let Some(watch) = self.query_watch.as_mut() else { return; };
+        watch.last_run = Instant::now();
+        let sql = watch.sql.clone();
+        // 结果已被其他查询或表数据替换时停止，避免覆盖用户正在看的内容
+        if self.last_query.as_deref() != Some(sql.as_str()) || !matches!(self.content.get_content_type(), ContentType::TableData) {
+            self.query_watch = None;
+            self.status_bar.set_status(t!("app.query_watch_replaced").to_string());
+            return;
+        }
+        if self.running_query.is_some() {
+            return;
+        }
+        let started = Instant::now();
+        match self.run_query(&sql).await {
+            Ok((headers, rows)) => {
+                let count = rows.len();
+                let changed = self.content.refresh_rows(headers, rows);
+                let Some(watch) = self.query_watch.as_mut() else { return; };
+                watch.runs += 1;
+                let text = t!(
+                    "app.query_watch_tick",
+                    watch.runs,
+                    chrono::Local::now().format("%H:%M:%S"),
+                    count,
+                    changed,
+                    format!("{:.3}", started.elapsed().as_secs_f64())
+                );
+                self.content.set_footer(Some(text.clone()));
+                self.status_bar.set_status(text);
+            }
+            Err(e) => self.status_bar.set_status(t!("app.query_watch_failed", e)),
+        }
+    }
+
+    // 非结果集语句的执行摘要：DDL/工具类语句显示操作、对象与耗时，其余显示受影响行数
+    fn show_execution_summary(&mut self, command: &str, started: Instant, exec: Option<&ExecResult>) {
+        let elapsed = started.elapsed().as_millis();
+        let mut lines = Vec::new();
+        match sql::ddl_summary(command, self.db.dialect()) {
+            Some((action, object)) => {
+                lines.push(t!("app.ddl_ok", action));
+                if let Some(object) = object {
+                    let qualified = match (&self.current_db, object.contains('.')) {
+                        (Some(db), false) => format!("{}.{}", db, object),
+                        _ => object,
+                    };
+                    lines.push(t!("app.ddl_object", qualified));
+                }
+                lines.push(t!("app.elapsed", elapsed));
+                // DDL 的受影响行数通常无意义，仅在非零时显示（如 CREATE TABLE ... AS SELECT）
+                if let Some(n) = exec.map(|e| e.rows_affected).filter(|n| *n > 0) {
+                    lines.push(t!("app.rows_affected", n));
+                }
+            }
+            None => {
+                lines.push(t!("app.exec_ok").to_string());
+                if let Some(exec) = exec {
+                    lines.push(t!("app.rows_affected", exec.rows_affected));
+                    if let Some(id) = exec.last_insert_id {
+                        lines.push(t!("app.last_insert_id", id));
+                    }
+                }
+                lines.push(t!("app.elapsed", elapsed));
+            }
+        }
+        if let Some(n) = exec.map(|e| e.warnings).filter(|n| *n > 0) {
+            lines.push(t!("app.warnings_below", n));
+        }
+        self.content.set_content_type(ContentType::Database);
+        self.content.set_content(lines.join("\n"));
+    }
+
+    // \snapshot [文件]：将当前结果、查询与连接信息保存为可只读打开的快照文件
+    fn save_snapshot(&mut self, command: &str) {
+        let args = meta_command::split_args(meta_command::strip_command(command, "\\snapshot").unwrap_or(""));
+        let Some((headers, rows)) = self.content.result() else {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.snapshot_nothing").to_string());
+            return;
+        };
+        let path = match args.first() {
+            Some(arg) => PathBuf::from(arg),
+            None => PathBuf::from(format!("snapshot-{}.sqltui", chrono::Local::now().format("%Y%m%d-%H%M%S"))),
+        };
+        let snapshot = Snapshot::new(self.last_query.clone().unwrap_or_default(), headers.to_vec(), rows.to_vec()).with_connection(
+            self.db.driver_name().to_string(),
+            self.config.host.clone(),
+            self.config.port(),
+            self.config.username.clone(),
+            self.current_db.clone(),
+        );
+        match snapshot.save(&path) {
+            Ok(()) => self.status_bar.set_status(t!("app.snapshot_saved", path.display(), snapshot.rows.len())),
+            Err(e) => {
+                self.content.set_content_type(ContentType::Error);
+                self.content.set_content(t!("app.snapshot_save_failed", e));
+            }
+        }
+    }
+
+    fn record_query(&mut self, sql: &str, tags: Vec<String>, started: Instant, rows: u64, success: bool) {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        if success {
+            tracing::info!(sql, duration_ms, rows, "{}", t!("log.query_done"));
+        } else {
+            tracing::warn!(sql, duration_ms, "{}", t!("log.query_failed"));
+        }
+        self.query_log.push(QueryRecord::new(sql.to_string(), tags, duration_ms, rows, success));
+    }
+
+    // \tags：按标签汇总本次会话的执行次数、总耗时与行数
+    fn show_tag_summary(&mut self) {
+        let mut summary: HashMap<String, (u64, u64, u64)> = HashMap::new();
+        for record in &self.query_log {
+            for tag in &record.tags {
+                let entry = summary.entry(tag.clone()).or_insert((0, 0, 0));
+                entry.0 += 1;
+                entry.1 += record.duration_ms;
+                entry.2 += record.rows;
+            }
+        }
+        if summary.is_empty() {
+            self.content.set_content_type(ContentType::Database);
+            self.content.set_content(t!("app.tags_empty").to_string());
+            return;
+        }
+        let mut items: Vec<(String, (u64, u64, u64))> = summary.into_iter().collect();
+        items.sort_by_key(|(_, (_, ms, _))| std::cmp::Reverse(*ms));
+        let headers = vec!["tag".to_string(), "queries".to_string(), "total_time".to_string(), "rows".to_string()];
+        let rows = items
+            .into_iter()
+            .map(|(tag, (count, ms, rows))| {
+                vec![tag, count.to_string(), format!("{:.3} s", ms as f64 / 1000.0), rows.to_string()]
+            })
+            .collect();
+        self.show_query_result(headers, rows, false);
+    }
+
+    // 展示非整表浏览产生的结果（不关联表级视图偏好）
+    fn show_query_result(&mut self, headers: Vec<String>, rows: Vec<Vec<String>>, vertical: bool) {
+        self.data_view_key = None;
+        self.data_table = None;
+        if vertical {
+            self.content.set_table_data_vertical(headers, rows);
+        } else {
+            self.content.set_table_data(headers, rows);
+        }
+    }
+
+    async fn load_databases(&mut self) -> Result<()> {
+        let db = self.db.clone();
+        let databases = self.with_spinner(t!("loading.databases").to_string(), db.get_databases()).await?;
+        self.sidebar.set_databases(databases);
+        Ok(())
+    }
+
+    async fn load_tables(&mut self) -> Result<()> {
+        if let Some(db_name) = self.current_db.clone() {
+            let db = self.db.clone();
+            match self.with_spinner(t!("loading.tables", db_name), db.get_tables(&db_name)).await {
+                Ok(tables) => {
+                    let names: Vec<String> = tables.iter().map(|t| t.name.clone()).collect();
+                    self.sidebar.set_tables(tables);
+                    // 权限查询失败（如无权访问 information_schema）时不标记
+                    if let Ok(unreadable) = db.get_unreadable_tables(&db_name, &names).await {
+                        self.sidebar.set_unreadable_tables(unreadable);
+                    }
+                    self.content.set_content_type(ContentType::Tables);
+                    self.content.set_content(t!("app.tables_of", db_name));
+                }
+                Err(e) => {
+                    self.content.set_content_type(ContentType::Error);
+                    self.content.set_content(t!("app.load_tables_failed", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn load_table_schema(&mut self, table_name: String) -> Result<()> {
+        if let Some(db_name) = self.current_db.clone() {
+            let db = self.db.clone();
+            let load = async {
+                let (mut columns, comment) = db.get_table_schema(&db_name, &table_name).await?;
+                let col_names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+                // 列权限查询失败（如无 information_schema 访问权）时视为未知，不影响结构展示
+                if let Ok(privileges) = db.get_column_privileges(&db_name, &table_name, &col_names).await {
+                    for col in columns.iter_mut() {
+                        col.privileges = privileges.get(&col.name).copied();
+                    }
+                }
+                anyhow::Ok((columns, comment, col_names))
+            };
+            match self.with_spinner(t!("loading.schema", table_name), load).await {
+                Ok((columns, comment, col_names)) => {
+                    // 先写入缓存再更新 UI
+                    self.table_columns.insert(table_name.clone(), col_names);
+                    self.content.set_table_name(table_name);
+                    self.content.set_table_schema(columns, comment);
+                }
+                Err(e) => {
+                    self.content.set_content_type(ContentType::Error);
+                    self.content.set_content(t!("app.load_schema_failed", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 读取第 page 页（每页 limit 行）；order 为 (主键列, 是否降序)，None 时按默认顺序读取
+    async fn load_table_data(&mut self, table_name: String, limit: usize, page: usize, order: Option<(&[String], bool)>) -> Result<()> {
+        if let Some(db_name) = self.current_db.clone() {
+            let view_key = ViewPrefs::table_key(&db_name, &table_name);
+            // 由于已经执行了 USE 命令，可以直接使用表名
+            let order_clause = match order {
+                Some((columns, desc)) => {
+                    let direction = if desc { "DESC" } else { "ASC" };
+                    let keys: Vec<String> = columns
+                        .iter()
+                        .map(|c| format!("{} {}", self.db.quote_ident(c), direction))
+                        .collect();
+                    format!(" ORDER BY {}", keys.join(", "))
+                }
+                None => String::new(),
+            };
+            let offset = page * limit;
+            let query = self.db.select_rows_sql(&table_name, &order_clause, limit, offset);
+            match self.run_query(&query).await {
+                Ok((_, rows)) if rows.is_empty() && page > 0 => {
+                    if self.data_table.as_ref().is_none_or(|(t, _)| *t != table_name) {
+                        // 记住的页已超出（如表被清理）：回到第一页
+                        self.table_pages.remove(&view_key);
+                        return Box::pin(self.load_table_data(table_name, limit, 0, order)).await;
+                    }
+                    // 翻过了最后一页：保留当前页
+                    self.status_bar.set_status(t!("app.last_page").to_string());
+                }
+                Ok((headers, rows)) => {
+                    if rows.is_empty() {
                         self.content.set_content_type(ContentType::TableData);
-                        self.content.set_content("表为空，没有数据".to_string());
+                        self.content.set_content(t!("app.table_empty").to_string());
                     } else {
+                        let label = self.page_label(offset, rows.len(), limit);
                         self.content.set_table_data(headers, rows);
+                        self.content.set_column_widths(self.view_prefs.column_widths(&view_key));
+                        self.content.set_result_note(Some(label));
+                        self.table_pages.insert(view_key.clone(), page);
+                        self.data_view_key = Some(view_key);
+                        self.last_query = Some(query);
+                        self.batch = None;
+                        self.data_table = Some((table_name, limit));
+                        self.data_page = page;
+                        self.data_order_desc = order.map(|(_, desc)| desc);
                     }
                 }
                 Err(e) => {
                     self.content.set_content_type(ContentType::Error);
-                    self.content.set_content(format!("加载表数据失败: {}", e));
+                    self.content.set_content(t!("app.load_data_failed", e));
                 }
             }
         }
         Ok(())
     }
 
+    // 表数据翻页（delta 为 ±1），沿用当前排序；主键排序需重新获取主键列
+    async fn change_data_page(&mut self, delta: isize) -> Result<()> {
+        let (Some(db_name), Some((table, limit))) = (self.current_db.clone(), self.data_table.clone()) else {
+            return Ok(());
+        };
+        let Some(page) = self.data_page.checked_add_signed(delta) else {
+            self.status_bar.set_status(t!("app.first_page").to_string());
+            return Ok(());
+        };
+        let primary_key = match self.data_order_desc {
+            Some(_) => self.db.get_primary_key(&db_name, &table).await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let order = self.data_order_desc.filter(|_| !primary_key.is_empty()).map(|desc| (primary_key.as_slice(), desc));
+        let previous = self.content.result_generation();
+        self.load_table_data(table, limit, page, order).await?;
+        if self.content.result_generation() != previous {
+            // 向后翻从首行开始，向前翻停在末行，便于连续浏览
+            if delta > 0 { self.content.scroll_data_top() } else { self.content.scroll_data_bottom() }
+        }
+        Ok(())
+    }
+
+    // 标题中的分页位置，如 "行 41–60 / 约 1.2M"；不足一页时即为最后一页，总数已确定
+    fn page_label(&self, offset: usize, count: usize, limit: usize) -> String {
+        let range = t!("app.page_range", offset + 1, offset + count);
+        if count < limit {
+            return t!("app.page_total", range, offset + count);
+        }
+        match self.data_row_estimate {
+            Some(total) => t!("app.page_estimate", range, approx_count(total)),
+            None => range,
+        }
+    }
+
+    // 表数据在前 N 行（主键升序）与最后 N 行（主键降序）之间切换
+    async fn toggle_primary_key_order(&mut self) -> Result<()> {
+        let (Some(db_name), Some((table, limit))) = (self.current_db.clone(), self.data_table.clone()) else {
+            return Ok(());
+        };
+        let primary_key = match self.db.get_primary_key(&db_name, &table).await {
+            Ok(columns) => columns,
+            Err(e) => {
+                self.status_bar.set_status(t!("app.primary_key_failed", e));
+                return Ok(());
+            }
+        };
+        if primary_key.is_empty() {
+            self.status_bar.set_status(t!("app.no_primary_key", table));
+            return Ok(());
+        }
+        let desc = !self.data_order_desc.unwrap_or(false);
+        self.content.reset_data_scroll();
+        self.load_table_data(table, limit, 0, Some((primary_key.as_slice(), desc))).await?;
+        let label = if desc { t!("app.last_rows", limit) } else { t!("app.first_rows", limit) };
+        self.status_bar.set_status(format!("{}: {}", primary_key.join(", "), label));
+        Ok(())
+    }
+
     async fn load_server_version(&mut self) -> Result<()> {
         match self.db.get_version().await {
             Ok(version) => {
@@ -911,10 +3947,14 @@ impl App {
                 self.status_bar.set_driver(self.db.driver_name());
                 self.status_bar.set_server_version(version);
             }
+            // 运行在 TUI 中，不能写标准错误，记入日志并在状态栏提示
             Err(e) => {
-                eprintln!("Failed to get server version: {}", e);
+                tracing::warn!(error = %e, "{}", t!("log.version_failed"));
+                self.status_bar.set_status(t!("app.version_failed", e));
             }
         }
+        // 部分云数据库不允许查询会话状态，失败时不显示
+        self.status_bar.set_tls(self.db.get_tls_status().await.ok().flatten());
         Ok(())
     }
 
@@ -924,14 +3964,15 @@ impl App {
                 self.status_bar.set_username(username);
             }
             Err(e) => {
-                eprintln!("Failed to get current user: {}", e);
+                tracing::warn!(error = %e, "{}", t!("log.user_failed"));
+                self.status_bar.set_status(t!("app.user_failed", e));
             }
         }
         Ok(())
     }
 
     fn parse_use_command(&self, command: &str) -> Option<String> {
-        let trimmed = command.trim();
+        let trimmed = sql::skip_leading_comments(command, self.db.dialect()).trim();
         // 支持多种格式：USE db, use db, USE db;, use db; 等
         if trimmed.to_uppercase().starts_with("USE ") {
             let parts: Vec<&str> = trimmed.split_whitespace().collect();
@@ -946,91 +3987,91 @@ impl App {
     }
 
     async fn handle_use_database(&mut self, db_name: String) -> Result<()> {
+        // 切换库会重建连接池，未结束的事务将丢失
+        if self.tx_open {
+            self.content.set_content_type(ContentType::Error);
+            self.content.set_content(t!("app.tx_switch_blocked").to_string());
+            return Ok(());
+        }
         // 检查驱动是否支持 USE 语义
         if !self.db.supports_use_database() {
             self.content.set_content_type(ContentType::Error);
-            self.content.set_content("当前驱动不支持 USE 语义，请通过重连指定数据库".to_string());
+            self.content.set_content(t!("app.use_unsupported").to_string());
             return Ok(());
         }
         // 检查数据库是否存在
         let databases = self.db.get_databases().await?;
         if !databases.iter().any(|db| db.name == db_name) {
             self.content.set_content_type(ContentType::Error);
-            self.content.set_content(format!("数据库 '{}' 不存在", db_name));
+            self.content.set_content(t!("app.database_missing", db_name));
             return Ok(());
         }
 
         // 重建连接池到目标数据库，避免 USE 的预处理限制
         if let Err(e) = self.rebuild_pool_for_database(Some(db_name.clone())).await {
             self.content.set_content_type(ContentType::Error);
-            self.content.set_content(format!("切换数据库失败: {}", e));
+            self.content.set_content(t!("app.switch_database_failed", e));
             return Ok(());
         }
         
         // 切换数据库
         self.current_db = Some(db_name.clone());
         self.status_bar.set_current_db(Some(db_name.clone()));
-        self.sidebar.set_show_databases(false);
         self.sidebar.set_current_db(Some(db_name.clone()));
+        self.sidebar.set_expanded(true);
         
         // 更新输入组件的数据库信息
         self.input.set_current_db(Some(db_name.clone()));
         
         // 加载新数据库的表
         self.content.set_content_type(ContentType::Database);
-        self.content.set_content(format!("已切换到数据库 '{}'，正在加载表...", db_name));
+        self.content.set_content(t!("app.switched_loading", db_name));
         
         if let Err(e) = self.load_tables().await {
             self.content.set_content_type(ContentType::Error);
-            self.content.set_content(format!("加载表列表失败: {}", e));
+            self.content.set_content(t!("app.load_tables_failed", e));
         } else {
             self.content.set_content_type(ContentType::Database);
-            self.content.set_content(format!("已切换到数据库 '{}'，共 {} 个表", db_name, self.sidebar.get_tables_count()));
+            self.content.set_content(t!("app.switched_database", db_name, self.sidebar.get_tables_count()));
         }
         
         Ok(())
     }
 
-    fn get_help_content(&self) -> String {
-        "SQLTUI v1.0 - READY\n\n\
-        [INSTRUCTIONS]\n\
-        - Use Up/Down keys to navigate\n\
-        - Press Enter to view table structure\n\
-        - Press Space to view table data (10 rows)\n\
-        - Press ':' to enter SQL edit mode\n\
-        - Press 'q' to exit\n\
-        \n\
-        帮助信息:\n\n\
-        导航:\n\
-        - Up/Down: 上下移动选择项\n\
-        - Enter: 查看表结构\n\
-        - Space: 查看表数据(前10行)\n\
-        - Esc: 返回上一级\n\n\
-        快捷键:\n\
-        - d: 查看数据库详情\n\
-        - t: 查看表详情\n\
-        - s: 切换数据库\n\
-        - : 进入 SQL 编辑模式\n\
-        - q: 在根菜单退出程序\n\n\
-        SQL 编辑模式:\n\
-        - 输入 SQL 语句后按 Enter 执行（不会自动退出 SQL 模式）\n\
-        - 末尾添加 \\\\G 或 \\\\g 使用垂直输出\n\
-        - 输入 \\h 或 \\help 显示本帮助\n\
-        - 智能提示：\n\
-          * 输入 'use ' 后提示库名；输入前缀可过滤\n\
-          * 输入 'from '/ 'join '/ 'desc '/ 'describe ' 后提示表名\n\
-          * 输入 'where '/ 'and '/ 'or ' 后提示列名\n\
-          * 输入 '<table>.' 时提示该表的列（自动加载并缓存列名）\n\
-          * 上/下 或 左/右 切换建议；Tab 应用当前建议（无建议时尝试生成）；Esc 关闭建议\n\
-        - 历史记录：建议关闭时，Up/Down 在历史命令中切换\n\
-        - 切库：执行 USE <db>; 或在侧边栏选择数据库\n\
-        - 退出：Esc 退出 SQL 模式；输入 exit/quit/\\q 并回车可退出程序\n\n\
-        表结构模式:\n\
-        - Up/Down: 滚动查看字段\n\
-        - Esc: 返回表列表\n\n\
-        表数据模式:\n\
-        - Up/Down: 垂直滚动查看行（垂直输出时切换行）\n\
-        - Left/Right: 水平滚动查看列\n\
-        - Esc: 返回表列表".to_string()
+    // 启动页与返回根目录时显示的简要说明，按键取自当前的 keymap
+    fn welcome_content(&self) -> String {
+        self.keymap.expand(t!("welcome"))
+    }
+}
+
+// \watch 定时重新执行的查询
+struct QueryWatch {
+    sql: String,
+    interval: Duration,
+    last_run: Instant,
+    // 已完成的刷新次数
+    runs: u64,
+}
+
+impl QueryWatch {
+    fn time_until_tick(&self) -> Duration {
+        self.interval.saturating_sub(self.last_run.elapsed())
+    }
+}
+
+// 估计行数的简写，如 1234567 -> "1.2M"
+fn approx_count(n: u64) -> String {
+    match n {
+        0..=9_999 => n.to_string(),
+        10_000..=999_999 => format!("{:.1}K", n as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}M", n as f64 / 1e6),
+        _ => format!("{:.1}B", n as f64 / 1e9),
     }
 }
+
+// 语句执行期间的界面刷新间隔
+const SPINNER_TICK: Duration = Duration::from_millis(100);
+// 空闲时的重绘节拍
+const TICK: Duration = Duration::from_millis(250);
+// 界面内等待超过该时长才显示加载动画，快速完成的加载（如 \watch 刷新）不闪烁
+const LOADING_DELAY: Duration = Duration::from_millis(300);
@@ -0,0 +1,93 @@
+use anyhow::{anyhow, Result};
+use std::io::{self, Write};
+use std::time::Instant;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    config::{Config, OutputFormat},
+    db::new_adapter,
+    sql, t,
+    ui::export,
+    ui::query_task::with_timeout,
+};
+
+// 不进入交互界面，逐条执行 SQL 并把结果写到标准输出；任一语句出错即停止并返回错误（进程退出码非 0）
+pub async fn run(config: &Config, script: &str, format: OutputFormat) -> Result<()> {
+    let db = new_adapter(config).await?;
+    let (statements, _) = sql::split_script(script, ";", db.dialect());
+    if statements.is_empty() {
+        return Err(anyhow!(t!("batch.empty")));
+    }
+    let timeout = config.statement_timeout();
+    let mut stdout = io::stdout().lock();
+    for (index, statement) in statements.iter().enumerate() {
+        let fail = |e: anyhow::Error| {
+            if statements.len() > 1 {
+                anyhow!(t!("batch.statement_failed", index + 1, e))
+            } else {
+                e
+            }
+        };
+        if config.read_only {
+            if let Some(keyword) = sql::read_only_violation(statement, db.dialect()) {
+                return Err(fail(anyhow!(t!("batch.read_only", keyword))));
+            }
+        }
+        let started = Instant::now();
+        if sql::returns_rows(&sql::first_keyword(statement, db.dialect())) {
+            let (headers, rows) = with_timeout(timeout, db.execute_query_raw(statement)).await.map_err(fail)?;
+            tracing::info!(sql = statement.as_str(), duration_ms = started.elapsed().as_millis() as u64, rows = rows.len(), "{}", t!("log.query_done"));
+            let output = match format {
+                OutputFormat::Table => format!("{}{}\n", format_table(&headers, &rows), t!("batch.rows", rows.len())),
+                OutputFormat::Csv => export::format_csv(&headers, &rows),
+                OutputFormat::Json => export::format_json(&headers, &rows)?,
+            };
+            stdout.write_all(output.as_bytes())?;
+        } else {
+            let result = with_timeout(timeout, db.execute_non_query(statement)).await.map_err(fail)?;
+            tracing::info!(sql = statement.as_str(), duration_ms = started.elapsed().as_millis() as u64, rows = result.rows_affected, "{}", t!("log.query_done"));
+            // 表格格式与结果同在标准输出；csv/json 输出给程序解析，摘要写到标准错误
+            match format {
+                OutputFormat::Table => writeln!(stdout, "{}", result.summary())?,
+                _ => eprintln!("{}", result.summary()),
+            }
+        }
+        for warning in db.take_warnings() {
+            eprintln!("{}", t!("batch.warning", warning));
+        }
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+// 类似 mysql 客户端的框线表格
+pub fn format_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.width()).collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.width());
+        }
+    }
+    let border = format!("+{}+\n", widths.iter().map(|w| "-".repeat(w + 2)).collect::<Vec<_>>().join("+"));
+    let line = |values: &[String]| {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let value = values.get(i).map(String::as_str).unwrap_or("");
+                format!(" {}{} ", value, " ".repeat(width - value.width()))
+            })
+            .collect();
+        format!("|{}|\n", cells.join("|"))
+    };
+    let mut out = border.clone();
+    out.push_str(&line(headers));
+    out.push_str(&border);
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    if !rows.is_empty() {
+        out.push_str(&border);
+    }
+    out
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::task::JoinHandle;
+
+use crate::db::DbAdapter;
+use crate::sql;
+use crate::t;
+use crate::ui::query_task::with_timeout;
+
+// 一次压测的结果：每次执行的耗时与返回（或影响）的行数
+pub struct BenchReport {
+    pub sql: String,
+    pub timings: Vec<Duration>,
+    pub rows: u64,
+}
+
+impl BenchReport {
+    // 耗时分布表：次数、最小/中位数/P95/最大/平均耗时（毫秒）与吞吐
+    pub fn table(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut sorted = self.timings.clone();
+        sorted.sort();
+        let ms = |d: Duration| format!("{:.3}", d.as_secs_f64() * 1000.0);
+        let total: Duration = sorted.iter().sum();
+        let percentile = |p: f64| sorted[((sorted.len() as f64 * p).ceil() as usize).clamp(1, sorted.len()) - 1];
+        let secs = total.as_secs_f64().max(f64::EPSILON);
+        let headers = [
+            t!("bench.runs"),
+            t!("bench.min"),
+            t!("bench.median"),
+            "P95 ms",
+            t!("bench.max"),
+            t!("bench.avg"),
+            t!("bench.rows_per_sec"),
+            t!("bench.runs_per_sec"),
+        ];
+        let row = vec![
+            sorted.len().to_string(),
+            ms(sorted[0]),
+            ms(percentile(0.5)),
+            ms(percentile(0.95)),
+            ms(sorted[sorted.len() - 1]),
+            ms(total / sorted.len() as u32),
+            format!("{:.1}", self.rows as f64 / secs),
+            format!("{:.1}", sorted.len() as f64 / secs),
+        ];
+        (headers.iter().map(|h| h.to_string()).collect(), vec![row])
+    }
+}
+
+enum BenchEvent {
+    Progress(usize),
+    Finished(Result<BenchReport>),
+}
+
+// 后台执行中的压测；逐次执行，每次完成后上报进度
+pub struct RunningBench {
+    pub total: usize,
+    pub done: usize,
+    rx: UnboundedReceiver<BenchEvent>,
+    task: JoinHandle<()>,
+}
+
+impl RunningBench {
+    pub fn spawn(db: Arc<dyn DbAdapter>, sql: String, times: usize, timeout: Option<u64>) -> Self {
+        let (tx, rx) = unbounded_channel();
+        let task = tokio::spawn(async move {
+            let result = run(db.as_ref(), sql, times, timeout, |done| {
+                let _ = tx.send(BenchEvent::Progress(done));
+            })
+            .await;
+            let _ = tx.send(BenchEvent::Finished(result));
+        });
+        Self { total: times, done: 0, rx, task }
+    }
+
+    // 收取进度；完成时返回结果
+    pub fn poll(&mut self) -> Option<Result<BenchReport>> {
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                BenchEvent::Progress(done) => self.done = done,
+                BenchEvent::Finished(result) => return Some(result),
+            }
+        }
+        None
+    }
+
+    pub fn cancel(self) {
+        self.task.abort();
+    }
+}
+
+// 逐次执行并丢弃结果；任一次出错即停止，计时包含取回全部行
+async fn run(db: &dyn DbAdapter, sql: String, times: usize, timeout: Option<u64>, progress: impl Fn(usize)) -> Result<BenchReport> {
+    let returns_rows = sql::returns_rows(&sql::first_keyword(&sql, db.dialect()));
+    let mut timings = Vec::with_capacity(times);
+    let mut rows = 0;
+    for i in 0..times {
+        let started = Instant::now();
+        rows += if returns_rows {
+            with_timeout(timeout, db.execute_query_raw(&sql)).await?.1.len() as u64
+        } else {
+            with_timeout(timeout, db.execute_non_query(&sql)).await?.rows_affected
+        };
+        timings.push(started.elapsed());
+        progress(i + 1);
+    }
+    Ok(BenchReport { sql, timings, rows })
+}
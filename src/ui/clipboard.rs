@@ -0,0 +1,43 @@
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use std::io::Write;
+use std::sync::Mutex;
+
+use crate::t;
+
+// X11/Wayland 下剪贴板内容由持有者进程提供，实例需存活到程序退出
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+// 复制文本，返回所用方式。SSH 会话中本机剪贴板在远端，直接用 OSC 52 交给本地终端；
+// 其余情况优先系统剪贴板，不可用时（如无图形环境）退回 OSC 52
+pub fn copy(text: &str) -> Result<&'static str> {
+    let over_ssh = std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+    if !over_ssh && copy_system(text).is_ok() {
+        return Ok(t!("clipboard.system"));
+    }
+    copy_osc52(text)?;
+    Ok("OSC 52")
+}
+
+fn copy_system(text: &str) -> Result<()> {
+    let mut clipboard = CLIPBOARD.lock().map_err(|_| anyhow!(t!("clipboard.unavailable")))?;
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+    clipboard.as_mut().expect("已初始化").set_text(text)?;
+    Ok(())
+}
+
+// 由终端写入本地剪贴板（iTerm2、kitty、WezTerm、Windows Terminal 等支持；tmux 内需开启 set-clipboard）
+fn copy_osc52(text: &str) -> Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", encoded)
+    } else {
+        format!("\x1b]52;c;{}\x07", encoded)
+    };
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+// 通用浮动选择菜单
+pub struct ActionMenu {
+    title: String,
+    items: Vec<String>,
+    state: ListState,
+}
+
+impl ActionMenu {
+    pub fn new(title: &str, items: Vec<String>) -> Self {
+        let mut state = ListState::default();
+        if !items.is_empty() { state.select(Some(0)); }
+        Self { title: title.to_string(), items, state }
+    }
+
+    pub fn next(&mut self) {
+        if self.items.is_empty() { return; }
+        let i = self.state.selected().map_or(0, |i| (i + 1) % self.items.len());
+        self.state.select(Some(i));
+    }
+
+    pub fn previous(&mut self) {
+        if self.items.is_empty() { return; }
+        let i = self.state.selected().map_or(0, |i| if i == 0 { self.items.len() - 1 } else { i - 1 });
+        self.state.select(Some(i));
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    // 在给定区域中居中渲染
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let width = (self.items.iter().map(|s| s.chars().count()).max().unwrap_or(0) as u16 + 6)
+            .max(self.title.chars().count() as u16 + 4)
+            .min(area.width);
+        let height = (self.items.len() as u16 + 2).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        let items: Vec<ListItem> = self.items.iter().map(|s| ListItem::new(s.as_str())).collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(self.title.as_str())
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Green)),
+            )
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::Green).bold());
+        frame.render_widget(Clear, popup);
+        frame.render_stateful_widget(list, popup, &mut self.state);
+    }
+}
@@ -0,0 +1,88 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::t;
+
+// 用户对确认框的操作结果
+pub enum ConfirmOutcome {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+
+// 执行破坏性语句前的确认框：按 y/n 确认，或要求输入对象名（DROP）后回车
+pub struct ConfirmDialog {
+    message: String,
+    expected: Option<String>,
+    typed: String,
+}
+
+impl ConfirmDialog {
+    pub fn new(message: String, expected: Option<String>) -> Self {
+        Self { message, expected, typed: String::new() }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ConfirmOutcome {
+        if key.code == KeyCode::Esc {
+            return ConfirmOutcome::Cancelled;
+        }
+        match &self.expected {
+            None => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => ConfirmOutcome::Confirmed,
+                KeyCode::Char('n') | KeyCode::Char('N') => ConfirmOutcome::Cancelled,
+                _ => ConfirmOutcome::Pending,
+            },
+            Some(expected) => {
+                match key.code {
+                    KeyCode::Enter if &self.typed == expected => return ConfirmOutcome::Confirmed,
+                    KeyCode::Backspace => {
+                        self.typed.pop();
+                    }
+                    KeyCode::Char(c) => self.typed.push(c),
+                    _ => {}
+                }
+                ConfirmOutcome::Pending
+            }
+        }
+    }
+
+    // 在给定区域中居中渲染
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = vec![
+            Line::from(Span::styled(self.message.clone(), Style::default().fg(Color::Red).bold())),
+            Line::from(""),
+        ];
+        match &self.expected {
+            None => lines.push(Line::from(t!("confirm.press_y"))),
+            Some(expected) => {
+                lines.push(Line::from(t!("confirm.type_name", expected)));
+                let color = if &self.typed == expected { Color::Green } else { Color::Yellow };
+                lines.push(Line::from(Span::styled(format!("> {}", self.typed), Style::default().fg(color))));
+            }
+        }
+
+        let width = 64.min(area.width);
+        let height = (lines.len() as u16 + 4).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        let dialog = Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(t!("confirm.title"))
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Red)),
+            );
+        frame.render_widget(Clear, popup);
+        frame.render_widget(dialog, popup);
+    }
+}
@@ -1,10 +1,22 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    widgets::{Block, Borders, Paragraph, Table},
+    widgets::{Block, Borders, Cell, Paragraph, Table},
     Frame,
 };
-use crate::models::SchemaColumn;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use crate::models::{ColumnPrivileges, SchemaColumn};
+use crate::ui::components::stats::{compute_stats, ColumnStats};
+use crate::ui::components::relative_time::{detect_timestamp_columns, format_relative};
+use crate::ui::components::status_bar::spinner;
+use crate::t;
+use crate::ui::theme;
+
+// 结果列宽（字符数）
+const DEFAULT_COLUMN_WIDTH: u16 = 15;
+const MIN_COLUMN_WIDTH: u16 = 4;
+const MAX_COLUMN_WIDTH: u16 = 120;
 
 pub enum ContentType {
     Welcome,
@@ -16,6 +28,9 @@ pub enum ContentType {
     Error,
 }
 
+// (权限名, 是否具备该权限)
+type PrivilegeCheck = (&'static str, fn(&ColumnPrivileges) -> bool);
+
 pub struct Content {
     content_type: ContentType,
     content: String,
@@ -23,12 +38,54 @@ pub struct Content {
     table_rows: Vec<Vec<String>>,
     // 是否采用垂直模式（由 \G 触发）
     vertical_mode: bool,
+    // 列名 -> 手动调整的列宽（未设置的列使用默认宽度）
+    column_widths: HashMap<String, u16>,
     schema_columns: Vec<SchemaColumn>,
     table_comment: Option<String>,
     current_table_name: Option<String>,
     schema_scroll_offset: usize,
     data_scroll_offset: usize,
     data_horizontal_scroll: usize,
+    // 鼠标点击选中的结果行；为空时焦点行为首个可见行，滚动后清除
+    clicked_row: Option<usize>,
+    // 上次渲染时结果数据行所在区域（不含表头），供鼠标点击定位；仅水平表格时有值
+    data_rows_area: Option<Rect>,
+    // 客户端后处理（去重/DISTINCT）前的原始结果，用于还原
+    original_result: Option<(Vec<String>, Vec<Vec<String>>)>,
+    // 后处理说明，显示在标题中
+    derived_label: Option<String>,
+    // 结果统计摘要（按需计算并缓存，结果变化时失效）
+    show_stats: bool,
+    stats: Option<Vec<ColumnStats>>,
+    // 时间列显示为相对时间（绝对值在单元格浮框中查看）
+    relative_time: bool,
+    timestamp_columns: Option<Vec<bool>>,
+    // 焦点单元格（首个可见行 × 最左可见列）详情浮框
+    show_cell_popup: bool,
+    // 上次渲染时可见的数据行数（整页滚动步长）
+    data_page_rows: usize,
+    // 结果内查找词（不区分大小写），匹配的单元格高亮
+    search_term: Option<String>,
+    // 结果集编号（每次设置新结果时递增），用于判断流式取回的行是否仍属于当前结果
+    result_generation: u64,
+    // 流式查询还有未取回的行
+    more_rows: bool,
+    // 标题中的结果说明：分页位置（如 "行 41–60 / 约 1.2M"）、截断提示等
+    result_note: Option<String>,
+    // 结果底部的执行摘要，如 "返回 123 行 (0.045 s，渲染 3 ms)"
+    footer: Option<String>,
+    // 语句产生的警告（MySQL SHOW WARNINGS），显示在结果下方
+    warnings: Vec<String>,
+    // \watch 刷新后与上一次结果不同的单元格（行, 列）
+    changed_cells: HashSet<(usize, usize)>,
+    // 正在加载的内容（说明, 开始时间），在标题栏右侧显示进度动画与已用时间
+    loading: Option<(String, Instant)>,
+}
+
+impl Default for Content {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Content {
@@ -39,12 +96,31 @@ impl Content {
             table_headers: Vec::new(),
             table_rows: Vec::new(),
             vertical_mode: false,
+            column_widths: HashMap::new(),
             schema_columns: Vec::new(),
             table_comment: None,
             current_table_name: None,
             schema_scroll_offset: 0,
             data_scroll_offset: 0,
             data_horizontal_scroll: 0,
+            clicked_row: None,
+            data_rows_area: None,
+            loading: None,
+            original_result: None,
+            derived_label: None,
+            show_stats: false,
+            stats: None,
+            relative_time: false,
+            timestamp_columns: None,
+            show_cell_popup: false,
+            data_page_rows: 1,
+            search_term: None,
+            result_generation: 0,
+            more_rows: false,
+            result_note: None,
+            footer: None,
+            warnings: Vec::new(),
+            changed_cells: HashSet::new(),
         }
     }
 
@@ -54,9 +130,11 @@ impl Content {
 
     pub fn set_content(&mut self, content: String) {
         self.content = content;
+        self.warnings.clear();
     }
 
     pub fn set_table_schema(&mut self, columns: Vec<SchemaColumn>, comment: Option<String>) {
+        self.warnings.clear();
         self.schema_columns = columns;
         self.table_comment = comment;
         self.content_type = ContentType::TableSchema;
@@ -67,6 +145,20 @@ impl Content {
     }
 
     pub fn set_table_data(&mut self, headers: Vec<String>, rows: Vec<Vec<String>>) {
+        self.result_generation += 1;
+        self.more_rows = false;
+        self.result_note = None;
+        self.footer = None;
+        self.warnings.clear();
+        self.original_result = None;
+        self.derived_label = None;
+        self.stats = None;
+        self.show_stats = false;
+        self.timestamp_columns = None;
+        self.column_widths.clear();
+        self.search_term = None;
+        self.changed_cells.clear();
+        self.clicked_row = None;
         self.table_headers = headers;
         self.table_rows = rows;
         self.vertical_mode = false;
@@ -74,12 +166,109 @@ impl Content {
     }
 
     pub fn set_table_data_vertical(&mut self, headers: Vec<String>, rows: Vec<Vec<String>>) {
+        self.result_generation += 1;
+        self.more_rows = false;
+        self.result_note = None;
+        self.footer = None;
+        self.warnings.clear();
+        self.original_result = None;
+        self.derived_label = None;
+        self.stats = None;
+        self.show_stats = false;
+        self.timestamp_columns = None;
+        // 竖排与横排的列宽含义不同，切换时重新计算
+        self.column_widths.clear();
+        self.search_term = None;
+        self.changed_cells.clear();
+        self.clicked_row = None;
         self.table_headers = headers;
         self.table_rows = rows;
         self.vertical_mode = true;
         self.content_type = ContentType::TableData;
     }
 
+    // 原地替换为重新执行的结果（\watch）：保留滚动位置、列宽与查找，标出与上次不同的单元格，
+    // 返回变化的单元格数；列变化时按新结果重新显示
+    pub fn refresh_rows(&mut self, headers: Vec<String>, rows: Vec<Vec<String>>) -> usize {
+        if self.is_derived() {
+            self.restore_result();
+        }
+        if headers != self.table_headers || !matches!(self.content_type, ContentType::TableData) {
+            let vertical = self.vertical_mode;
+            if vertical {
+                self.set_table_data_vertical(headers, rows);
+            } else {
+                self.set_table_data(headers, rows);
+            }
+            return 0;
+        }
+        self.changed_cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                let previous = self.table_rows.get(r);
+                row.iter()
+                    .enumerate()
+                    .filter(move |(c, value)| previous.and_then(|p| p.get(*c)) != Some(*value))
+                    .map(move |(c, _)| (r, c))
+            })
+            .collect();
+        self.result_generation += 1;
+        self.more_rows = false;
+        self.stats = None;
+        self.timestamp_columns = None;
+        self.table_rows = rows;
+        self.changed_cells.len()
+    }
+
+    pub fn result_generation(&self) -> u64 {
+        self.result_generation
+    }
+
+    pub fn set_warnings(&mut self, warnings: Vec<String>) {
+        self.warnings = warnings;
+    }
+
+    pub fn set_loading(&mut self, loading: Option<(String, Instant)>) {
+        self.loading = loading;
+    }
+
+    pub fn set_footer(&mut self, footer: Option<String>) {
+        self.footer = footer;
+    }
+
+    pub fn set_result_note(&mut self, label: Option<String>) {
+        self.result_note = label;
+    }
+
+    // 是否已滚动到已加载行的首/末页（用于翻到上一页/下一页表数据）
+    pub fn at_data_top(&self) -> bool {
+        self.data_scroll_offset == 0
+    }
+
+    pub fn at_data_bottom(&self) -> bool {
+        self.data_scroll_offset + self.data_page_rows.max(1) >= self.table_rows.len()
+    }
+
+    pub fn set_more_rows(&mut self, more: bool) {
+        self.more_rows = more;
+    }
+
+    // 追加流式取回的行（统计与时间列检测随之失效）
+    pub fn append_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.table_rows.extend(rows);
+        self.stats = None;
+        self.timestamp_columns = None;
+    }
+
+    // 滚动接近已加载行的末尾（剩余不足两页）时需要继续取行；后处理视图不追加
+    pub fn needs_more_rows(&self) -> bool {
+        self.more_rows
+            && matches!(self.content_type, ContentType::TableData)
+            && !self.is_derived()
+            && self.data_scroll_offset + self.data_page_rows.max(1) * 2 >= self.table_rows.len()
+    }
+
     pub fn get_content_type(&self) -> &ContentType {
         &self.content_type
     }
@@ -106,12 +295,14 @@ impl Content {
     }
 
     pub fn scroll_data_up(&mut self) {
+        self.clicked_row = None;
         if self.data_scroll_offset > 0 {
             self.data_scroll_offset -= 1;
         }
     }
 
     pub fn scroll_data_down(&mut self) {
+        self.clicked_row = None;
         self.data_scroll_offset += 1;
     }
 
@@ -125,15 +316,253 @@ impl Content {
         self.data_horizontal_scroll += 1;
     }
 
+    // 整页滚动（步长为上次渲染时的可见行数）
+    pub fn page_data_down(&mut self) {
+        self.clicked_row = None;
+        self.data_scroll_offset += self.data_page_rows.max(1);
+    }
+
+    pub fn page_data_up(&mut self) {
+        self.clicked_row = None;
+        self.data_scroll_offset = self.data_scroll_offset.saturating_sub(self.data_page_rows.max(1));
+    }
+
+    pub fn scroll_data_top(&mut self) {
+        self.clicked_row = None;
+        self.data_scroll_offset = 0;
+    }
+
+    pub fn scroll_data_bottom(&mut self) {
+        self.clicked_row = None;
+        self.data_scroll_offset = self.table_rows.len().saturating_sub(self.data_page_rows.max(1));
+    }
+
+    // 设置查找词并从焦点行开始查找，匹配行滚动到首行；返回是否找到
+    pub fn search(&mut self, term: &str) -> bool {
+        self.search_term = if term.is_empty() { None } else { Some(term.to_string()) };
+        let start = self.focus_index().min(self.table_rows.len().saturating_sub(1));
+        self.find_match(start, true)
+    }
+
+    // 跳到下一个/上一个匹配行（循环）
+    pub fn search_next(&mut self, forward: bool) -> bool {
+        let total = self.table_rows.len();
+        if total == 0 { return false; }
+        let current = self.focus_index().min(total - 1);
+        let start = if forward { (current + 1) % total } else { (current + total - 1) % total };
+        self.find_match(start, forward)
+    }
+
+    pub fn search_term(&self) -> Option<&str> {
+        self.search_term.as_deref()
+    }
+
+    fn find_match(&mut self, start: usize, forward: bool) -> bool {
+        let Some(term) = self.search_term.as_ref().map(|t| t.to_lowercase()) else { return false; };
+        let total = self.table_rows.len();
+        for step in 0..total {
+            let idx = if forward { (start + step) % total } else { (start + total - step) % total };
+            if self.table_rows[idx].iter().any(|cell| cell.to_lowercase().contains(&term)) {
+                self.data_scroll_offset = idx;
+                self.clicked_row = None;
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn reset_data_scroll(&mut self) {
         self.data_scroll_offset = 0;
         self.data_horizontal_scroll = 0;
+        self.clicked_row = None;
+    }
+
+    // 焦点行：鼠标点击选中的行，否则为首个可见行
+    fn focus_index(&self) -> usize {
+        self.clicked_row.filter(|row| *row < self.table_rows.len()).unwrap_or(self.data_scroll_offset)
+    }
+
+    // 鼠标点击结果行：选中为焦点行（高亮），行操作与复制随之作用于该行；返回是否点中
+    pub fn click_row(&mut self, position: Position) -> bool {
+        let Some(area) = self.data_rows_area.filter(|area| area.contains(position)) else { return false; };
+        let row = self.data_scroll_offset + (position.y - area.y) as usize;
+        if row >= self.table_rows.len() {
+            return false;
+        }
+        self.clicked_row = Some(row);
+        true
+    }
+
+    // 当前结果是否经过客户端后处理
+    pub fn is_derived(&self) -> bool {
+        self.original_result.is_some()
+    }
+
+    // 合并完全相同的行，末列追加出现次数
+    pub fn dedup_rows(&mut self) {
+        let (headers, rows) = self.take_base_result();
+        let mut order: Vec<Vec<String>> = Vec::new();
+        let mut counts: HashMap<Vec<String>, usize> = HashMap::new();
+        for row in &rows {
+            let entry = counts.entry(row.clone()).or_insert(0);
+            if *entry == 0 { order.push(row.clone()); }
+            *entry += 1;
+        }
+        let mut new_headers = headers.clone();
+        new_headers.push("count".to_string());
+        let new_rows: Vec<Vec<String>> = order
+            .into_iter()
+            .map(|row| {
+                let n = counts.get(&row).copied().unwrap_or(0);
+                let mut r = row;
+                r.push(n.to_string());
+                r
+            })
+            .collect();
+        self.derived_label = Some(t!("content.dedup", new_rows.len(), rows.len()));
+        self.apply_derived(headers, rows, new_headers, new_rows);
+    }
+
+    // 仅显示当前焦点列（水平滚动最左列）的不同值及出现次数，按次数降序
+    // 焦点列是派生视图自身的列（如 count）、原结果中没有时返回 false，视图保持不变
+    pub fn distinct_focused_column(&mut self) -> bool {
+        let Some(col_name) = self.table_headers.get(self.data_horizontal_scroll).cloned() else { return false; };
+        let base_headers = self.original_result.as_ref().map_or(&self.table_headers, |(headers, _)| headers);
+        let Some(col) = base_headers.iter().position(|h| *h == col_name) else { return false; };
+        let (headers, rows) = self.take_base_result();
+        let mut order: Vec<String> = Vec::new();
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for row in &rows {
+            let value = row.get(col).cloned().unwrap_or_default();
+            let entry = counts.entry(value.clone()).or_insert(0);
+            if *entry == 0 { order.push(value); }
+            *entry += 1;
+        }
+        // 稳定排序：次数相同时保持首次出现顺序
+        order.sort_by(|a, b| counts[b].cmp(&counts[a]));
+        let new_rows: Vec<Vec<String>> = order
+            .into_iter()
+            .map(|v| {
+                let n = counts[&v];
+                vec![v, n.to_string()]
+            })
+            .collect();
+        self.derived_label = Some(t!("content.distinct", col_name, new_rows.len()));
+        self.apply_derived(headers, rows, vec![col_name, "count".to_string()], new_rows);
+        true
+    }
+
+    // 切换结果统计视图（每列 NULL 占比、不同值数量、数值范围）
+    pub fn toggle_stats(&mut self) {
+        self.show_stats = !self.show_stats;
+        if self.show_stats && self.stats.is_none() {
+            self.stats = Some(compute_stats(&self.table_headers, &self.table_rows));
+        }
+    }
+
+    pub fn set_column_widths(&mut self, widths: HashMap<String, u16>) {
+        self.column_widths = widths;
+    }
+
+    fn column_width(&self, header: &str) -> u16 {
+        self.column_widths.get(header).copied().unwrap_or(DEFAULT_COLUMN_WIDTH)
+    }
+
+    // 调整焦点列（最左可见列）宽度，返回调整后的 (列名, 宽度)
+    pub fn adjust_focused_column_width(&mut self, delta: i32) -> Option<(String, u16)> {
+        let header = self.table_headers.get(self.data_horizontal_scroll)?.clone();
+        let current = self.column_width(&header) as i32;
+        let width = (current + delta).clamp(MIN_COLUMN_WIDTH as i32, MAX_COLUMN_WIDTH as i32) as u16;
+        self.column_widths.insert(header.clone(), width);
+        Some((header, width))
+    }
+
+    // 当前展示的结果集（含客户端后处理），非结果视图时为 None
+    pub fn result(&self) -> Option<(&[String], &[Vec<String>])> {
+        if !matches!(self.content_type, ContentType::TableData) || self.table_headers.is_empty() {
+            return None;
+        }
+        Some((self.table_headers.as_slice(), self.table_rows.as_slice()))
+    }
+
+    // 焦点行（点击选中的行或首个可见行）及表头，用于行操作
+    pub fn focused_row(&self) -> Option<(&[String], &[String])> {
+        self.table_rows
+            .get(self.focus_index())
+            .map(|row| (self.table_headers.as_slice(), row.as_slice()))
+    }
+
+    // 焦点单元格（焦点行与最左可见列）的列名与值
+    pub fn focused_cell(&self) -> Option<(&str, &str)> {
+        let header = self.table_headers.get(self.data_horizontal_scroll)?;
+        let value = self.table_rows.get(self.focus_index())?.get(self.data_horizontal_scroll)?;
+        Some((header.as_str(), value.as_str()))
+    }
+
+    // 切换时间列的相对时间显示
+    pub fn toggle_relative_time(&mut self) {
+        self.relative_time = !self.relative_time;
+    }
+
+    // 切换焦点单元格详情浮框
+    pub fn toggle_cell_popup(&mut self) {
+        self.show_cell_popup = !self.show_cell_popup;
+    }
+
+    // 还原为后处理前的原始结果
+    pub fn restore_result(&mut self) {
+        if let Some((headers, rows)) = self.original_result.take() {
+            self.table_headers = headers;
+            self.table_rows = rows;
+            self.derived_label = None;
+            self.stats = None;
+            self.timestamp_columns = None;
+            self.reset_data_scroll();
+        }
+    }
+
+    // 取得后处理的基准结果：已处理过则基于原始结果，避免叠加
+    fn take_base_result(&mut self) -> (Vec<String>, Vec<Vec<String>>) {
+        match self.original_result.take() {
+            Some(original) => original,
+            None => (self.table_headers.clone(), self.table_rows.clone()),
+        }
+    }
+
+    fn apply_derived(
+        &mut self,
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+        new_headers: Vec<String>,
+        new_rows: Vec<Vec<String>>,
+    ) {
+        self.original_result = Some((headers, rows));
+        self.stats = None;
+        self.timestamp_columns = None;
+        self.table_headers = new_headers;
+        self.table_rows = new_rows;
+        self.vertical_mode = false;
+        self.content_type = ContentType::TableData;
+        self.reset_data_scroll();
     }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.data_rows_area = None;
+        // 有警告时在下方留出警告区（最多 6 条可见）
+        let area = if self.warnings.is_empty() {
+            area
+        } else {
+            let height = (self.warnings.len() as u16 + 2).min(8).min(area.height / 2);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(height)])
+                .split(area);
+            self.render_warnings(frame, chunks[1]);
+            chunks[0]
+        };
         let block = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme::current().border());
 
         match self.content_type {
             ContentType::Welcome => {
@@ -155,6 +584,20 @@ impl Content {
                 frame.render_widget(paragraph, area);
             }
         }
+        if let Some((label, started)) = &self.loading {
+            self.render_loading(frame, area, label, *started);
+        }
+    }
+
+    // 标题栏右侧的进度动画，不遮挡已有内容
+    fn render_loading(&self, frame: &mut Frame, area: Rect, label: &str, started: Instant) {
+        let elapsed = started.elapsed();
+        let text = t!("content.loading", spinner(elapsed), label, format!("{:.1}", elapsed.as_secs_f64()));
+        let title = Rect { x: area.x + 1, y: area.y, width: area.width.saturating_sub(2), height: area.height.min(1) };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(text, theme::current().info().bold()))).alignment(Alignment::Right),
+            title,
+        );
     }
 
     fn render_table_schema(&mut self, frame: &mut Frame, area: Rect) {
@@ -165,33 +608,33 @@ impl Content {
         
         // 根据是否需要滚动来显示不同的标题
         let scroll_hint = if total_rows > max_rows {
-            " (↑↓滚动)"
+            t!("content.scroll_hint")
         } else {
             ""
         };
         
-        let title = if let Some(v) = &self.current_table_name {
-            if !v.is_empty() {
-                format!("表结构 - {}{}", v, scroll_hint)
-            } else {
-                format!("表结构{}", scroll_hint)
-            }
-        } else {
-            format!("表结构{}", scroll_hint)
+        let title = match self.current_table_name.as_deref() {
+            Some(v) if !v.is_empty() => format!("{} - {}{}", t!("content.schema"), v, scroll_hint),
+            _ => format!("{}{}", t!("content.schema"), scroll_hint),
         };
 
         // 创建主框
         let main_block = Block::default()
             .title(title)
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme::current().border());
 
         // 在框内创建布局
+        // 权限受限的列：提前说明哪些操作不可用，而不是等到执行时报错
+        let show_privileges = self.schema_columns.iter().any(|c| c.privileges.is_some());
+        let restriction_note = self.privilege_restriction_note();
+
         let inner_area = main_block.inner(area);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(0),    // 表格区域
+                Constraint::Length(if restriction_note.is_some() { 1 } else { 0 }), // 权限提示
             ])
             .split(inner_area);
 
@@ -229,48 +672,111 @@ impl Content {
                 let extra = col.extra.as_deref().unwrap_or("");
                 let comment = col.comment.as_deref().unwrap_or("");
 
-                ratatui::widgets::Row::new(vec![
-                    col.name.clone(),
-                    col.data_type.clone(),
-                    nullable.to_string(),
-                    default.to_string(),
-                    extra.to_string(),
-                    comment.to_string(),
-                ])
+                let mut cells = vec![
+                    Cell::from(col.name.clone()),
+                    Cell::from(col.data_type.clone()),
+                    Cell::from(nullable.to_string()),
+                    Cell::from(default.to_string()),
+                    Cell::from(extra.to_string()),
+                ];
+                if show_privileges {
+                    // 不完整的权限以灰色显示
+                    let (marks, style) = match col.privileges {
+                        Some(p) if p == ColumnPrivileges::all() => (p.marks(), Style::default()),
+                        Some(p) => (p.marks(), theme::current().dim()),
+                        None => ("?".to_string(), theme::current().dim()),
+                    };
+                    cells.push(Cell::from(marks).style(style));
+                }
+                cells.push(Cell::from(comment.to_string()));
+                // 不可读的列整行灰显
+                let row = ratatui::widgets::Row::new(cells);
+                if col.privileges.is_some_and(|p| !p.select) {
+                    row.style(theme::current().dim())
+                } else {
+                    row
+                }
             })
             .collect();
 
-        let widths = [
+        let mut widths = vec![
             Constraint::Length(20),
             Constraint::Length(15),
             Constraint::Length(8),
             Constraint::Length(15),
             Constraint::Length(15),
-            Constraint::Min(20),
         ];
+        let mut header = vec![
+            t!("content.col_name"),
+            t!("content.col_type"),
+            t!("content.col_nullable"),
+            t!("content.col_default"),
+            t!("content.col_extra"),
+        ];
+        if show_privileges {
+            widths.push(Constraint::Length(7));
+            header.push(t!("content.col_privileges"));
+        }
+        widths.push(Constraint::Min(20));
+        header.push(t!("content.col_comment"));
 
         let table = Table::new(rows, widths)
             .header(
-                ratatui::widgets::Row::new(vec![
-                    "字段名", "类型", "可空", "默认值", "额外", "注释"
-                ])
-                .style(Style::default().fg(Color::Yellow).bold())
+                ratatui::widgets::Row::new(header)
+                .style(theme::current().heading())
             )
             .block(Block::default().borders(Borders::NONE))
             .column_spacing(1);
 
         frame.render_widget(table, chunks[0]);
+
+        if let Some(note) = restriction_note {
+            frame.render_widget(
+                Paragraph::new(note).style(theme::current().dim()),
+                chunks[1],
+            );
+        }
+    }
+
+    // 汇总当前用户缺失的列权限，如 "无 UPDATE 权限: a, b"
+    fn privilege_restriction_note(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        let checks: [PrivilegeCheck; 3] = [
+            ("SELECT", |p| p.select),
+            ("INSERT", |p| p.insert),
+            ("UPDATE", |p| p.update),
+        ];
+        for (name, allowed) in checks {
+            let denied: Vec<&str> = self.schema_columns
+                .iter()
+                .filter(|c| c.privileges.is_some_and(|p| !allowed(&p)))
+                .map(|c| c.name.as_str())
+                .collect();
+            if !denied.is_empty() {
+                parts.push(t!("content.denied", name, denied.join(", ")));
+            }
+        }
+        if parts.is_empty() { None } else { Some(t!("content.privileges_note", parts.join(" | "))) }
     }
 
     fn render_table_data(&mut self, frame: &mut Frame, area: Rect) {
+        // 统计视图优先
+        if self.show_stats {
+            if self.stats.is_none() {
+                self.stats = Some(compute_stats(&self.table_headers, &self.table_rows));
+            }
+            self.render_stats(frame, area);
+            return;
+        }
+
         // 垂直模式（\G）优先
         if self.vertical_mode {
             let total_rows = self.table_rows.len();
             if total_rows == 0 {
                 let block = Block::default()
-                    .title("垂直输出")
+                    .title(t!("content.vertical"))
                     .borders(Borders::ALL)
-                    .style(Style::default().fg(Color::Green));
+                    .style(theme::current().border());
                 frame.render_widget(block, area);
                 return;
             }
@@ -279,18 +785,21 @@ impl Content {
                 self.data_scroll_offset = total_rows.saturating_sub(1);
             }
 
+            self.data_page_rows = 1;
             let current_row = self.data_scroll_offset;
-            let title_suffix = format!(" (↑↓切换行) {}/{}", current_row + 1, total_rows);
+            let more = if self.more_rows { "+" } else { "" };
+            let title_suffix = t!("content.vertical_hint", current_row + 1, total_rows, more);
             let title = if let Some(table_name) = &self.current_table_name {
-                format!("垂直输出 - {}{}", table_name, title_suffix)
+                format!("{} - {}{}", t!("content.vertical"), table_name, title_suffix)
             } else {
-                format!("垂直输出{}", title_suffix)
+                format!("{}{}", t!("content.vertical"), title_suffix)
             };
 
             let block = Block::default()
                 .title(title)
+                .title_bottom(self.footer_line())
                 .borders(Borders::ALL)
-                .style(Style::default().fg(Color::Green));
+                .style(theme::current().border());
 
             let inner_area = block.inner(area);
 
@@ -304,14 +813,19 @@ impl Content {
                         .get(i)
                         .cloned()
                         .unwrap_or_default();
-                    ratatui::widgets::Row::new(vec![h.clone(), value])
+                    let value = if self.changed_cells.contains(&(current_row, i)) {
+                        Cell::from(value).style(changed_style())
+                    } else {
+                        Cell::from(value)
+                    };
+                    ratatui::widgets::Row::new(vec![Cell::from(h.clone()), value])
                 })
                 .collect();
 
             let widths = [Constraint::Length(20), Constraint::Min(10)];
             let table = Table::new(rows, widths)
                 .header(
-                    ratatui::widgets::Row::new(vec!["字段", "值"]).style(Style::default().fg(Color::Yellow).bold())
+                    ratatui::widgets::Row::new(vec![t!("content.field"), t!("content.value")]).style(theme::current().heading())
                 )
                 .block(Block::default().borders(Borders::NONE))
                 .column_spacing(1);
@@ -326,6 +840,7 @@ impl Content {
         let available_width = area.width as usize;
         let header_height = 1;
         let max_rows = available_height.saturating_sub(header_height + 2); // 减去边框高度
+        self.data_page_rows = max_rows;
         
         // 限制垂直滚动
         let total_rows = self.table_rows.len();
@@ -342,78 +857,231 @@ impl Content {
         let start_row = self.data_scroll_offset;
         let end_row = (start_row + max_rows).min(total_rows);
         
-        // 计算要显示的列范围
+        // 计算要显示的列范围（每列宽度可单独调整，列间隔 1）
         let total_cols = self.table_headers.len();
-        let col_width = 15; // 每列固定宽度
-        let max_cols = (available_width / col_width).max(1);
+        let col_widths: Vec<usize> = self.table_headers.iter().map(|h| self.column_width(h) as usize).collect();
+        let inner_width = available_width.saturating_sub(2);
+        let all_cols_fit = col_widths.iter().map(|w| w + 1).sum::<usize>() <= inner_width;
         
         // 限制水平滚动
         if self.data_horizontal_scroll >= total_cols {
             self.data_horizontal_scroll = total_cols.saturating_sub(1);
         }
         
-        if total_cols <= max_cols {
+        if all_cols_fit {
             self.data_horizontal_scroll = 0;
         }
         
         let start_col = self.data_horizontal_scroll;
-        let end_col = (start_col + max_cols).min(total_cols);
+        // 从起始列累加宽度，至少显示一列
+        let mut end_col = start_col;
+        let mut used_width = 0;
+        while end_col < total_cols {
+            let w = col_widths[end_col] + 1;
+            if used_width + w > inner_width && end_col > start_col { break; }
+            used_width += w;
+            end_col += 1;
+        }
+
+        if self.relative_time && self.timestamp_columns.is_none() {
+            self.timestamp_columns = Some(detect_timestamp_columns(total_cols, &self.table_rows));
+        }
+        let is_time_col = |idx: usize| -> bool {
+            self.relative_time
+                && self.timestamp_columns.as_ref().is_some_and(|cols| cols.get(idx).copied().unwrap_or(false))
+        };
         
-        // 创建要显示的行
+        let search = self.search_term.as_ref().map(|t| t.to_lowercase());
+        let is_match = |cell: &str| search.as_ref().is_some_and(|t| cell.to_lowercase().contains(t));
+
+        // 创建要显示的行（查找匹配的单元格高亮）
         let rows: Vec<ratatui::widgets::Row> = self.table_rows
             .iter()
             .enumerate()
             .filter(|(idx, _)| *idx >= start_row && *idx < end_row)
-            .map(|(_, row)| {
-                let visible_cells: Vec<String> = row
+            .map(|(row_idx, row)| {
+                let clicked = self.clicked_row == Some(row_idx);
+                let visible_cells: Vec<Cell> = row
                     .iter()
                     .enumerate()
                     .filter(|(idx, _)| *idx >= start_col && *idx < end_col)
-                    .map(|(_, cell)| cell.clone())
+                    .map(|(idx, cell)| {
+                        let text = if is_time_col(idx) {
+                            format_relative(cell).unwrap_or_else(|| cell.clone())
+                        } else {
+                            cell.clone()
+                        };
+                        if is_match(cell) {
+                            Cell::from(text).style(theme::current().matched())
+                        } else if self.changed_cells.contains(&(row_idx, idx)) {
+                            Cell::from(text).style(changed_style())
+                        } else {
+                            Cell::from(text)
+                        }
+                    })
                     .collect();
-                ratatui::widgets::Row::new(visible_cells)
+                let row = ratatui::widgets::Row::new(visible_cells);
+                if clicked { row.style(theme::current().selected()) } else { row }
             })
             .collect();
 
-        // 创建要显示的列头
+        // 创建要显示的列头（相对时间列加标记）
         let visible_headers: Vec<String> = self.table_headers
             .iter()
             .enumerate()
             .filter(|(idx, _)| *idx >= start_col && *idx < end_col)
-            .map(|(_, header)| header.clone())
+            .map(|(idx, header)| {
+                if is_time_col(idx) { format!("{} ⏱", header) } else { header.clone() }
+            })
             .collect();
 
         // 设置列宽
-        let widths: Vec<Constraint> = (0..visible_headers.len())
-            .map(|_| Constraint::Length(col_width as u16))
+        let widths: Vec<Constraint> = col_widths[start_col..end_col]
+            .iter()
+            .map(|w| Constraint::Length(*w as u16))
             .collect();
 
         // 创建标题，显示滚动信息
-        let scroll_info = if total_rows > max_rows || total_cols > max_cols {
-            format!(" (↑↓←→滚动) 行{}/{} 列{}/{}", 
-                start_row + 1, total_rows, 
-                start_col + 1, total_cols)
+        let more = if self.more_rows { "+" } else { "" };
+        let scroll_info = if total_rows > max_rows || !all_cols_fit {
+            t!("content.data_scroll", start_row + 1, total_rows, more, start_col + 1, total_cols)
         } else {
             String::new()
         };
         
+        let derived = self.derived_label
+            .as_ref()
+            .or(self.result_note.as_ref())
+            .map(|l| format!(" [{}]", l))
+            .unwrap_or_default();
+        let search_info = self.search_term
+            .as_ref()
+            .map(|term| t!("content.search_hint", term))
+            .unwrap_or_default();
         let title = if let Some(table_name) = &self.current_table_name {
-            format!("表数据 - {}{}", table_name, scroll_info)
+            format!("{} - {}{}{}{}", t!("content.data"), table_name, derived, scroll_info, search_info)
         } else {
-            format!("表数据{}", scroll_info)
+            format!("{}{}{}{}", t!("content.data"), derived, scroll_info, search_info)
         };
 
         let block = Block::default()
             .title(title)
+            .title_bottom(self.footer_line())
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme::current().border());
 
         let inner_area = block.inner(area);
 
         let table = Table::new(rows, &widths)
             .header(
                 ratatui::widgets::Row::new(visible_headers)
-                .style(Style::default().fg(Color::Yellow).bold())
+                .style(theme::current().heading())
+            )
+            .block(Block::default().borders(Borders::NONE))
+            .column_spacing(1);
+
+        frame.render_widget(block, area);
+        frame.render_widget(table, inner_area);
+        self.data_rows_area = Some(Rect {
+            y: inner_area.y + header_height as u16,
+            height: inner_area.height.saturating_sub(header_height as u16),
+            ..inner_area
+        });
+
+        if self.show_cell_popup {
+            self.render_cell_popup(frame, inner_area, self.focus_index(), start_col);
+        }
+    }
+
+    fn render_warnings(&self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let lines: Vec<Line> = self
+            .warnings
+            .iter()
+            .map(|w| {
+                let style = if w.starts_with("Note") { theme.muted() } else { theme.warning() };
+                Line::from(Span::styled(w.clone(), style))
+            })
+            .collect();
+        let block = Block::default()
+            .title(t!("content.warnings", self.warnings.len()))
+            .borders(Borders::ALL)
+            .style(theme.warning());
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn footer_line(&self) -> Line<'static> {
+        match &self.footer {
+            Some(footer) => Line::from(format!(" {} ", footer)).right_aligned(),
+            None => Line::default(),
+        }
+    }
+
+    // 焦点单元格的完整（绝对）值
+    fn render_cell_popup(&self, frame: &mut Frame, area: Rect, row: usize, col: usize) {
+        let Some(header) = self.table_headers.get(col) else { return; };
+        let value = self.table_rows.get(row).and_then(|r| r.get(col)).cloned().unwrap_or_default();
+        let mut lines = vec![Line::from(Span::styled(value.clone(), theme::current().text()))];
+        if let Some(relative) = format_relative(&value) {
+            lines.push(Line::from(Span::styled(relative, theme::current().muted())));
+        }
+        let width = area.width.min(60);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup = Rect { x: area.x + area.width.saturating_sub(width), y: area.y, width, height };
+        let block = Block::default()
+            .title(t!("content.cell_title", header, row + 1))
+            .borders(Borders::ALL)
+            .style(theme::current().warning());
+        frame.render_widget(ratatui::widgets::Clear, popup);
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(ratatui::widgets::Wrap { trim: false }),
+            popup,
+        );
+    }
+
+    fn render_stats(&mut self, frame: &mut Frame, area: Rect) {
+        let stats = self.stats.as_deref().unwrap_or(&[]);
+        let title = t!("content.stats", self.table_rows.len());
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .style(theme::current().border());
+        let inner_area = block.inner(area);
+
+        let max_rows = (inner_area.height as usize).saturating_sub(1);
+        if self.data_scroll_offset > stats.len().saturating_sub(max_rows) {
+            self.data_scroll_offset = stats.len().saturating_sub(max_rows);
+        }
+        let rows: Vec<ratatui::widgets::Row> = stats
+            .iter()
+            .skip(self.data_scroll_offset)
+            .take(max_rows)
+            .map(|s| {
+                let (min, max) = match s.numeric_range {
+                    Some((lo, hi)) => (lo.to_string(), hi.to_string()),
+                    None => (String::new(), String::new()),
+                };
+                ratatui::widgets::Row::new(vec![
+                    s.name.clone(),
+                    format!("{:.1}%", s.null_percent()),
+                    s.distinct.to_string(),
+                    min,
+                    max,
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(20),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(15),
+            Constraint::Min(15),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                ratatui::widgets::Row::new(vec![t!("content.stats_column"), "NULL%", t!("content.stats_distinct"), t!("content.stats_min"), t!("content.stats_max")])
+                    .style(theme::current().heading())
             )
             .block(Block::default().borders(Borders::NONE))
             .column_spacing(1);
@@ -422,3 +1090,8 @@ impl Content {
         frame.render_widget(table, inner_area);
     }
 }
+
+// \watch 刷新后变化的单元格
+fn changed_style() -> Style {
+    theme::current().changed()
+}
@@ -0,0 +1,171 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::keymap::Keymap;
+use crate::t;
+use crate::ui::theme;
+
+pub enum HelpOutcome {
+    Pending,
+    Closed,
+}
+
+// 一条帮助：按键（或命令）与说明；"* " 开头的补充行没有按键
+struct HelpEntry {
+    keys: String,
+    text: String,
+}
+
+struct HelpSection {
+    title: String,
+    entries: Vec<HelpEntry>,
+}
+
+// ? 打开的帮助浮层：按场景分组列出按键与命令，按键取自当前的 keymap；输入即筛选
+pub struct HelpOverlay {
+    sections: Vec<HelpSection>,
+    filter: String,
+    scroll: usize,
+    // 上次渲染时可见的行数，用于翻页
+    page: usize,
+}
+
+impl HelpOverlay {
+    pub fn new(keymap: &Keymap) -> Self {
+        Self { sections: parse(&keymap.expand(t!("help.reference"))), filter: String::new(), scroll: 0, page: 10 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> HelpOutcome {
+        match key.code {
+            KeyCode::Esc => return HelpOutcome::Closed,
+            KeyCode::F(1) => return HelpOutcome::Closed,
+            KeyCode::Char('?') if self.filter.is_empty() => return HelpOutcome::Closed,
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page),
+            KeyCode::PageDown => self.scroll += self.page,
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.scroll = 0;
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.filter.clear();
+                self.scroll = 0;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.filter.push(c);
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        HelpOutcome::Pending
+    }
+
+    // 筛选后的行：分组标题与其下匹配的条目；标题匹配时列出整组
+    fn lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let filter = self.filter.to_lowercase();
+        let matches = |s: &str| s.to_lowercase().contains(&filter);
+        let key_width = self
+            .sections
+            .iter()
+            .flat_map(|s| &s.entries)
+            .map(|e| e.keys.width())
+            .filter(|w| *w <= 24)
+            .max()
+            .unwrap_or(0);
+        let mut lines = Vec::new();
+        for section in &self.sections {
+            let whole = matches(&section.title);
+            let entries: Vec<&HelpEntry> = section
+                .entries
+                .iter()
+                .filter(|e| whole || matches(&e.keys) || matches(&e.text))
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(section.title.clone(), theme.heading())));
+            for entry in entries {
+                let pad = key_width.saturating_sub(entry.keys.width());
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {}{}  ", entry.keys, " ".repeat(pad)), theme.info()),
+                    Span::styled(entry.text.clone(), theme.text()),
+                ]));
+            }
+        }
+        lines
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let width = area.width.saturating_sub(4).min(110);
+        let height = area.height.saturating_sub(2);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        let block = Block::default()
+            .title(t!("help.title"))
+            .title_bottom(Line::from(t!("help.footer")).right_aligned())
+            .borders(Borders::ALL)
+            .style(theme.border());
+        let inner = block.inner(popup);
+        frame.render_widget(Clear, popup);
+        frame.render_widget(block, popup);
+        if inner.height < 2 {
+            return;
+        }
+
+        let filter = Line::from(vec![
+            Span::styled(t!("help.filter"), theme.heading()),
+            Span::styled(format!("{}▏", self.filter), theme.text()),
+        ]);
+        frame.render_widget(Paragraph::new(filter), Rect { height: 1, ..inner });
+
+        let body = Rect { y: inner.y + 1, height: inner.height - 1, ..inner };
+        let mut lines = self.lines();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(t!("help.no_match"), theme.dim())));
+        }
+        self.page = (body.height as usize).max(1);
+        self.scroll = self.scroll.min(lines.len().saturating_sub(self.page));
+        let visible: Vec<Line> = lines.into_iter().skip(self.scroll).take(self.page).collect();
+        frame.render_widget(Paragraph::new(visible), body);
+    }
+}
+
+// 解析帮助文本：以冒号结尾的行为分组标题，"- 按键: 说明" 为一条，"* " 为上一条的补充
+fn parse(text: &str) -> Vec<HelpSection> {
+    let mut sections: Vec<HelpSection> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(item) = line.strip_prefix("- ") {
+            let (keys, text) = item.split_once(": ").unwrap_or(("", item));
+            let entry = HelpEntry { keys: keys.to_string(), text: text.to_string() };
+            match sections.last_mut() {
+                Some(section) => section.entries.push(entry),
+                None => sections.push(HelpSection { title: String::new(), entries: vec![entry] }),
+            }
+        } else if let Some(item) = line.strip_prefix("* ") {
+            if let Some(section) = sections.last_mut() {
+                section.entries.push(HelpEntry { keys: String::new(), text: format!("· {}", item) });
+            }
+        } else {
+            let title = line.trim_end_matches([':', '：']).to_string();
+            sections.push(HelpSection { title, entries: Vec::new() });
+        }
+    }
+    sections
+}
@@ -0,0 +1,111 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::models::QueryRecord;
+use crate::t;
+use crate::ui::theme;
+
+pub enum HistoryOutcome {
+    Pending,
+    // 载入输入框待编辑
+    Load(String),
+    // 重新执行
+    Run(String),
+    Closed,
+}
+
+// 面板中的一条：本次会话执行过的语句带执行时间、耗时与成败，之前会话的历史只有语句
+struct HistoryItem {
+    sql: String,
+    record: Option<QueryRecord>,
+}
+
+// F2 历史面板：最近的语句在上，Enter 载入输入框，r 重新执行
+pub struct HistoryPanel {
+    items: Vec<HistoryItem>,
+    state: ListState,
+}
+
+impl HistoryPanel {
+    pub fn new(records: &[QueryRecord], history: &[String]) -> Self {
+        let mut items: Vec<HistoryItem> = records
+            .iter()
+            .rev()
+            .map(|r| HistoryItem { sql: r.sql.clone(), record: Some(r.clone()) })
+            .collect();
+        // 没有执行记录的历史命令（元命令、之前会话的语句）排在后面，同一语句只列一次
+        for sql in history.iter().rev() {
+            if !items.iter().any(|item| &item.sql == sql) {
+                items.push(HistoryItem { sql: sql.clone(), record: None });
+            }
+        }
+        let mut state = ListState::default();
+        if !items.is_empty() {
+            state.select(Some(0));
+        }
+        Self { items, state }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> HistoryOutcome {
+        let count = self.items.len();
+        let selected = self.state.selected().and_then(|i| self.items.get(i)).map(|item| item.sql.clone());
+        match key.code {
+            KeyCode::Esc | KeyCode::F(2) | KeyCode::Char('q') => return HistoryOutcome::Closed,
+            KeyCode::Enter => return selected.map_or(HistoryOutcome::Closed, HistoryOutcome::Load),
+            KeyCode::Char('r') => return selected.map_or(HistoryOutcome::Closed, HistoryOutcome::Run),
+            KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+                self.state.select(Some(self.state.selected().map_or(0, |i| (i + count - 1) % count)));
+            }
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                self.state.select(Some(self.state.selected().map_or(0, |i| (i + 1) % count)));
+            }
+            KeyCode::PageUp | KeyCode::Home if count > 0 => self.state.select(Some(0)),
+            KeyCode::PageDown | KeyCode::End if count > 0 => self.state.select(Some(count - 1)),
+            _ => {}
+        }
+        HistoryOutcome::Pending
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                let sql = item.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+                let line = match &item.record {
+                    Some(r) => {
+                        let (mark, style) = if r.success { ("✔", theme::current().success()) } else { ("✘", theme::current().error()) };
+                        Line::from(vec![
+                            Span::styled(format!("{} ", mark), style),
+                            Span::styled(r.executed_at.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+                            Span::styled(format!("{:>8} ", format!("{} ms", r.duration_ms)), Style::default().fg(Color::Yellow)),
+                            Span::styled(format!("{:>7} ", t!("history.rows", r.rows)), Style::default().fg(Color::DarkGray)),
+                            Span::styled(sql, Style::default().fg(Color::White)),
+                        ])
+                    }
+                    None => Line::from(vec![
+                        Span::styled(format!("· {:<9}{:>9}{:>8}", "", "", ""), Style::default().fg(Color::DarkGray)),
+                        Span::styled(sql, Style::default().fg(Color::Gray)),
+                    ]),
+                };
+                ListItem::new(line)
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(t!("history.title", self.items.len()))
+                    .title_bottom(Line::from(t!("history.footer")).right_aligned())
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Cyan)),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray).bold());
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
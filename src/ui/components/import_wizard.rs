@@ -0,0 +1,242 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::t;
+use crate::ui::import::{parse_delimited, ImportJob};
+
+// 可选的分隔符及其名称的消息键，d 键依次切换
+const DELIMITERS: &[(char, &str)] = &[
+    (',', "import.comma"),
+    (';', "import.semicolon"),
+    ('\t', "import.tab"),
+    ('|', "import.pipe"),
+];
+// 预览的数据行数
+const PREVIEW_ROWS: usize = 5;
+
+pub enum ImportOutcome {
+    Pending,
+    Start(ImportJob),
+    Cancelled,
+}
+
+// \import 的导入向导：预览文件，选择分隔符与表头选项，把文件中的列映射到表的列
+pub struct ImportWizard {
+    path: String,
+    table: String,
+    text: String,
+    table_columns: Vec<String>,
+    delimiter: usize,
+    has_header: bool,
+    empty_as_null: bool,
+    records: Vec<(usize, Vec<String>)>,
+    // 文件中每一列写入的表列（table_columns 下标），None 为跳过
+    mapping: Vec<Option<usize>>,
+    focus: usize,
+    error: Option<String>,
+}
+
+impl ImportWizard {
+    pub fn new(path: String, table: String, text: String, table_columns: Vec<String>) -> Self {
+        // 按扩展名与首行猜测分隔符
+        let first_line = text.lines().next().unwrap_or("");
+        let delimiter = if path.to_ascii_lowercase().ends_with(".tsv") {
+            2
+        } else {
+            (0..DELIMITERS.len())
+                .max_by_key(|&i| first_line.matches(DELIMITERS[i].0).count())
+                .filter(|&i| first_line.contains(DELIMITERS[i].0))
+                .unwrap_or(0)
+        };
+        let mut wizard = Self {
+            path,
+            table,
+            text,
+            table_columns,
+            delimiter,
+            has_header: true,
+            empty_as_null: true,
+            records: Vec::new(),
+            mapping: Vec::new(),
+            focus: 0,
+            error: None,
+        };
+        wizard.reparse();
+        wizard
+    }
+
+    fn reparse(&mut self) {
+        self.records = parse_delimited(&self.text, DELIMITERS[self.delimiter].0);
+        self.auto_map();
+    }
+
+    // 有表头时按列名（不区分大小写）匹配，否则按位置对应
+    fn auto_map(&mut self) {
+        let names = self.source_names();
+        self.mapping = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                if self.has_header {
+                    self.table_columns.iter().position(|c| c.eq_ignore_ascii_case(name.trim()))
+                } else {
+                    (i < self.table_columns.len()).then_some(i)
+                }
+            })
+            .collect();
+        self.focus = self.focus.min(names.len().saturating_sub(1));
+    }
+
+    fn source_names(&self) -> Vec<String> {
+        let Some((_, first)) = self.records.first() else { return Vec::new(); };
+        if self.has_header {
+            first.clone()
+        } else {
+            (1..=first.len()).map(|i| t!("import.column_n", i)).collect()
+        }
+    }
+
+    fn data_rows(&self) -> &[(usize, Vec<String>)] {
+        let skip = usize::from(self.has_header).min(self.records.len());
+        &self.records[skip..]
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ImportOutcome {
+        let count = self.mapping.len();
+        let targets = self.table_columns.len() + 1;
+        match key.code {
+            KeyCode::Esc => return ImportOutcome::Cancelled,
+            KeyCode::Enter => match self.build_job() {
+                Ok(job) => return ImportOutcome::Start(job),
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Up if count > 0 => self.focus = (self.focus + count - 1) % count,
+            KeyCode::Down | KeyCode::Tab if count > 0 => self.focus = (self.focus + 1) % count,
+            // 目标列依次为：跳过、表的各列
+            KeyCode::Left | KeyCode::Right if count > 0 => {
+                let current = self.mapping[self.focus].map_or(0, |i| i + 1);
+                let next = if key.code == KeyCode::Right { (current + 1) % targets } else { (current + targets - 1) % targets };
+                self.mapping[self.focus] = next.checked_sub(1);
+            }
+            KeyCode::Char('d') => {
+                self.delimiter = (self.delimiter + 1) % DELIMITERS.len();
+                self.reparse();
+            }
+            KeyCode::Char('h') => {
+                self.has_header = !self.has_header;
+                self.auto_map();
+            }
+            KeyCode::Char('n') => self.empty_as_null = !self.empty_as_null,
+            _ => return ImportOutcome::Pending,
+        }
+        if !matches!(key.code, KeyCode::Enter) {
+            self.error = None;
+        }
+        ImportOutcome::Pending
+    }
+
+    fn build_job(&self) -> Result<ImportJob, String> {
+        let mapped: Vec<(usize, usize)> = self.mapping.iter().enumerate().filter_map(|(src, t)| t.map(|t| (src, t))).collect();
+        if mapped.is_empty() {
+            return Err(t!("import.nothing_mapped").to_string());
+        }
+        for (i, (_, target)) in mapped.iter().enumerate() {
+            if mapped[..i].iter().any(|(_, t)| t == target) {
+                return Err(t!("import.mapped_twice", self.table_columns[*target]));
+            }
+        }
+        if self.data_rows().is_empty() {
+            return Err(t!("import.no_rows").to_string());
+        }
+        let rows = self
+            .data_rows()
+            .iter()
+            .map(|(line, record)| {
+                let values = mapped
+                    .iter()
+                    .map(|(src, _)| record.get(*src).filter(|v| !(self.empty_as_null && v.is_empty())).cloned())
+                    .collect();
+                (*line, values)
+            })
+            .collect();
+        Ok(ImportJob {
+            table: self.table.clone(),
+            columns: mapped.iter().map(|(_, t)| self.table_columns[*t].clone()).collect(),
+            rows,
+        })
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 96.min(area.width);
+        let height = area.height.saturating_sub(2).max(3);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        let yes_no = |b: bool| if b { t!("import.yes") } else { t!("import.no") };
+        let key_style = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw(t!("import.delimiter", t!(DELIMITERS[self.delimiter].1))),
+                Span::styled("[d]", key_style),
+                Span::raw(t!("import.has_header", yes_no(self.has_header))),
+                Span::styled("[h]", key_style),
+                Span::raw(t!("import.empty_as_null", yes_no(self.empty_as_null))),
+                Span::styled("[n]", key_style),
+                Span::raw(t!("import.data_rows", self.data_rows().len())),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(t!("import.mapping"), Style::default().fg(Color::Cyan))),
+        ];
+
+        let names = self.source_names();
+        let name_width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0).min(24);
+        // 预览、提示等占用的行数之外用于列映射
+        let reserved = PREVIEW_ROWS as u16 + 10;
+        let visible = (height.saturating_sub(reserved) as usize).max(3);
+        let offset = self.focus.saturating_sub(visible - 1);
+        for (i, name) in names.iter().enumerate().skip(offset).take(visible) {
+            let target = self.mapping[i].map_or(t!("import.skip").to_string(), |t| self.table_columns[t].clone());
+            let sample: Vec<&str> = self.data_rows().iter().take(3).filter_map(|(_, r)| r.get(i).map(String::as_str)).collect();
+            let style = if i == self.focus {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else if self.mapping[i].is_none() {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let name: String = name.chars().take(24).collect();
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:<w$} → {:<20}", name, target, w = name_width), style),
+                Span::styled(t!("import.sample", sample.join(", ")), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(t!("import.preview"), Style::default().fg(Color::Cyan))));
+        for (line, record) in self.data_rows().iter().take(PREVIEW_ROWS) {
+            lines.push(Line::from(format!("{:>5}: {}", line, record.join(" | "))));
+        }
+        lines.push(Line::from(""));
+        if let Some(error) = &self.error {
+            lines.push(Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))));
+        }
+        lines.push(Line::from(t!("import.hint")));
+
+        let form = Paragraph::new(lines).block(
+            Block::default()
+                .title(t!("import.title", self.path, self.table))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(Clear, popup);
+        frame.render_widget(form, popup);
+    }
+}
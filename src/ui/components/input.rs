@@ -4,6 +4,11 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
+
+use crate::sql;
+use crate::t;
+use crate::ui::theme;
 
 pub struct Input {
     input: String,
@@ -19,12 +24,31 @@ pub struct Input {
     external_suggestions: Option<Vec<String>>,
     // 可注入的关键字表（来自适配器）；为空则使用默认集
     injected_keywords: Option<Vec<String>>,
+    // Ctrl+R 反向查找历史的状态
+    reverse_search: Option<ReverseSearch>,
+}
+
+// 反向查找：查找词与当前匹配的历史条目；original 为开始查找前的输入，取消时还原
+struct ReverseSearch {
+    query: String,
+    matched: Option<usize>,
+    original: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputMode {
     Command,
-    SQL,
+    Sql,
+    // 结果内查找（/ 进入）
+    Search,
+    // 侧边栏过滤（无结果时 / 进入）
+    Filter,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Input {
@@ -40,6 +64,7 @@ impl Input {
             cursor_pos: 0,
             external_suggestions: None,
             injected_keywords: None,
+            reverse_search: None,
         }
     }
 
@@ -87,6 +112,12 @@ impl Input {
         }
     }
 
+    // 载入持久化的历史（启动时）
+    pub fn load_history(&mut self, entries: Vec<String>) {
+        self.history = entries;
+        self.history_index = self.history.len();
+    }
+
     pub fn get_history_up(&mut self) -> Option<String> {
         if self.history_index > 0 {
             self.history_index -= 1;
@@ -109,6 +140,108 @@ impl Input {
         self.history_index = self.history.len();
     }
 
+    // 正在回看的历史条目；未回看时为最近一条
+    pub fn history_target_index(&self) -> Option<usize> {
+        if self.history_index < self.history.len() {
+            Some(self.history_index)
+        } else {
+            self.history.len().checked_sub(1)
+        }
+    }
+
+    pub fn history_entries(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn history_entry(&self, index: usize) -> Option<&String> {
+        self.history.get(index)
+    }
+
+    pub fn replace_history_entry(&mut self, index: usize, command: String) {
+        if let Some(entry) = self.history.get_mut(index) {
+            *entry = command;
+        }
+    }
+
+    // 开始反向查找（Ctrl+R）；已在查找中时跳到更早的匹配
+    pub fn start_reverse_search(&mut self) {
+        self.hide_suggestions();
+        match self.reverse_search.as_ref() {
+            Some(search) => {
+                let before = search.matched.unwrap_or(self.history.len());
+                let query = search.query.clone();
+                let current = search.matched.and_then(|i| self.history.get(i)).cloned();
+                let older = self.find_history(&query, before, current.as_deref());
+                if let (Some(search), Some(older)) = (self.reverse_search.as_mut(), older) {
+                    search.matched = Some(older);
+                }
+            }
+            None => {
+                self.reverse_search = Some(ReverseSearch {
+                    query: String::new(),
+                    matched: self.history.len().checked_sub(1),
+                    original: self.input.clone(),
+                });
+            }
+        }
+    }
+
+    pub fn is_reverse_searching(&self) -> bool {
+        self.reverse_search.is_some()
+    }
+
+    // 修改查找词后从最近的条目重新匹配
+    pub fn reverse_search_push(&mut self, c: char) {
+        let Some(search) = self.reverse_search.as_mut() else { return; };
+        search.query.push(c);
+        self.refresh_reverse_search();
+    }
+
+    pub fn reverse_search_pop(&mut self) {
+        let Some(search) = self.reverse_search.as_mut() else { return; };
+        search.query.pop();
+        self.refresh_reverse_search();
+    }
+
+    fn refresh_reverse_search(&mut self) {
+        let Some(query) = self.reverse_search.as_ref().map(|s| s.query.clone()) else { return; };
+        let matched = self.find_history(&query, self.history.len(), None);
+        if let Some(search) = self.reverse_search.as_mut() {
+            search.matched = matched;
+        }
+    }
+
+    // 在 before 之前（不含）向前找包含 query（不区分大小写）的条目，跳过与 skip 相同的重复命令
+    fn find_history(&self, query: &str, before: usize, skip: Option<&str>) -> Option<usize> {
+        let query = query.to_lowercase();
+        self.history[..before.min(self.history.len())]
+            .iter()
+            .rposition(|entry| Some(entry.as_str()) != skip && entry.to_lowercase().contains(&query))
+    }
+
+    // 结束查找并把匹配的命令载入输入框（无匹配时保留原输入），返回载入的内容
+    pub fn accept_reverse_search(&mut self) -> String {
+        if let Some(search) = self.reverse_search.take() {
+            let text = search.matched.and_then(|i| self.history.get(i)).cloned().unwrap_or(search.original);
+            self.set_input(&text);
+            self.history_index = self.history.len();
+        }
+        self.input.clone()
+    }
+
+    // 取消查找，还原开始查找前的输入
+    pub fn cancel_reverse_search(&mut self) {
+        if let Some(search) = self.reverse_search.take() {
+            self.set_input(&search.original);
+        }
+    }
+
+    // 用给定文本替换输入内容，光标置于末尾
+    pub fn set_input(&mut self, text: &str) {
+        self.input = text.to_string();
+        self.cursor_pos = self.input.chars().count();
+    }
+
     // toggle_suggestions 已不再使用，交由 App 控制弹出显示
 
     pub fn hide_suggestions(&mut self) {
@@ -231,16 +364,18 @@ impl Input {
         self.hide_suggestions();
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    // 模式标记与提示符（mycli 风格，去除硬编码 mysql 用户名/主机）
+    fn prompt_parts(&self) -> (&'static str, String) {
         let mode_text = match self.mode {
             InputMode::Command => "[CMD_MODE]",
-            InputMode::SQL => "[SQL_MODE]",
+            InputMode::Sql => "[SQL_MODE]",
+            InputMode::Search => "[SEARCH]",
+            InputMode::Filter => "[FILTER]",
         };
-
-        // mycli风格的提示符（更通用，去除硬编码 mysql 用户名/主机）
         let prompt = match self.mode {
             InputMode::Command => "> ".to_string(),
-            InputMode::SQL => {
+            InputMode::Search | InputMode::Filter => "/".to_string(),
+            InputMode::Sql => {
                 if let Some(db) = &self.current_db {
                     format!("{}> ", db)
                 } else {
@@ -248,6 +383,15 @@ impl Input {
                 }
             },
         };
+        (mode_text, prompt)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if self.reverse_search.is_some() {
+            self.render_reverse_search(frame, area);
+            return;
+        }
+        let (mode_text, prompt) = self.prompt_parts();
 
         // 语法高亮的输入内容（带光标，反色覆盖当前字符）
         self.cursor_pos = self.cursor_pos.min(self.input.chars().count());
@@ -256,9 +400,9 @@ impl Input {
         let styled_before = self.highlight_sql_syntax(before);
 
         let mut content_spans = vec![
-            Span::styled(mode_text, Style::default().fg(Color::Yellow).bold()),
+            Span::styled(mode_text, theme::current().heading()),
             Span::raw(" > "),
-            Span::styled(&prompt, Style::default().fg(Color::Green)),
+            Span::styled(&prompt, theme::current().border()),
         ];
         
         content_spans.extend(styled_before);
@@ -283,7 +427,7 @@ impl Input {
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme::current().border());
 
         let paragraph = Paragraph::new(content)
             .block(block)
@@ -294,6 +438,44 @@ impl Input {
         // 建议弹出浮框由 App 统一渲染（覆盖在输入框外部）
     }
 
+    // 查找中的输入框：(reverse-i-search)`查找词': 匹配的命令（匹配部分高亮，多行语句按一行显示）
+    fn render_reverse_search(&self, frame: &mut Frame, area: Rect) {
+        let Some(search) = &self.reverse_search else { return; };
+        let matched = search.matched.and_then(|i| self.history.get(i));
+        let label = if matched.is_some() || search.query.is_empty() { "(reverse-i-search)" } else { "(failing reverse-i-search)" };
+        let mut spans = vec![
+            Span::styled(label, theme::current().heading()),
+            Span::raw("`"),
+            Span::styled(search.query.clone(), theme::current().text()),
+            Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+            Span::raw("': "),
+        ];
+        if let Some(entry) = matched {
+            let flat = entry.split_whitespace().collect::<Vec<_>>().join(" ");
+            let lower = flat.to_lowercase();
+            // 小写后字节长度可能变化，仅在长度一致时按位置高亮
+            let query = search.query.to_lowercase();
+            let found = (!query.is_empty() && lower.len() == flat.len())
+                .then(|| lower.find(&query))
+                .flatten()
+                .map(|start| (start, start + query.len()))
+                .filter(|(start, end)| flat.is_char_boundary(*start) && flat.is_char_boundary(*end));
+            match found {
+                Some((start, end)) => {
+                    spans.push(Span::styled(flat[..start].to_string(), theme::current().text()));
+                    spans.push(Span::styled(flat[start..end].to_string(), theme::current().matched()));
+                    spans.push(Span::styled(flat[end..].to_string(), theme::current().text()));
+                }
+                None => spans.push(Span::styled(flat, theme::current().text())),
+            }
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title_bottom(Line::from(t!("input.reverse_search_hint")).right_aligned())
+            .style(theme::current().border());
+        frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+    }
+
     // 光标移动与边界
     pub fn move_cursor_start(&mut self) { self.cursor_pos = 0; }
     pub fn move_cursor_end(&mut self) { self.cursor_pos = self.input.chars().count(); }
@@ -328,18 +510,12 @@ impl Input {
     }
 
     fn byte_index_for_char_pos(&self, char_pos: usize) -> usize {
-        if char_pos == 0 { return 0; }
-        let mut count = 0;
-        for (byte_idx, _ch) in self.input.char_indices() {
-            if count == char_pos { return byte_idx; }
-            count += 1;
-        }
-        self.input.len()
+        self.input.char_indices().nth(char_pos).map_or(self.input.len(), |(byte_idx, _)| byte_idx)
     }
 
     fn highlight_sql_syntax(&self, input: &str) -> Vec<Span<'static>> {
-        if self.mode != InputMode::SQL {
-            return vec![Span::styled(input.to_string(), Style::default().fg(Color::White))];
+        if self.mode != InputMode::Sql {
+            return vec![Span::styled(input.to_string(), theme::current().text())];
         }
 
         // 如果输入为空，直接返回
@@ -348,10 +524,9 @@ impl Input {
         }
 
         let mut spans = Vec::new();
-        let mut chars = input.chars().peekable();
         let mut current_word = String::new();
         
-        while let Some(ch) = chars.next() {
+        for ch in input.chars() {
             if ch.is_whitespace() {
                 // 如果当前有单词，先处理单词
                 if !current_word.is_empty() {
@@ -376,6 +551,7 @@ impl Input {
     }
 
     fn get_word_style(&self, word: &str) -> Style {
+        let theme = theme::current();
         let word_upper = word.to_uppercase();
         match word_upper.as_str() {
             "SELECT" | "FROM" | "WHERE" | "INSERT" | "UPDATE" | "DELETE" | "CREATE" | "DROP" |
@@ -384,18 +560,18 @@ impl Input {
             "OFFSET" | "DISTINCT" | "COUNT" | "SUM" | "AVG" | "MIN" | "MAX" | "AND" | "OR" |
             "NOT" | "IN" | "LIKE" | "BETWEEN" | "IS" | "NULL" | "TRUE" | "FALSE" | "ASC" |
             "DESC" | "AS" | "UNION" | "ALL" | "EXISTS" => {
-                Style::default().fg(Color::Cyan).bold()
+                theme.fg(theme.keyword).bold()
             },
             _ if word.starts_with('\'') && word.ends_with('\'') => {
-                Style::default().fg(Color::Green) // 字符串
+                theme.fg(theme.string) // 字符串
             },
             _ if word.starts_with('"') && word.ends_with('"') => {
-                Style::default().fg(Color::Green) // 字符串
+                theme.fg(theme.string) // 字符串
             },
             _ if word.parse::<i64>().is_ok() || word.parse::<f64>().is_ok() => {
-                Style::default().fg(Color::Yellow) // 数字
+                theme.fg(theme.number) // 数字
             },
-            _ => Style::default().fg(Color::White), // 普通文本
+            _ => theme.text(), // 普通文本
         }
     }
 
@@ -410,16 +586,16 @@ impl Input {
         let mut suggestion_lines = Vec::new();
         for (i, suggestion) in suggestions.iter().enumerate() {
             let style = if i == self.suggestion_index {
-                Style::default().fg(Color::Black).bg(Color::Green).bold()
+                theme::current().selected()
             } else {
-                Style::default().fg(Color::Cyan)
+                theme::current().info()
             };
             suggestion_lines.push(Line::from(vec![Span::styled(suggestion, style)]));
         }
 
         let suggestion_block = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme::current().border());
 
         let suggestion_paragraph = Paragraph::new(suggestion_lines)
             .block(suggestion_block)
@@ -428,24 +604,69 @@ impl Input {
         frame.render_widget(suggestion_paragraph, popup_area);
     }
 
+    // 建议项的说明面板：第一行签名，第二行说明
+    pub fn render_doc_popup(&self, frame: &mut Frame, area: Rect, signature: &str, description: &str) {
+        let lines = vec![
+            Line::from(Span::styled(signature.to_string(), theme::current().heading())),
+            Line::from(Span::styled(description.to_string(), theme::current().text())),
+        ];
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .style(theme::current().border());
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left);
+        frame.render_widget(ratatui::widgets::Clear, area);
+        frame.render_widget(paragraph, area);
+    }
+
+    // 光标在输入框内（不含边框）的显示列：按终端显示宽度计算，CJK 等宽字符占 2 列
     pub fn cursor_display_column(&self) -> usize {
-        // 计算渲染时左侧前缀宽度：[MODE] + " > " + prompt
-        let mode_text = match self.mode {
-            InputMode::Command => "[CMD_MODE]",
-            InputMode::SQL => "[SQL_MODE]",
-        };
-        let prompt = match self.mode {
-            InputMode::Command => "> ".to_string(),
-            InputMode::SQL => {
-                if let Some(db) = &self.current_db {
-                    format!("{}> ", db)
-                } else {
-                    "> ".to_string()
-                }
-            },
-        };
-        let prefix_len = mode_text.len() + 3 + prompt.len();
-        prefix_len + self.cursor_pos
+        let (mode_text, prompt) = self.prompt_parts();
+        let prefix_width = mode_text.width() + " > ".width() + prompt.width();
+        // 渲染时空白统一显示为单个空格
+        let before: String = self.input
+            .chars()
+            .take(self.cursor_pos)
+            .map(|c| if c.is_whitespace() { ' ' } else { c })
+            .collect();
+        prefix_width + before.width()
+    }
+
+    // 光标所在（或紧邻）的聚合函数名及其字符区间 [start, end)
+    pub fn aggregate_at_cursor(&self) -> Option<(String, usize, usize)> {
+        let chars: Vec<char> = self.input.chars().collect();
+        let cur = self.cursor_pos.min(chars.len());
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let mut start = cur;
+        while start > 0 && is_name_char(chars[start - 1]) { start -= 1; }
+        let mut end = cur;
+        while end < chars.len() && is_name_char(chars[end]) { end += 1; }
+        if start == end { return None; }
+        let name: String = chars[start..end].iter().collect();
+        // 必须是函数调用：名称后（可有空白）紧跟 '('
+        let mut next = end;
+        while next < chars.len() && chars[next] == ' ' { next += 1; }
+        if chars.get(next) != Some(&'(') { return None; }
+        sql::aggregate_null_semantics(&name)?;
+        Some((name.to_uppercase(), start, end))
+    }
+
+    // 将光标处的聚合调用包裹为 COALESCE(AGG(...), 默认值)；COUNT 等无需包裹时返回 false
+    pub fn wrap_aggregate_in_coalesce(&mut self) -> bool {
+        let Some((name, start, end)) = self.aggregate_at_cursor() else { return false; };
+        let Some((_, Some(default))) = sql::aggregate_null_semantics(&name) else { return false; };
+        let chars: Vec<char> = self.input.chars().collect();
+        let mut open = end;
+        while open < chars.len() && chars[open] != '(' { open += 1; }
+        let Some(close) = sql::matching_paren(&chars, open) else { return false; };
+        let call: String = chars[start..=close].iter().collect();
+        let wrapped = format!("COALESCE({}, {})", call, default);
+        let start_byte = self.byte_index_for_char_pos(start);
+        let end_byte = self.byte_index_for_char_pos(close + 1);
+        self.input.replace_range(start_byte..end_byte, &wrapped);
+        self.cursor_pos = start + wrapped.chars().count();
+        true
     }
 
     fn current_token(&self) -> (String, usize) {
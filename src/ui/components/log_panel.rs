@@ -0,0 +1,104 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use tracing::Level;
+
+use crate::logging;
+use crate::t;
+use crate::ui::theme;
+
+pub enum LogOutcome {
+    Pending,
+    Closed,
+}
+
+// 数字键 1-5 对应的最低显示级别
+const LEVELS: [Level; 5] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE];
+
+// ~ 打开的日志面板：显示连接与查询诊断，每次绘制取最新日志；默认跟随末尾
+pub struct LogPanel {
+    level: Level,
+    // 首个可见行；None 表示跟随最新日志
+    scroll: Option<usize>,
+    // 上次渲染时可见的行数，用于翻页
+    page: usize,
+}
+
+impl Default for LogPanel {
+    fn default() -> Self {
+        Self { level: Level::INFO, scroll: None, page: 10 }
+    }
+}
+
+impl LogPanel {
+    pub fn handle_key(&mut self, key: KeyEvent) -> LogOutcome {
+        let count = logging::records(self.level).len();
+        let last = count.saturating_sub(self.page);
+        let top = self.scroll.unwrap_or(last);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('~') | KeyCode::Char('q') => return LogOutcome::Closed,
+            KeyCode::Char(c @ '1'..='5') => {
+                self.level = LEVELS[c as usize - '1' as usize];
+                self.scroll = None;
+            }
+            KeyCode::Up => self.scroll = Some(top.saturating_sub(1)),
+            KeyCode::Down => self.scroll = Some(top + 1),
+            KeyCode::PageUp => self.scroll = Some(top.saturating_sub(self.page)),
+            KeyCode::PageDown => self.scroll = Some(top + self.page),
+            KeyCode::Home => self.scroll = Some(0),
+            KeyCode::End => self.scroll = None,
+            _ => {}
+        }
+        // 滚到末尾即恢复跟随
+        if self.scroll.is_some_and(|s| s >= last) {
+            self.scroll = None;
+        }
+        LogOutcome::Pending
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let records = logging::records(self.level);
+        let block = Block::default()
+            .title(t!("logs.title", self.level, records.len()))
+            .title_bottom(Line::from(t!("logs.footer")).right_aligned())
+            .borders(Borders::ALL)
+            .style(theme.border());
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        self.page = (inner.height as usize).max(1);
+        let last = records.len().saturating_sub(self.page);
+        let top = self.scroll.map_or(last, |s| s.min(last));
+        let lines: Vec<Line> = records
+            .iter()
+            .skip(top)
+            .take(self.page)
+            .map(|r| {
+                let style = match r.level {
+                    Level::ERROR => theme.error(),
+                    Level::WARN => theme.warning(),
+                    Level::INFO => theme.info(),
+                    Level::DEBUG => theme.muted(),
+                    _ => theme.dim(),
+                };
+                Line::from(vec![
+                    Span::styled(r.time.format("%H:%M:%S ").to_string(), theme.dim()),
+                    Span::styled(format!("{:<5} ", r.level), style),
+                    Span::styled(format!("{} ", r.target), theme.muted()),
+                    Span::styled(r.message.clone(), theme.text()),
+                ])
+            })
+            .collect();
+        if lines.is_empty() {
+            frame.render_widget(Paragraph::new(Span::styled(t!("logs.empty"), theme.dim())), inner);
+        } else {
+            frame.render_widget(Paragraph::new(lines), inner);
+        }
+    }
+}
@@ -1,9 +1,31 @@
+pub mod action_menu;
+pub mod confirm_dialog;
 pub mod sidebar;
 pub mod content;
 pub mod status_bar;
 pub mod input;
+pub mod stats;
+pub mod relative_time;
+pub mod table_watch;
+pub mod result_list;
+pub mod param_form;
+pub mod import_wizard;
+pub mod history_panel;
+pub mod snippet_form;
+pub mod help_overlay;
+pub mod log_panel;
 
+pub use action_menu::ActionMenu;
+pub use confirm_dialog::ConfirmDialog;
 pub use sidebar::Sidebar;
 pub use content::Content;
 pub use status_bar::StatusBar;
 pub use input::Input;
+pub use table_watch::TableWatch;
+pub use result_list::ResultList;
+pub use param_form::ParamForm;
+pub use import_wizard::ImportWizard;
+pub use history_panel::HistoryPanel;
+pub use snippet_form::SnippetForm;
+pub use help_overlay::HelpOverlay;
+pub use log_panel::LogPanel;
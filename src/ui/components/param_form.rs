@@ -0,0 +1,98 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::t;
+
+// 用户对参数表单的操作结果
+pub enum ParamOutcome {
+    Pending,
+    Submitted(Vec<String>),
+    Cancelled,
+}
+
+// 执行带占位符的语句前逐个填写参数：Tab/上下切换，Enter 执行，Esc 取消
+pub struct ParamForm {
+    names: Vec<String>,
+    values: Vec<String>,
+    focus: usize,
+}
+
+impl ParamForm {
+    // values 为上次执行该语句时的取值，用于快速重跑
+    pub fn new(names: Vec<String>, values: Option<Vec<String>>) -> Self {
+        let values = values
+            .filter(|v| v.len() == names.len())
+            .unwrap_or_else(|| vec![String::new(); names.len()]);
+        Self { names, values, focus: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ParamOutcome {
+        let count = self.names.len();
+        match key.code {
+            KeyCode::Esc => return ParamOutcome::Cancelled,
+            KeyCode::Enter => return ParamOutcome::Submitted(self.values.clone()),
+            KeyCode::Tab | KeyCode::Down => self.focus = (self.focus + 1) % count,
+            KeyCode::BackTab | KeyCode::Up => self.focus = (self.focus + count - 1) % count,
+            KeyCode::Backspace => {
+                self.values[self.focus].pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => self.values[self.focus].clear(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => self.values[self.focus].push(c),
+            _ => {}
+        }
+        ParamOutcome::Pending
+    }
+
+    // 在给定区域中居中渲染
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let label_width = self.names.iter().map(|n| n.chars().count() + 1).max().unwrap_or(0);
+        let mut lines: Vec<Line> = self
+            .names
+            .iter()
+            .zip(&self.values)
+            .enumerate()
+            .map(|(i, (name, value))| {
+                // ? 占位符按序号显示
+                let label = if name.chars().all(|c| c.is_ascii_digit()) { format!("?{}", name) } else { format!(":{}", name) };
+                let style = if i == self.focus {
+                    Style::default().fg(Color::Black).bg(Color::Cyan)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let cursor = if i == self.focus { "▏" } else { "" };
+                Line::from(vec![
+                    Span::styled(format!("{:<width$} ", label, width = label_width), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("{}{}", value, cursor), style),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!("param.binding_hint"),
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(t!("param.hint")));
+
+        let width = 64.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        let form = Paragraph::new(lines).block(
+            Block::default()
+                .title(t!("param.title"))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(Clear, popup);
+        frame.render_widget(form, popup);
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{Local, NaiveDateTime};
+
+use crate::t;
+
+// 适配器输出的时间格式（可带小数秒或 T 分隔）
+const FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M:%S%.f"];
+
+pub fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    let v = value.trim();
+    FORMATS.iter().find_map(|f| NaiveDateTime::parse_from_str(v, f).ok())
+}
+
+// 非 NULL 值全部可解析为时间（且至少一个）的列视为时间列
+pub fn detect_timestamp_columns(column_count: usize, rows: &[Vec<String>]) -> Vec<bool> {
+    (0..column_count)
+        .map(|idx| {
+            let mut seen = false;
+            for row in rows {
+                let value = row.get(idx).map(|s| s.as_str()).unwrap_or("NULL");
+                if value == "NULL" { continue; }
+                if parse_timestamp(value).is_none() { return false; }
+                seen = true;
+            }
+            seen
+        })
+        .collect()
+}
+
+// 相对当前本地时间的描述，如 "3 小时前"、"2 天后"（英文为 "3 hours ago"、"in 2 days"）
+pub fn format_relative(value: &str) -> Option<String> {
+    parse_timestamp(value).map(relative_to_now)
+}
+
+pub fn relative_to_now(ts: NaiveDateTime) -> String {
+    let now = Local::now().naive_local();
+    let secs = (now - ts).num_seconds();
+    let (amount, future) = (secs.unsigned_abs(), secs < 0);
+    let (count, one, many) = match amount {
+        0..=59 => return t!("time.just_now").to_string(),
+        60..=3599 => (amount / 60, "time.minute", "time.minutes"),
+        3600..=86_399 => (amount / 3600, "time.hour", "time.hours"),
+        86_400..=2_591_999 => (amount / 86_400, "time.day", "time.days"),
+        2_592_000..=31_535_999 => (amount / 2_592_000, "time.month", "time.months"),
+        _ => (amount / 31_536_000, "time.year", "time.years"),
+    };
+    // 英文区分单复数，中文两者相同
+    let text = t!(if count == 1 { one } else { many }, count);
+    if future { t!("time.later", text) } else { t!("time.ago", text) }
+}
@@ -0,0 +1,164 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+use std::time::Duration;
+
+use crate::t;
+
+// 多语句中单条语句的执行状态
+pub enum StatementStatus {
+    Pending,
+    Running,
+    // 返回行数，more 表示只保留了首批行
+    Rows { count: usize, more: bool },
+    // 非查询语句的摘要，如 "受影响 1 行"
+    Done(String),
+    Failed(String),
+    Skipped,
+}
+
+pub struct BatchEntry {
+    pub sql: String,
+    pub status: StatementStatus,
+    pub elapsed: Option<Duration>,
+    // 查询语句的结果集，切换到该条时重新展示
+    pub result: Option<(Vec<String>, Vec<Vec<String>>)>,
+}
+
+impl BatchEntry {
+    // (标记, 说明, 颜色)
+    fn status_parts(&self) -> (&'static str, String, Color) {
+        match &self.status {
+            StatementStatus::Pending => ("·", t!("results.pending").to_string(), Color::DarkGray),
+            StatementStatus::Running => ("…", t!("results.running").to_string(), Color::Yellow),
+            StatementStatus::Rows { count, more } => {
+                ("✔", t!("results.rows", format!("{}{}", count, if *more { "+" } else { "" })), Color::Green)
+            }
+            StatementStatus::Done(summary) => ("✔", summary.clone(), Color::Green),
+            StatementStatus::Failed(err) => ("✘", err.clone(), Color::Red),
+            StatementStatus::Skipped => ("-", t!("results.skipped").to_string(), Color::DarkGray),
+        }
+    }
+
+    // 无结果集的语句切换到时展示的文字
+    pub fn describe(&self) -> String {
+        let (mark, detail, _) = self.status_parts();
+        let elapsed = self.elapsed.map(|d| t!("results.elapsed", d.as_millis())).unwrap_or_default();
+        format!("{}\n\n{} {}{}", self.sql, mark, detail, elapsed)
+    }
+}
+
+// 一次提交的多条语句（stmt1; stmt2; ...）：按顺序执行，遇错停止，
+// 结果按条保存，[ / ] 切换查看
+pub struct ResultList {
+    entries: Vec<BatchEntry>,
+    pub vertical: bool,
+    // 正在执行的语句下标
+    current: Option<usize>,
+    state: ListState,
+}
+
+impl ResultList {
+    pub fn new(statements: Vec<String>, vertical: bool) -> Self {
+        let entries = statements
+            .into_iter()
+            .map(|sql| BatchEntry { sql, status: StatementStatus::Pending, elapsed: None, result: None })
+            .collect();
+        Self { entries, vertical, current: None, state: ListState::default() }
+    }
+
+    // 取下一条待执行的语句并标记为执行中
+    pub fn start_next(&mut self) -> Option<String> {
+        let next = self.current.map_or(0, |i| i + 1);
+        let entry = self.entries.get_mut(next).filter(|e| matches!(e.status, StatementStatus::Pending))?;
+        entry.status = StatementStatus::Running;
+        self.current = Some(next);
+        self.state.select(Some(next));
+        Some(entry.sql.clone())
+    }
+
+    // 记录当前语句的结果；失败时其余语句不再执行
+    pub fn finish_current(
+        &mut self,
+        status: StatementStatus,
+        elapsed: Duration,
+        result: Option<(Vec<String>, Vec<Vec<String>>)>,
+    ) {
+        let Some(entry) = self.current.and_then(|i| self.entries.get_mut(i)) else { return; };
+        let failed = matches!(status, StatementStatus::Failed(_));
+        entry.status = status;
+        entry.elapsed = Some(elapsed);
+        entry.result = result;
+        if failed {
+            self.skip_rest();
+        }
+    }
+
+    pub fn skip_rest(&mut self) {
+        for entry in self.entries.iter_mut() {
+            if matches!(entry.status, StatementStatus::Pending) {
+                entry.status = StatementStatus::Skipped;
+            }
+        }
+    }
+
+    // (成功条数, 总条数)
+    pub fn progress(&self) -> (usize, usize) {
+        let ok = self
+            .entries
+            .iter()
+            .filter(|e| matches!(e.status, StatementStatus::Rows { .. } | StatementStatus::Done(_)))
+            .count();
+        (ok, self.entries.len())
+    }
+
+    // 选中上一条/下一条已执行的语句
+    pub fn select_offset(&mut self, delta: isize) -> Option<&BatchEntry> {
+        let executed = self.entries.iter().filter(|e| e.elapsed.is_some()).count();
+        if executed == 0 {
+            return None;
+        }
+        let current = self.state.selected().unwrap_or(0).min(executed - 1) as isize;
+        let index = (current + delta).rem_euclid(executed as isize) as usize;
+        self.state.select(Some(index));
+        self.entries.get(index)
+    }
+
+    // 列表占用的高度（最多显示 6 条，其余滚动）
+    pub fn height(&self) -> u16 {
+        self.entries.len().min(6) as u16 + 2
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let sql_width = (area.width as usize).saturating_sub(36).max(10);
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let (mark, detail, color) = entry.status_parts();
+                let sql: String = entry.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+                let sql: String = sql.chars().take(sql_width).collect();
+                let elapsed = entry.elapsed.map(|d| format!(" {} ms", d.as_millis())).unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{} {:>2}. ", mark, i + 1), Style::default().fg(color)),
+                    Span::raw(format!("{:<width$}  ", sql, width = sql_width)),
+                    Span::styled(format!("{}{}", detail, elapsed), Style::default().fg(color)),
+                ]))
+            })
+            .collect();
+        let (ok, total) = self.progress();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(t!("results.title", ok, total))
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Green)),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray).bold());
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -5,14 +5,40 @@ use ratatui::{
     Frame,
 };
 use crate::models::{Database, Table};
+use crate::t;
+use crate::ui::theme;
 
+// 树中的一行：数据库节点或当前数据库下的表节点，值为对应列表中的下标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Database(usize),
+    Table(usize),
+}
+
+// 树形侧边栏：数据库为父节点，当前数据库可展开为其下的表；
+// 表只为当前连接的数据库加载，因此同一时间只有当前数据库能展开
 pub struct Sidebar {
     databases: Vec<Database>,
+    // 当前数据库的表
     tables: Vec<Table>,
-    show_databases: bool,
     current_db: Option<String>,
-    db_list_state: ListState,
-    table_list_state: ListState,
+    // 当前数据库是否展开
+    expanded: bool,
+    // 按树的顺序排列的可见行（已按过滤词筛选），列表状态的序号指向这里
+    rows: Vec<Row>,
+    list_state: ListState,
+    // 上次渲染时列表所在区域，供鼠标点击定位
+    list_area: Rect,
+    // / 输入的过滤词
+    filter: String,
+    // 当前用户无 SELECT 权限的表，以灰色显示
+    unreadable: Vec<String>,
+}
+
+impl Default for Sidebar {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Sidebar {
@@ -20,105 +46,207 @@ impl Sidebar {
         Self {
             databases: Vec::new(),
             tables: Vec::new(),
-            show_databases: true,
             current_db: None,
-            db_list_state: ListState::default(),
-            table_list_state: ListState::default(),
+            expanded: false,
+            rows: Vec::new(),
+            list_state: ListState::default(),
+            list_area: Rect::default(),
+            filter: String::new(),
+            unreadable: Vec::new(),
         }
     }
 
     pub fn set_databases(&mut self, databases: Vec<Database>) {
         self.databases = databases;
-        self.db_list_state.select(Some(0));
+        self.filter.clear();
+        self.rebuild();
+        self.select_current_db();
     }
 
+    // 更新当前数据库的表；已展开时选中第一个表
     pub fn set_tables(&mut self, tables: Vec<Table>) {
+        let selected = self.selected_row();
         self.tables = tables;
-        self.table_list_state.select(Some(0));
+        self.unreadable.clear();
+        self.filter.clear();
+        self.rebuild();
+        if self.expanded {
+            self.select_first_table();
+        } else {
+            self.select_row(selected);
+        }
+    }
+
+    // 展开或收起当前数据库；收起时选中数据库节点
+    pub fn set_expanded(&mut self, expanded: bool) {
+        let selected = self.selected_row();
+        self.expanded = expanded;
+        self.filter.clear();
+        self.rebuild();
+        if expanded {
+            self.select_row(selected);
+        } else {
+            self.select_current_db();
+        }
+    }
+
+    pub fn set_unreadable_tables(&mut self, tables: Vec<String>) {
+        self.unreadable = tables;
     }
 
-    pub fn set_show_databases(&mut self, show: bool) {
-        self.show_databases = show;
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
     }
 
+    // 切换到其他数据库时丢弃原来的表，等待重新加载
     pub fn set_current_db(&mut self, db: Option<String>) {
+        let selected = self.selected_row();
+        if self.current_db != db {
+            self.tables.clear();
+            self.unreadable.clear();
+        }
         self.current_db = db;
+        self.rebuild();
+        self.select_row(selected);
     }
 
     pub fn next_item(&mut self) {
-        if self.show_databases {
-            let i = match self.db_list_state.selected() {
-                Some(i) => {
-                    if i >= self.databases.len().saturating_sub(1) {
-                        0
-                    } else {
-                        i + 1
-                    }
-                }
-                None => 0,
-            };
-            self.db_list_state.select(Some(i));
-        } else {
-            let i = match self.table_list_state.selected() {
-                Some(i) => {
-                    if i >= self.tables.len().saturating_sub(1) {
-                        0
-                    } else {
-                        i + 1
-                    }
-                }
-                None => 0,
-            };
-            self.table_list_state.select(Some(i));
-        }
+        let len = self.rows.len();
+        let i = match self.list_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.list_state.select(Some(i));
     }
 
     pub fn previous_item(&mut self) {
-        if self.show_databases {
-            let i = match self.db_list_state.selected() {
-                Some(i) => {
-                    if i == 0 {
-                        self.databases.len().saturating_sub(1)
-                    } else {
-                        i - 1
-                    }
-                }
-                None => 0,
-            };
-            self.db_list_state.select(Some(i));
-        } else {
-            let i = match self.table_list_state.selected() {
-                Some(i) => {
-                    if i == 0 {
-                        self.tables.len().saturating_sub(1)
-                    } else {
-                        i - 1
-                    }
-                }
-                None => 0,
-            };
-            self.table_list_state.select(Some(i));
+        let len = self.rows.len();
+        let i = match self.list_state.selected() {
+            Some(0) | None => len.saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    // 选中表时跳到其所属数据库节点；返回是否移动了选中项
+    pub fn select_parent(&mut self) -> bool {
+        if matches!(self.selected_row(), Some(Row::Table(_))) {
+            self.select_current_db();
+            return true;
         }
+        false
     }
 
-    pub fn get_selected_database(&self) -> Option<&Database> {
-        if self.show_databases {
-            self.db_list_state.selected().and_then(|i| self.databases.get(i))
+    // 选中的是否为当前数据库节点
+    pub fn current_db_selected(&self) -> bool {
+        self.get_selected_database()
+            .is_some_and(|db| self.current_db.as_deref() == Some(db.name.as_str()))
+    }
+
+    // 设置过滤词并重新计算可见行；子串匹配排在前面，其次是按顺序包含各字符的模糊匹配
+    // 当前数据库展开时过滤其下的表，父节点保留以显示上下文
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = filter.to_string();
+        self.rebuild();
+        if self.expanded && !self.filter.is_empty() && self.rows.iter().any(|r| matches!(r, Row::Table(_))) {
+            self.select_first_table();
         } else {
-            None
+            self.list_state.select((!self.rows.is_empty()).then_some(0));
         }
     }
 
-    pub fn get_selected_table(&self) -> Option<&Table> {
-        if !self.show_databases {
-            self.table_list_state.selected().and_then(|i| self.tables.get(i))
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    fn rebuild(&mut self) {
+        let databases = ranked(self.databases.iter().map(|d| d.name.as_str()), &self.filter);
+        let tables = if self.expanded {
+            ranked(self.tables.iter().map(|t| t.name.as_str()), &self.filter)
         } else {
-            None
+            Vec::new()
+        };
+        let current = self
+            .current_db
+            .as_deref()
+            .and_then(|name| self.databases.iter().position(|d| d.name == name));
+        // 当前数据库有匹配的表时，即使名称不匹配也保留为父节点
+        let mut order = databases;
+        if let Some(current) = current {
+            if !tables.is_empty() && !order.contains(&current) {
+                order.insert(0, current);
+            }
+        }
+        let mut rows = Vec::new();
+        for i in order {
+            rows.push(Row::Database(i));
+            if Some(i) == current {
+                rows.extend(tables.iter().map(|&j| Row::Table(j)));
+            }
+        }
+        // 当前数据库不在列表中（如 SQLite）：表直接作为顶层节点
+        if current.is_none() {
+            rows.extend(tables.iter().map(|&j| Row::Table(j)));
+        }
+        self.rows = rows;
+    }
+
+    fn selected_row(&self) -> Option<Row> {
+        self.list_state.selected().and_then(|i| self.rows.get(i).copied())
+    }
+
+    fn select_row(&mut self, row: Option<Row>) {
+        let index = row.and_then(|row| self.rows.iter().position(|r| *r == row));
+        match index {
+            Some(index) => self.list_state.select(Some(index)),
+            None => self.select_current_db(),
         }
     }
 
-    pub fn get_show_databases(&self) -> bool {
-        self.show_databases
+    fn select_current_db(&mut self) {
+        let index = self.rows.iter().position(|r| match r {
+            Row::Database(i) => self.current_db.as_deref() == Some(self.databases[*i].name.as_str()),
+            Row::Table(_) => false,
+        });
+        self.list_state.select(index.or((!self.rows.is_empty()).then_some(0)));
+    }
+
+    fn select_first_table(&mut self) {
+        match self.rows.iter().position(|r| matches!(r, Row::Table(_))) {
+            Some(index) => self.list_state.select(Some(index)),
+            None => self.select_current_db(),
+        }
+    }
+
+    // 鼠标位置对应的列表项序号
+    pub fn item_at(&self, position: Position) -> Option<usize> {
+        if !self.list_area.contains(position) {
+            return None;
+        }
+        let index = self.list_state.offset() + (position.y - self.list_area.y) as usize;
+        (index < self.rows.len()).then_some(index)
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.list_state.selected()
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.list_state.select(Some(index));
+    }
+
+    pub fn get_selected_database(&self) -> Option<&Database> {
+        match self.selected_row()? {
+            Row::Database(i) => self.databases.get(i),
+            Row::Table(_) => None,
+        }
+    }
+
+    pub fn get_selected_table(&self) -> Option<&Table> {
+        match self.selected_row()? {
+            Row::Table(i) => self.tables.get(i),
+            Row::Database(_) => None,
+        }
     }
 
     pub fn get_tables_count(&self) -> usize {
@@ -130,18 +258,24 @@ impl Sidebar {
     pub fn get_tables_ref(&self) -> &Vec<Table> { &self.tables }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
         // 标题
-        let title = if self.show_databases {
-            "数据库列表"
-        } else {
-            &format!("表列表 - {}", self.current_db.as_deref().unwrap_or(""))
-        };
+        let mut title = t!("sidebar.databases").to_string();
+        if !self.filter.is_empty() {
+            let total = if self.expanded { self.tables.len() } else { self.databases.len() };
+            let shown = self
+                .rows
+                .iter()
+                .filter(|r| matches!(r, Row::Table(_)) == self.expanded)
+                .count();
+            title.push_str(&t!("sidebar.filtered", self.filter, shown, total));
+        }
 
         // 创建主框
         let main_block = Block::default()
             .title(title)
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme.border());
 
         // 在框内创建布局
         let inner_area = main_block.inner(area);
@@ -156,75 +290,116 @@ impl Sidebar {
 
         // 渲染主框
         frame.render_widget(main_block, area);
+        self.list_area = chunks[0];
 
-        // 列表
-        if self.show_databases {
-            let items: Vec<ListItem> = self.databases
-                .iter()
-                .map(|db| {
-                    let _comment = db.charset.as_deref().unwrap_or("");
-                    let table_count = db.table_count.map(|c| format!(" ({} 表)", c)).unwrap_or_default();
+        // 树：数据库前显示展开标记，表缩进显示
+        let items: Vec<ListItem> = self.rows
+            .iter()
+            .map(|row| match *row {
+                Row::Database(i) => {
+                    let db = &self.databases[i];
+                    let current = self.current_db.as_deref() == Some(db.name.as_str());
+                    let marker = if current && self.expanded { "▾ " } else { "▸ " };
+                    let table_count = db.table_count.map(|c| t!("sidebar.table_count", c)).unwrap_or_default();
                     ListItem::new(Line::from(vec![
-                        Span::styled(&db.name, Style::default().fg(Color::White)),
-                        Span::styled(table_count, Style::default().fg(Color::Gray)),
+                        Span::styled(marker, theme.muted()),
+                        Span::styled(&db.name, if current { theme.heading() } else { theme.text() }),
+                        Span::styled(table_count, theme.muted()),
                     ]))
-                })
-                .collect();
-
-            let list = List::new(items)
-                .block(Block::default().borders(Borders::NONE))
-                .highlight_style(Style::default().fg(Color::Black).bg(Color::Green).bold());
-            
-            frame.render_stateful_widget(list, chunks[0], &mut self.db_list_state);
-        } else {
-            let items: Vec<ListItem> = self.tables
-                .iter()
-                .map(|table| {
+                }
+                Row::Table(i) => {
+                    let table = &self.tables[i];
                     let comment = table.comment.as_deref().unwrap_or("");
+                    let style = if self.unreadable.contains(&table.name) { theme.dim() } else { theme.text() };
                     ListItem::new(Line::from(vec![
-                        Span::styled(&table.name, Style::default().fg(Color::White)),
+                        Span::raw("    "),
+                        Span::styled(&table.name, style),
                         if !comment.is_empty() {
-                            Span::styled(format!(" - {}", comment), Style::default().fg(Color::Gray))
+                            Span::styled(format!(" - {}", comment), theme.muted())
                         } else {
                             Span::raw("")
                         },
                     ]))
-                })
-                .collect();
+                }
+            })
+            .collect();
 
-            let list = List::new(items)
-                .block(Block::default().borders(Borders::NONE))
-                .highlight_style(Style::default().fg(Color::Black).bg(Color::Green).bold());
-            
-            frame.render_stateful_widget(list, chunks[0], &mut self.table_list_state);
-        }
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::NONE))
+            .highlight_style(theme.selected());
+
+        frame.render_stateful_widget(list, chunks[0], &mut self.list_state);
 
         // 状态信息（在框内底部）
         let status = if let Some(selected) = self.get_selected_database() {
-            format!("选中: {}", selected.name)
+            t!("sidebar.selected", selected.name)
         } else if let Some(selected) = self.get_selected_table() {
-            format!("选中: {}", selected.name)
+            if self.unreadable.contains(&selected.name) {
+                t!("sidebar.unreadable", selected.name)
+            } else {
+                t!("sidebar.selected", selected.name)
+            }
         } else {
             String::new()
         };
 
-        let status_style = Style::default().fg(Color::Green);
+        let status_style = theme.border();
         frame.render_widget(
             ratatui::widgets::Paragraph::new(status).style(status_style),
             chunks[1]
         );
 
         // 帮助信息（在框内底部）
-        let help_text = if self.show_databases {
-            "Up/Down 移动 | Enter 选择 | d 详情"
+        let help_text = if self.get_selected_table().is_some() {
+            t!("sidebar.help_tables")
         } else {
-            "Up/Down 移动 | Enter 选择 | t 详情 | s 返回"
+            t!("sidebar.help_databases")
         };
 
-        let help_style = Style::default().fg(Color::Gray);
+        let help_style = theme.muted();
         frame.render_widget(
             ratatui::widgets::Paragraph::new(help_text).style(help_style),
             chunks[2]
         );
     }
 }
+
+// 按过滤词筛选并排序的下标；过滤词为空时保持原顺序
+fn ranked<'a>(names: impl Iterator<Item = &'a str>, filter: &str) -> Vec<usize> {
+    let mut scored: Vec<((u8, usize), usize)> = names
+        .enumerate()
+        .filter_map(|(i, name)| match_score(name, filter).map(|score| (score, i)))
+        .collect();
+    scored.sort_by_key(|&(score, _)| score);
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+// 名称与过滤词的匹配程度（越小越靠前），不区分大小写；不匹配时为 None
+// 子串匹配按出现位置排序，模糊匹配（按顺序包含各字符）按首尾跨度排序
+fn match_score(name: &str, filter: &str) -> Option<(u8, usize)> {
+    if filter.is_empty() {
+        return Some((0, 0));
+    }
+    let name = name.to_lowercase();
+    let filter = filter.to_lowercase();
+    if let Some(pos) = name.find(&filter) {
+        return Some((0, pos));
+    }
+    let mut chars = filter.chars();
+    let mut want = chars.next();
+    let (mut first, mut last) = (None, 0);
+    for (i, c) in name.chars().enumerate() {
+        if Some(c) == want {
+            first.get_or_insert(i);
+            last = i;
+            want = chars.next();
+            if want.is_none() {
+                break;
+            }
+        }
+    }
+    match (want, first) {
+        (None, Some(first)) => Some((1, last - first)),
+        _ => None,
+    }
+}
@@ -0,0 +1,130 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::t;
+
+// 用户对占位符表单的操作结果
+pub enum SnippetOutcome {
+    Pending,
+    // (参数名, 值)，按表单顺序
+    Submitted(Vec<(String, String)>),
+    Cancelled,
+}
+
+// 取用带 {{参数}} 的语句前逐个填写：初值为上次用过的值，否则为默认值；
+// ↑↓ 翻看该参数用过的值，Tab 切换参数，Enter 执行，Esc 取消
+pub struct SnippetForm {
+    snippet: String,
+    names: Vec<String>,
+    values: Vec<String>,
+    // 每个参数可翻看的候选：用过的值（最近的在前），之后为默认值
+    choices: Vec<Vec<String>>,
+    // 正在查看的候选下标；手工输入后为 None
+    choice: Vec<Option<usize>>,
+    focus: usize,
+}
+
+impl SnippetForm {
+    pub fn new(snippet: String, params: Vec<(String, Option<String>)>, history: impl Fn(&str) -> Vec<String>) -> Self {
+        let mut names = Vec::new();
+        let mut choices = Vec::new();
+        for (name, default) in params {
+            let mut values = history(&name);
+            if let Some(default) = default {
+                if !values.contains(&default) {
+                    values.push(default);
+                }
+            }
+            names.push(name);
+            choices.push(values);
+        }
+        let values: Vec<String> = choices.iter().map(|c| c.first().cloned().unwrap_or_default()).collect();
+        let choice = choices.iter().map(|c| (!c.is_empty()).then_some(0)).collect();
+        Self { snippet, names, values, choices, choice, focus: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SnippetOutcome {
+        let count = self.names.len();
+        let i = self.focus;
+        match key.code {
+            KeyCode::Esc => return SnippetOutcome::Cancelled,
+            KeyCode::Enter => {
+                return SnippetOutcome::Submitted(self.names.iter().cloned().zip(self.values.iter().cloned()).collect());
+            }
+            KeyCode::Tab => self.focus = (self.focus + 1) % count,
+            KeyCode::BackTab => self.focus = (self.focus + count - 1) % count,
+            KeyCode::Up | KeyCode::Down if !self.choices[i].is_empty() => {
+                let n = self.choices[i].len();
+                let next = match (self.choice[i], key.code) {
+                    (None, _) => 0,
+                    (Some(c), KeyCode::Down) => (c + 1) % n,
+                    (Some(c), _) => (c + n - 1) % n,
+                };
+                self.choice[i] = Some(next);
+                self.values[i] = self.choices[i][next].clone();
+            }
+            KeyCode::Backspace => {
+                self.values[i].pop();
+                self.choice[i] = None;
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.values[i].clear();
+                self.choice[i] = None;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.values[i].push(c);
+                self.choice[i] = None;
+            }
+            _ => {}
+        }
+        SnippetOutcome::Pending
+    }
+
+    // 在给定区域中居中渲染
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let label_width = self.names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, (name, value)) in self.names.iter().zip(&self.values).enumerate() {
+            let style = if i == self.focus {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let cursor = if i == self.focus { "▏" } else { "" };
+            let hint = match (self.choice[i], self.choices[i].len()) {
+                (Some(c), n) if n > 1 => format!("  {}/{}", c + 1, n),
+                _ => String::new(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<width$} ", name, width = label_width), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{}{}", value, cursor), style),
+                Span::styled(hint, Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(t!("snippet.substitution_hint"), Style::default().fg(Color::DarkGray))));
+        lines.push(Line::from(t!("snippet.hint")));
+
+        let width = 64.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        let form = Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(" {} ", self.snippet))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(Clear, popup);
+        frame.render_widget(form, popup);
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+// 已获取结果集中单列的统计摘要（仅基于客户端已有的行）
+pub struct ColumnStats {
+    pub name: String,
+    pub null_count: usize,
+    pub total: usize,
+    pub distinct: usize,
+    // 全部非 NULL 值都可解析为数字时才有 min/max
+    pub numeric_range: Option<(f64, f64)>,
+}
+
+impl ColumnStats {
+    pub fn null_percent(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.null_count as f64 * 100.0 / self.total as f64 }
+    }
+}
+
+pub fn compute_stats(headers: &[String], rows: &[Vec<String>]) -> Vec<ColumnStats> {
+    headers
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let mut null_count = 0;
+            let mut distinct: HashSet<&str> = HashSet::new();
+            let mut range: Option<(f64, f64)> = None;
+            let mut all_numeric = true;
+            for row in rows {
+                let value = row.get(idx).map(|s| s.as_str()).unwrap_or("NULL");
+                if value == "NULL" {
+                    null_count += 1;
+                    continue;
+                }
+                distinct.insert(value);
+                if all_numeric {
+                    match value.trim().parse::<f64>() {
+                        Ok(n) => {
+                            range = Some(match range {
+                                Some((lo, hi)) => (lo.min(n), hi.max(n)),
+                                None => (n, n),
+                            });
+                        }
+                        Err(_) => all_numeric = false,
+                    }
+                }
+            }
+            ColumnStats {
+                name: name.clone(),
+                null_count,
+                total: rows.len(),
+                distinct: distinct.len(),
+                numeric_range: if all_numeric { range } else { None },
+            }
+        })
+        .collect()
+}
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use ratatui::{
     layout::{Alignment, Rect},
     prelude::*,
@@ -5,14 +7,44 @@ use ratatui::{
     Frame,
 };
 
+use crate::t;
+use crate::ui::theme;
+
+// 进度动画：按已用时间每 100 ms 换一帧
+pub fn spinner(elapsed: Duration) -> char {
+    const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    FRAMES[(elapsed.as_millis() / 100) as usize % FRAMES.len()]
+}
+
+// 最近一次连接探测的结果
+#[derive(Clone, Copy)]
+pub enum Health {
+    Alive(Duration),
+    Down,
+}
+
 pub struct StatusBar {
     current_db: Option<String>,
     driver_name: Option<String>,
     server_version: Option<String>,
     username: Option<String>,
+    tls: Option<String>,
+    health: Option<Health>,
+    read_only: bool,
+    // 事务进行中 / 自动提交是否开启
+    tx_open: bool,
+    autocommit: bool,
+    // 后台语句开始执行的时间
+    running: Option<Instant>,
     status: String,
 }
 
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StatusBar {
     pub fn new() -> Self {
         Self {
@@ -20,6 +52,12 @@ impl StatusBar {
             driver_name: None,
             server_version: None,
             username: None,
+            tls: None,
+            health: None,
+            read_only: false,
+            tx_open: false,
+            autocommit: true,
+            running: None,
             status: "READY".to_string(),
         }
     }
@@ -40,6 +78,31 @@ impl StatusBar {
         self.username = Some(username);
     }
 
+    pub fn set_tls(&mut self, tls: Option<String>) {
+        self.tls = tls;
+    }
+
+    pub fn set_health(&mut self, health: Option<Health>) {
+        self.health = health;
+    }
+
+    pub fn set_running(&mut self, running: Option<Instant>) {
+        self.running = running;
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn set_transaction(&mut self, open: bool, autocommit: bool) {
+        self.tx_open = open;
+        self.autocommit = autocommit;
+    }
+
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
         let db_info = self.current_db
@@ -56,20 +119,56 @@ impl StatusBar {
             .map(|u| format!("User: {}", u))
             .unwrap_or_else(|| "User: Unknown".to_string());
 
-        let content = Line::from(vec![
-            Span::styled("[SQLTUI] ", Style::default().fg(Color::Green).bold()),
-            Span::styled(&self.status, Style::default().fg(Color::Yellow)),
+        let theme = theme::current();
+        let mut spans = vec![
+            Span::styled("[SQLTUI] ", theme.border().bold()),
+        ];
+        if self.read_only {
+            spans.push(Span::styled(t!("status.read_only"), Style::default().fg(theme.selected_fg).bg(theme.warning).bold()));
+        }
+        if self.tx_open {
+            spans.push(Span::styled("[TX OPEN] ", Style::default().fg(theme.text).bg(theme.error).bold()));
+        } else if !self.autocommit {
+            spans.push(Span::styled("[AUTOCOMMIT OFF] ", theme.error().bold()));
+        }
+        if let Some(started) = self.running {
+            let elapsed = started.elapsed();
+            let frame = spinner(elapsed);
+            spans.push(Span::styled(
+                t!("status.running", frame, format!("{:.1}", elapsed.as_secs_f64())),
+                theme.info().bold(),
+            ));
+        }
+        spans.extend([
+            Span::styled(&self.status, theme.warning()),
             Span::raw(" | "),
-            Span::styled(&user_info, Style::default().fg(Color::Magenta)),
+            Span::styled(&user_info, theme.fg(theme.secondary)),
             Span::raw(" | "),
-            Span::styled(&db_info, Style::default().fg(Color::Cyan)),
+            Span::styled(&db_info, theme.info()),
             Span::raw(" | "),
-            Span::styled(&version_info, Style::default().fg(Color::Blue)),
+            Span::styled(&version_info, theme.fg(theme.tertiary)),
         ]);
+        // 未加密的连接用红色提示
+        if let Some(tls) = self.tls.as_deref() {
+            let color = if tls == "off" { theme.error } else { theme.success };
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(format!("TLS: {}", tls), theme.fg(color)));
+        }
+        // 连接状态：绿色正常，耗时偏高为黄色，探测失败为红色
+        if let Some(health) = self.health {
+            let (text, color) = match health {
+                Health::Alive(rtt) if rtt < Duration::from_millis(200) => (format!("● {} ms", rtt.as_millis()), theme.success),
+                Health::Alive(rtt) => (format!("● {} ms", rtt.as_millis()), theme.warning),
+                Health::Down => (t!("status.down").to_string(), theme.error),
+            };
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(text, theme.fg(color)));
+        }
+        let content = Line::from(spans);
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(theme.border());
 
         let paragraph = Paragraph::new(content)
             .block(block)
@@ -0,0 +1,119 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Sparkline},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+use crate::t;
+
+// 保留的采样点数（超出后丢弃最早的）
+const MAX_SAMPLES: usize = 240;
+
+// \watch-table：定时重新统计表行数，以折线图显示写入速率
+pub struct TableWatch {
+    table: String,
+    interval: Duration,
+    last_tick: Option<Instant>,
+    // (采样时间, 行数)
+    last_count: Option<(Instant, u64)>,
+    first_count: Option<u64>,
+    // 每次采样的写入速率（行/秒，负值即删除按 0 绘制）
+    rates: Vec<f64>,
+    error: Option<String>,
+}
+
+impl TableWatch {
+    pub fn new(table: String, interval: Duration) -> Self {
+        Self {
+            table,
+            interval,
+            last_tick: None,
+            last_count: None,
+            first_count: None,
+            rates: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    // 距下次采样的等待时间；首次立即采样
+    pub fn time_until_tick(&self) -> Duration {
+        match self.last_tick {
+            Some(t) => self.interval.saturating_sub(t.elapsed()),
+            None => Duration::ZERO,
+        }
+    }
+
+    pub fn record(&mut self, count: u64) {
+        let now = Instant::now();
+        self.last_tick = Some(now);
+        self.error = None;
+        if let Some((at, previous)) = self.last_count {
+            let secs = now.duration_since(at).as_secs_f64().max(0.001);
+            self.rates.push((count as f64 - previous as f64) / secs);
+            if self.rates.len() > MAX_SAMPLES {
+                self.rates.remove(0);
+            }
+        }
+        self.first_count.get_or_insert(count);
+        self.last_count = Some((now, count));
+    }
+
+    pub fn record_error(&mut self, error: String) {
+        self.last_tick = Some(Instant::now());
+        self.error = Some(error);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(t!("watch.title", self.table, self.interval.as_secs()))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Green));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Min(0)])
+            .split(inner);
+
+        let count = self.last_count.map(|(_, c)| c);
+        let current = self.rates.last().copied();
+        let average = if self.rates.is_empty() {
+            None
+        } else {
+            Some(self.rates.iter().sum::<f64>() / self.rates.len() as f64)
+        };
+        let fmt_rate = |r: Option<f64>| r.map(|r| t!("watch.rate", format!("{:+.1}", r))).unwrap_or_else(|| "-".to_string());
+        let mut lines = vec![
+            Line::from(t!(
+                "watch.count",
+                count.map(|c| c.to_string()).unwrap_or_else(|| t!("watch.counting").to_string()),
+                match (self.first_count, count) {
+                    (Some(first), Some(c)) => format!("{:+}", c as i64 - first as i64),
+                    _ => "-".to_string(),
+                }
+            )),
+            Line::from(t!("watch.rates", fmt_rate(current), fmt_rate(average))),
+        ];
+        if let Some(error) = &self.error {
+            lines.push(Line::from(Span::styled(t!("watch.failed", error), Style::default().fg(Color::Red))));
+        }
+        frame.render_widget(Paragraph::new(lines).style(Style::default().fg(Color::White)), chunks[0]);
+
+        // 只显示能放下的最近采样
+        let width = chunks[1].width as usize;
+        let start = self.rates.len().saturating_sub(width);
+        let data: Vec<u64> = self.rates[start..].iter().map(|r| r.max(0.0).round() as u64).collect();
+        let sparkline = Sparkline::default()
+            .block(Block::default().title(t!("watch.chart")).borders(Borders::TOP))
+            .data(&data)
+            .style(Style::default().fg(Color::Cyan));
+        frame.render_widget(sparkline, chunks[1]);
+    }
+}
@@ -0,0 +1,257 @@
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    backend::Backend,
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame, Terminal,
+};
+use std::path::PathBuf;
+
+use crate::config::{Config, DRIVERS};
+use crate::db::{new_adapter, DbAdapter};
+use crate::t;
+use crate::ui::terminal::TerminalGuard;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Name,
+    Driver,
+    Host,
+    Port,
+    Username,
+    Password,
+    Database,
+    Service,
+    Path,
+}
+
+// 未提供连接信息时的启动表单：填写后尝试连接，成功则交给 App；
+// 也用于在连接管理界面中新建/编辑连接配置（此时多一个名称字段，Enter 保存）
+pub struct ConnectForm {
+    config: Config,
+    // 编辑连接配置时的名称；None 为直接连接
+    name: Option<String>,
+    focus: usize,
+    port: String,
+    path: String,
+    error: Option<String>,
+}
+
+impl ConnectForm {
+    pub fn new(config: Config) -> Self {
+        let port = config.port.map(|p| p.to_string()).unwrap_or_default();
+        let path = config.path.as_deref().map(|p| p.display().to_string()).unwrap_or_default();
+        Self { config, name: None, port, path, focus: 0, error: None }
+    }
+
+    pub fn for_profile(config: Config, name: &str) -> Self {
+        Self { name: Some(name.to_string()), ..Self::new(config) }
+    }
+
+    // 返回 None 表示用户放弃连接
+    pub async fn run(self) -> Result<Option<(Config, Box<dyn DbAdapter>)>> {
+        let mut guard = TerminalGuard::new()?;
+        self.run_form(guard.terminal_mut()).await
+    }
+
+    pub(crate) async fn run_form<B: Backend>(mut self, terminal: &mut Terminal<B>) -> Result<Option<(Config, Box<dyn DbAdapter>)>> {
+        loop {
+            terminal.draw(|f| self.render(f))?;
+            let Event::Key(key) = event::read()? else { continue };
+            if key.code == KeyCode::Esc || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)) {
+                return Ok(None);
+            }
+            if key.code == KeyCode::Enter {
+                self.error = None;
+                terminal.draw(|f| self.render(f))?;
+                match self.connect().await {
+                    Ok(db) => return Ok(Some((self.config, db))),
+                    Err(e) => self.error = Some(e.to_string()),
+                }
+                continue;
+            }
+            self.handle_key(key);
+        }
+    }
+
+    // 编辑模式：Enter 校验后返回 (名称, 配置)，不连接数据库
+    pub(crate) fn run_edit<B: Backend>(mut self, terminal: &mut Terminal<B>) -> Result<Option<(String, Config)>> {
+        loop {
+            terminal.draw(|f| self.render(f))?;
+            let Event::Key(key) = event::read()? else { continue };
+            match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => match self.finish_edit() {
+                    Ok(name) => return Ok(Some((name, self.config))),
+                    Err(e) => self.error = Some(e.to_string()),
+                },
+                _ => self.handle_key(key),
+            }
+        }
+    }
+
+    fn finish_edit(&mut self) -> Result<String> {
+        let name = self.name.as_deref().unwrap_or("").trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!(t!("connect.name_empty")));
+        }
+        self.apply_inputs()?;
+        Ok(name)
+    }
+
+    async fn connect(&mut self) -> Result<Box<dyn DbAdapter>> {
+        self.apply_inputs()?;
+        self.config.validate()?;
+        new_adapter(&self.config).await
+    }
+
+    // 把文本输入写回配置
+    fn apply_inputs(&mut self) -> Result<()> {
+        self.config.port = if self.port.trim().is_empty() {
+            None
+        } else {
+            Some(self.port.trim().parse().map_err(|_| anyhow::anyhow!(t!("connect.port_invalid")))?)
+        };
+        self.config.path = (!self.path.trim().is_empty()).then(|| PathBuf::from(self.path.trim()));
+        if self.config.database.as_deref() == Some("") {
+            self.config.database = None;
+        }
+        if self.config.service.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            self.config.service = None;
+        }
+        Ok(())
+    }
+
+    // 本地文件库只需要路径
+    fn fields(&self) -> Vec<Field> {
+        let mut fields = if matches!(self.config.driver.as_str(), "sqlite" | "duckdb") {
+            vec![Field::Driver, Field::Path, Field::Database]
+        } else if self.name.is_some() {
+            // 连接配置不保存密码（使用 --save-password 存入系统密钥环）
+            vec![Field::Driver, Field::Host, Field::Port, Field::Username, Field::Database]
+        } else {
+            vec![Field::Driver, Field::Host, Field::Port, Field::Username, Field::Password, Field::Database]
+        };
+        // Oracle 通过服务名连接
+        if self.config.driver == "oracle" {
+            fields.push(Field::Service);
+        }
+        if self.name.is_some() {
+            fields.insert(0, Field::Name);
+        }
+        fields
+    }
+
+    fn focused(&self) -> Field {
+        let fields = self.fields();
+        fields[self.focus.min(fields.len() - 1)]
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let count = self.fields().len();
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => self.focus = (self.focus + 1) % count,
+            KeyCode::BackTab | KeyCode::Up => self.focus = (self.focus + count - 1) % count,
+            KeyCode::Left | KeyCode::Right if self.focused() == Field::Driver => {
+                let current = DRIVERS.iter().position(|d| *d == self.config.driver).unwrap_or(0);
+                let next = if key.code == KeyCode::Right {
+                    (current + 1) % DRIVERS.len()
+                } else {
+                    (current + DRIVERS.len() - 1) % DRIVERS.len()
+                };
+                self.config.driver = DRIVERS[next].to_string();
+                self.focus = self.fields().iter().position(|f| *f == Field::Driver).unwrap_or(0);
+            }
+            KeyCode::Backspace => {
+                if let Some(value) = self.value_mut(self.focused()) {
+                    value.pop();
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(value) = self.value_mut(self.focused()) {
+                    value.push(c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn value_mut(&mut self, field: Field) -> Option<&mut String> {
+        match field {
+            Field::Name => self.name.as_mut(),
+            Field::Driver => None,
+            Field::Host => Some(&mut self.config.host),
+            Field::Port => Some(&mut self.port),
+            Field::Username => Some(&mut self.config.username),
+            Field::Password => Some(&mut self.config.password),
+            Field::Database => Some(self.config.database.get_or_insert_with(String::new)),
+            Field::Service => Some(self.config.service.get_or_insert_with(String::new)),
+            Field::Path => Some(&mut self.path),
+        }
+    }
+
+    fn label_and_value(&self, field: Field) -> (&'static str, String) {
+        match field {
+            Field::Name => (t!("connect.name"), self.name.clone().unwrap_or_default()),
+            Field::Driver => (t!("connect.driver"), format!("< {} >", self.config.driver)),
+            Field::Host => (t!("connect.host"), self.config.host.clone()),
+            Field::Port => (
+                t!("connect.port"),
+                if self.port.is_empty() { t!("connect.port_default", self.config.port()) } else { self.port.clone() },
+            ),
+            Field::Username => (t!("connect.username"), self.config.username.clone()),
+            Field::Password => (t!("connect.password"), "*".repeat(self.config.password.chars().count())),
+            Field::Database => (t!("connect.database"), self.config.database.clone().unwrap_or_default()),
+            Field::Service => (t!("connect.service"), self.config.service.clone().unwrap_or_default()),
+            Field::Path => (t!("connect.path"), self.path.clone()),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = 60.min(area.width);
+        let height = 15.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let focused = self.focused();
+        let mut lines = Vec::new();
+        for field in self.fields() {
+            let (label, value) = self.label_and_value(field);
+            let style = if field == focused {
+                Style::default().fg(Color::Black).bg(Color::Green).bold()
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>8}  ", label), Style::default().fg(Color::Cyan)),
+                Span::styled(value, style),
+            ]));
+        }
+        lines.push(Line::from(""));
+        match &self.error {
+            Some(err) => lines.push(Line::from(Span::styled(err.clone(), Style::default().fg(Color::Red)))),
+            None => lines.push(Line::from(Span::styled(
+                if self.name.is_some() { t!("connect.hint_edit") } else { t!("connect.hint_connect") },
+                Style::default().fg(Color::DarkGray),
+            ))),
+        }
+
+        let form = Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(if self.name.is_some() { t!("connect.title_edit") } else { t!("connect.title_connect") })
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Green)),
+            );
+        frame.render_widget(Clear, popup);
+        frame.render_widget(form, popup);
+    }
+}
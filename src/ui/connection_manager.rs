@@ -0,0 +1,234 @@
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::config::profiles::{Connections, Profile, ProfileUsage};
+use crate::config::Config;
+use crate::ui::components::relative_time::relative_to_now;
+use crate::ui::connect_form::ConnectForm;
+use crate::t;
+use crate::ui::terminal::TerminalGuard;
+
+// 启动界面：列出 connections.toml 中的连接配置（按最近使用排序）
+// Enter 连接，n 新建，e 编辑，d 删除，q/Esc 退出
+pub struct ConnectionManager {
+    // 命令行给出的其余参数，作为各连接配置的基础
+    base: Config,
+    connections: Connections,
+    usage: ProfileUsage,
+    names: Vec<String>,
+    state: ListState,
+    status: Option<(String, Color)>,
+    // 等待确认删除的配置名
+    pending_delete: Option<String>,
+}
+
+impl ConnectionManager {
+    pub fn new(base: Config, connections: Connections) -> Self {
+        let mut manager = Self {
+            base,
+            connections,
+            usage: ProfileUsage::load(),
+            names: Vec::new(),
+            state: ListState::default(),
+            status: None,
+            pending_delete: None,
+        };
+        manager.refresh(None);
+        manager
+    }
+
+    // 返回所选配置合并后的连接参数（由调用方按常规流程连接，以便建立 SSH 隧道、提示输入密码）；
+    // None 表示用户直接退出
+    pub fn run(mut self) -> Result<Option<Config>> {
+        let mut guard = TerminalGuard::new()?;
+        let terminal = guard.terminal_mut();
+        loop {
+            terminal.draw(|f| self.render(f))?;
+            let Event::Key(key) = event::read()? else { continue };
+
+            if let Some(name) = self.pending_delete.take() {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.connections.profiles.remove(&name);
+                    self.connections.save()?;
+                    self.refresh(None);
+                    self.set_status(t!("manager.deleted", name), Color::Yellow);
+                } else {
+                    self.status = None;
+                }
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(None),
+                KeyCode::Down | KeyCode::Char('j') => self.select_offset(1),
+                KeyCode::Up | KeyCode::Char('k') => self.select_offset(-1),
+                KeyCode::Enter => {
+                    let Some(name) = self.selected() else { continue };
+                    let mut config = self.profile_config(&name);
+                    config.fill_password();
+                    match config.validate() {
+                        Ok(()) => return Ok(Some(config)),
+                        Err(e) => self.set_status(format!("{}: {}", name, e), Color::Red),
+                    }
+                }
+                KeyCode::Char('n') => {
+                    let form = ConnectForm::for_profile(self.base.clone(), "");
+                    if let Some((name, config)) = form.run_edit(terminal)? {
+                        self.save_profile(None, &name, &config)?;
+                    }
+                }
+                KeyCode::Char('e') => {
+                    let Some(name) = self.selected() else { continue };
+                    let form = ConnectForm::for_profile(self.profile_config(&name), &name);
+                    if let Some((new_name, config)) = form.run_edit(terminal)? {
+                        self.save_profile(Some(&name), &new_name, &config)?;
+                    }
+                }
+                KeyCode::Char('d') => {
+                    if let Some(name) = self.selected() {
+                        self.set_status(t!("manager.confirm_delete", name), Color::Red);
+                        self.pending_delete = Some(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn profile_config(&self, name: &str) -> Config {
+        let mut config = self.base.clone();
+        if let Some(profile) = self.connections.profiles.get(name) {
+            profile.apply_all(&mut config);
+        }
+        config.profile = Some(name.to_string());
+        config.unconfigured = false;
+        config
+    }
+
+    // 改名时移除旧条目；保留表单之外的字段（TLS、SSH 等）
+    fn save_profile(&mut self, old_name: Option<&str>, name: &str, config: &Config) -> Result<()> {
+        if old_name != Some(name) && self.connections.profiles.contains_key(name) {
+            self.set_status(t!("manager.exists", name), Color::Red);
+            return Ok(());
+        }
+        let mut profile = old_name
+            .and_then(|old| self.connections.profiles.remove(old))
+            .unwrap_or_default();
+        profile.update_from(config);
+        self.connections.profiles.insert(name.to_string(), profile);
+        self.connections.save()?;
+        self.refresh(Some(name));
+        self.set_status(t!("manager.saved", name), Color::Green);
+        Ok(())
+    }
+
+    // 最近使用的排在前面，从未使用的按名称排序
+    fn refresh(&mut self, select: Option<&str>) {
+        let mut names: Vec<String> = self.connections.profiles.keys().cloned().collect();
+        names.sort_by(|a, b| {
+            let (ta, tb) = (self.usage.last_used.get(a), self.usage.last_used.get(b));
+            tb.cmp(&ta).then_with(|| a.cmp(b))
+        });
+        self.names = names;
+        let index = select
+            .and_then(|name| self.names.iter().position(|n| n == name))
+            .or_else(|| self.state.selected())
+            .map(|i| i.min(self.names.len().saturating_sub(1)));
+        self.state.select(if self.names.is_empty() { None } else { Some(index.unwrap_or(0)) });
+    }
+
+    fn selected(&self) -> Option<String> {
+        self.state.selected().and_then(|i| self.names.get(i)).cloned()
+    }
+
+    fn select_offset(&mut self, delta: isize) {
+        if self.names.is_empty() {
+            return;
+        }
+        let len = self.names.len() as isize;
+        let current = self.state.selected().unwrap_or(0) as isize;
+        self.state.select(Some((current + delta).rem_euclid(len) as usize));
+    }
+
+    fn set_status(&mut self, message: String, color: Color) {
+        self.status = Some((message, color));
+    }
+
+    fn describe(profile: &Profile) -> String {
+        let driver = profile.driver.as_deref().unwrap_or("mysql");
+        if let Some(path) = profile.path.as_deref() {
+            return format!("{} {}", driver, path.display());
+        }
+        let mut target = format!(
+            "{}://{}@{}",
+            driver,
+            profile.username.as_deref().unwrap_or("root"),
+            profile.host.as_deref().unwrap_or("localhost")
+        );
+        if let Some(port) = profile.port {
+            target.push_str(&format!(":{}", port));
+        }
+        if let Some(db) = profile.database.as_deref() {
+            target.push_str(&format!("/{}", db));
+        }
+        if let Some(ssh) = profile.ssh.as_deref() {
+            target.push_str(&format!(" via {}", ssh));
+        }
+        target
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(frame.area());
+
+        let name_width = self.names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+        let items: Vec<ListItem> = self
+            .names
+            .iter()
+            .map(|name| {
+                let profile = &self.connections.profiles[name];
+                let used = self
+                    .usage
+                    .last_used
+                    .get(name)
+                    .map(|t| relative_to_now(t.naive_local()))
+                    .unwrap_or_else(|| t!("manager.never_used").to_string());
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<width$}  ", name, width = name_width), Style::default().bold()),
+                    Span::styled(format!("{:<12}", used), Style::default().fg(Color::DarkGray)),
+                    Span::raw(Self::describe(profile)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(t!("manager.title", Connections::path().display()))
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Green)),
+            )
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::Green).bold());
+        frame.render_stateful_widget(list, chunks[0], &mut self.state);
+
+        let footer = match &self.status {
+            Some((message, color)) => Line::from(Span::styled(message.clone(), Style::default().fg(*color))),
+            None => Line::from(Span::styled(
+                t!("manager.hint"),
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        frame.render_widget(
+            Paragraph::new(footer).block(Block::default().borders(Borders::ALL).style(Style::default().fg(Color::Green))),
+            chunks[1],
+        );
+    }
+}
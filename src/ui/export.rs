@@ -0,0 +1,446 @@
+use anyhow::{anyhow, Result};
+use arrow::array::{ArrayRef, BooleanBuilder, Date32Builder, Float64Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use chrono::{NaiveDate, NaiveDateTime};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rust_xlsxwriter::{Format, FormatAlign, Workbook};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::{db::DbAdapter, models::{ParamValue, SchemaColumn}, t};
+
+// 结果集导出格式（\export 与 e 键）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+    Json,
+    Markdown,
+    Xlsx,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub const NAMES: &'static [&'static str] = &["csv", "tsv", "json", "md", "xlsx", "parquet"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" => Some(Self::Tsv),
+            "json" => Some(Self::Json),
+            "md" | "markdown" => Some(Self::Markdown),
+            "xlsx" | "excel" => Some(Self::Xlsx),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    // 按文件扩展名推断格式
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|e| e.to_str()).and_then(Self::from_name)
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Json => "json",
+            Self::Markdown => "md",
+            Self::Xlsx => "xlsx",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    // 文本格式的内容；xlsx / parquet 为二进制，只能经 write_file 写入
+    pub fn render(self, headers: &[String], rows: &[Vec<String>]) -> Result<String> {
+        Ok(match self {
+            Self::Csv => format_csv(headers, rows),
+            Self::Tsv => format_tsv(headers, rows),
+            Self::Json => format_json(headers, rows)?,
+            Self::Markdown => format_markdown(headers, rows),
+            Self::Xlsx | Self::Parquet => return Err(anyhow!(t!("export.binary_format", self.extension()))),
+        })
+    }
+}
+
+// 解析 "\export [格式] 路径" 的参数；省略格式时按扩展名推断
+pub fn parse_args(args: &[String]) -> Result<(ExportFormat, &Path)> {
+    let usage = || anyhow!(t!("export.usage", ExportFormat::NAMES.join("|")));
+    match args {
+        [path] => {
+            let path = Path::new(path);
+            let format = ExportFormat::from_path(path)
+                .ok_or_else(|| anyhow!(t!("export.unknown_extension", ExportFormat::NAMES.join(" / "))))?;
+            Ok((format, path))
+        }
+        [format, path] => {
+            let format = ExportFormat::from_name(format)
+                .ok_or_else(|| anyhow!(t!("export.unknown_format", format, ExportFormat::NAMES.join(" / "))))?;
+            Ok((format, Path::new(path)))
+        }
+        _ => Err(usage()),
+    }
+}
+
+// 把结果集按格式写入文件（覆盖已有文件）。column_types 为各列的数据库类型（整表导出时取自表结构），
+// 为空时按值推断；目前只有 parquet 使用
+pub fn write_file(format: ExportFormat, path: &Path, headers: &[String], rows: &[Vec<String>], column_types: &[String]) -> Result<()> {
+    let binary = match format {
+        ExportFormat::Xlsx => Some(write_xlsx(path, headers, rows)),
+        ExportFormat::Parquet => Some(write_parquet(path, headers, rows, column_types)),
+        _ => None,
+    };
+    if let Some(result) = binary {
+        return result.map_err(|e| anyhow!(t!("export.write_failed", path.display(), e)));
+    }
+    let text = format.render(headers, rows)?;
+    std::fs::write(path, text).map_err(|e| anyhow!(t!("export.write_failed", path.display(), e)))
+}
+
+// RFC 4180：含分隔符、引号或换行的字段加双引号，引号写两次；NULL 为空字段，空字符串写作 ""
+pub fn format_csv(headers: &[String], rows: &[Vec<String>]) -> String {
+    let field = |v: &String| {
+        if v.is_empty() || v.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", v.replace('"', "\"\""))
+        } else if v == "NULL" {
+            String::new()
+        } else {
+            v.clone()
+        }
+    };
+    let mut out = format!("{}\n", headers.iter().map(field).collect::<Vec<_>>().join(","));
+    for row in rows {
+        out.push_str(&row.iter().map(field).collect::<Vec<_>>().join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// 同 MySQL / PostgreSQL 的文本格式：NULL 写作 \N，制表符、换行与反斜杠转义
+pub fn format_tsv(headers: &[String], rows: &[Vec<String>]) -> String {
+    let field = |v: &String| {
+        if v == "NULL" {
+            return "\\N".to_string();
+        }
+        v.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+    };
+    let mut out = format!("{}\n", headers.iter().map(field).collect::<Vec<_>>().join("\t"));
+    for row in rows {
+        out.push_str(&row.iter().map(field).collect::<Vec<_>>().join("\t"));
+        out.push('\n');
+    }
+    out
+}
+
+// 每个结果集输出一个对象数组，NULL 输出为 null，其余值均为字符串
+pub fn format_json(headers: &[String], rows: &[Vec<String>]) -> Result<String> {
+    let objects: Vec<serde_json::Map<String, serde_json::Value>> = rows
+        .iter()
+        .map(|row| {
+            headers
+                .iter()
+                .zip(row)
+                .map(|(header, value)| {
+                    let value = if value == "NULL" { serde_json::Value::Null } else { serde_json::Value::String(value.clone()) };
+                    (header.clone(), value)
+                })
+                .collect()
+        })
+        .collect();
+    Ok(format!("{}\n", serde_json::to_string_pretty(&objects)?))
+}
+
+// GitHub 风格表格：| 与换行转义
+pub fn format_markdown(headers: &[String], rows: &[Vec<String>]) -> String {
+    let line = |values: &[String]| {
+        let cells: Vec<String> = values.iter().map(|v| v.replace('|', "\\|").replace('\n', "<br>")).collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+    let mut out = line(headers);
+    out.push_str(&format!("|{}|\n", vec![" --- "; headers.len()].join("|")));
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    out
+}
+
+// 每条 INSERT 携带 rows_per_statement 行（复制到剪贴板时为 1，便于挑选）；表名可带库/schema 前缀（db.table），各段分别加引号。
+// schema 为表结构（按列名对应，\dump 时提供）：数值类型列的数字原样输出，其余列一律转义为字符串字面量，
+// NOT NULL 列中的 NULL 文本按字符串写出；没有对应结构时按值推断：数字原样输出，NULL 为 NULL
+pub fn format_inserts(
+    db: &dyn DbAdapter,
+    table: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+    rows_per_statement: usize,
+    schema: &[SchemaColumn],
+) -> String {
+    let table = db.quote_table(table);
+    let columns = headers.iter().map(|h| db.quote_ident(h)).collect::<Vec<_>>().join(", ");
+    let schema: Vec<Option<&SchemaColumn>> = headers.iter().map(|h| schema.iter().find(|c| c.name == *h)).collect();
+    let mut out = String::new();
+    for batch in rows.chunks(rows_per_statement.max(1)) {
+        let tuples: Vec<String> = batch
+            .iter()
+            .map(|row| {
+                let values: Vec<String> = row
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| insert_literal(db, v, schema.get(i).copied().flatten()))
+                    .collect();
+                format!("({})", values.join(", "))
+            })
+            .collect();
+        out.push_str(&format!("INSERT INTO {} ({}) VALUES {};\n", table, columns, tuples.join(",\n    ")));
+    }
+    out
+}
+
+fn insert_literal(db: &dyn DbAdapter, value: &str, column: Option<&SchemaColumn>) -> String {
+    let numeric = matches!(ParamValue::parse(value), ParamValue::Int(_) | ParamValue::Float(_)) && value.trim() == value;
+    match column {
+        Some(column) if value == "NULL" && column.is_nullable => "NULL".to_string(),
+        Some(column) if numeric && is_numeric_type(&column.data_type) => value.to_string(),
+        Some(_) => db.quote_literal(value),
+        None if value == "NULL" => "NULL".to_string(),
+        None if numeric => value.to_string(),
+        None => db.quote_literal(value),
+    }
+}
+
+// 整数、浮点与定点数类型（decimal 在 parquet 中按文本处理，这里仍按数值写出）
+fn is_numeric_type(type_name: &str) -> bool {
+    let t = type_name.to_ascii_lowercase();
+    matches!(ColumnKind::from_type_name(&t), Some(ColumnKind::Int | ColumnKind::Float))
+        || ["decimal", "numeric", "number"].iter().any(|n| t.starts_with(n))
+}
+
+// Excel 单个工作表的行数上限（含表头）与单元格字符数上限
+const XLSX_MAX_ROWS: usize = 1_048_576;
+const XLSX_MAX_CHARS: usize = 32_767;
+// 超过 15 位有效数字的整数按文本写入，避免 Excel 丢失精度（如雪花 ID）
+const XLSX_MAX_EXACT_INT: i64 = 999_999_999_999_999;
+
+// 表头加粗并冻结首行；数字写为数值，日期/时间写为日期单元格，NULL 留空，其余为文本；按内容自动列宽
+fn write_xlsx(path: &Path, headers: &[String], rows: &[Vec<String>]) -> Result<()> {
+    if rows.len() >= XLSX_MAX_ROWS {
+        return Err(anyhow!(t!("export.xlsx_too_many_rows", rows.len(), XLSX_MAX_ROWS - 1)));
+    }
+    let header_format = Format::new().set_bold();
+    let datetime_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss").set_align(FormatAlign::Left);
+    let date_format = Format::new().set_num_format("yyyy-mm-dd").set_align(FormatAlign::Left);
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, header, &header_format)?;
+    }
+    for (index, row) in rows.iter().enumerate() {
+        let r = index as u32 + 1;
+        for (col, value) in row.iter().enumerate() {
+            let c = col as u16;
+            if value == "NULL" {
+                continue;
+            }
+            match ParamValue::parse(value) {
+                ParamValue::Int(v) if v.abs() <= XLSX_MAX_EXACT_INT => {
+                    worksheet.write_number(r, c, v as f64)?;
+                    continue;
+                }
+                ParamValue::Float(v) if v.is_finite() => {
+                    worksheet.write_number(r, c, v)?;
+                    continue;
+                }
+                _ => {}
+            }
+            if let Some(dt) = parse_datetime(value) {
+                worksheet.write_datetime_with_format(r, c, dt, &datetime_format)?;
+            } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                worksheet.write_datetime_with_format(r, c, date, &date_format)?;
+            } else {
+                let text: String = value.chars().take(XLSX_MAX_CHARS).collect();
+                worksheet.write_string(r, c, &text)?;
+            }
+        }
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+    worksheet.autofit();
+    workbook.save(path)?;
+    Ok(())
+}
+
+// parquet 列类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Int,
+    Float,
+    Bool,
+    Date,
+    Timestamp,
+    Text,
+}
+
+impl ColumnKind {
+    // 由数据库类型名映射；无法识别的类型返回 None，改为按值推断
+    fn from_type_name(type_name: &str) -> Option<Self> {
+        let t = type_name.to_ascii_lowercase();
+        let base = t.split(['(', ' ']).next().unwrap_or("");
+        if base.starts_with("bool") {
+            Some(Self::Bool)
+        } else if base.contains("int") && !base.contains("interval") && !base.contains("point") {
+            Some(Self::Int)
+        } else if ["float", "double", "real"].iter().any(|f| base.starts_with(f)) {
+            Some(Self::Float)
+        } else if base == "date" {
+            Some(Self::Date)
+        } else if base.starts_with("datetime") || base.starts_with("timestamp") {
+            Some(Self::Timestamp)
+        } else if ["char", "varchar", "text", "string", "uuid", "json", "decimal", "numeric"].iter().any(|s| base.contains(s)) {
+            // decimal 保留为文本，避免转为浮点丢失精度
+            Some(Self::Text)
+        } else {
+            None
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Int => DataType::Int64,
+            Self::Float => DataType::Float64,
+            Self::Bool => DataType::Boolean,
+            Self::Date => DataType::Date32,
+            Self::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+            Self::Text => DataType::Utf8,
+        }
+    }
+
+    // 该类型能否无损表示这个值
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::Int => parse_int(value).is_some(),
+            Self::Float => parse_float(value).is_some(),
+            Self::Bool => parse_bool(value).is_some(),
+            Self::Date => parse_date(value).is_some(),
+            Self::Timestamp => parse_datetime(value).is_some(),
+            Self::Text => true,
+        }
+    }
+}
+
+fn parse_int(value: &str) -> Option<i64> {
+    match ParamValue::parse(value) {
+        ParamValue::Int(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+    match ParamValue::parse(value) {
+        ParamValue::Int(v) => Some(v as f64),
+        ParamValue::Float(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "t" | "true" => Some(true),
+        "0" | "f" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+// 日期时间：带时区偏移的值（如 PostgreSQL timestamptz）换算为 UTC
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        .or_else(|| {
+            ["%Y-%m-%d %H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M:%S%.f%#z"]
+                .iter()
+                .find_map(|f| chrono::DateTime::parse_from_str(value, f).ok())
+                .map(|dt| dt.naive_utc())
+        })
+}
+
+// 确定列类型：优先采用表结构中的类型，否则按值推断；有值不符合时退回文本
+fn column_kind(rows: &[Vec<String>], col: usize, type_name: Option<&str>) -> ColumnKind {
+    let mut values = rows.iter().filter_map(|row| row.get(col).map(String::as_str)).filter(|v| *v != "NULL");
+    if let Some(kind) = type_name.and_then(ColumnKind::from_type_name) {
+        return if values.all(|v| kind.accepts(v)) { kind } else { ColumnKind::Text };
+    }
+    let values: Vec<&str> = values.collect();
+    if values.is_empty() {
+        return ColumnKind::Text;
+    }
+    [ColumnKind::Int, ColumnKind::Float, ColumnKind::Timestamp, ColumnKind::Date]
+        .into_iter()
+        .find(|kind| values.iter().all(|v| kind.accepts(v)))
+        .unwrap_or(ColumnKind::Text)
+}
+
+fn build_column(rows: &[Vec<String>], col: usize, kind: ColumnKind) -> ArrayRef {
+    let values = rows.iter().map(|row| row.get(col).map(String::as_str).filter(|v| *v != "NULL"));
+    match kind {
+        ColumnKind::Int => {
+            let mut builder = Int64Builder::with_capacity(rows.len());
+            values.for_each(|v| builder.append_option(v.and_then(parse_int)));
+            Arc::new(builder.finish())
+        }
+        ColumnKind::Float => {
+            let mut builder = Float64Builder::with_capacity(rows.len());
+            values.for_each(|v| builder.append_option(v.and_then(parse_float)));
+            Arc::new(builder.finish())
+        }
+        ColumnKind::Bool => {
+            let mut builder = BooleanBuilder::with_capacity(rows.len());
+            values.for_each(|v| builder.append_option(v.and_then(parse_bool)));
+            Arc::new(builder.finish())
+        }
+        ColumnKind::Date => {
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("有效日期");
+            let mut builder = Date32Builder::with_capacity(rows.len());
+            values.for_each(|v| builder.append_option(v.and_then(parse_date).map(|d| (d - epoch).num_days() as i32)));
+            Arc::new(builder.finish())
+        }
+        ColumnKind::Timestamp => {
+            let mut builder = TimestampMicrosecondBuilder::with_capacity(rows.len());
+            values.for_each(|v| builder.append_option(v.and_then(parse_datetime).map(|dt| dt.and_utc().timestamp_micros())));
+            Arc::new(builder.finish())
+        }
+        ColumnKind::Text => {
+            let mut builder = StringBuilder::with_capacity(rows.len(), rows.len() * 16);
+            values.for_each(|v| builder.append_option(v));
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+// 单个 row group、snappy 压缩；所有列均可为空
+fn write_parquet(path: &Path, headers: &[String], rows: &[Vec<String>], column_types: &[String]) -> Result<()> {
+    let kinds: Vec<ColumnKind> = (0..headers.len())
+        .map(|col| column_kind(rows, col, column_types.get(col).map(String::as_str)))
+        .collect();
+    let fields: Vec<Field> = headers
+        .iter()
+        .zip(&kinds)
+        .map(|(name, kind)| Field::new(name, kind.data_type(), true))
+        .collect();
+    let schema = Arc::new(Schema::new(fields));
+    let columns: Vec<ArrayRef> = kinds.iter().enumerate().map(|(col, kind)| build_column(rows, col, *kind)).collect();
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+
+    let props = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let file = std::fs::File::create(path)?;
+    let mut writer = ArrowWriter::try_new(file, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use anyhow::Result;
+use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
+use std::fs;
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{config::Config, db::new_adapter, ui::components::Content};
+
+// 单元格像素尺寸（SVG 输出）
+const CELL_W: usize = 9;
+const CELL_H: usize = 18;
+// 最多渲染的行数：整张表格画在一个缓冲区中，行数过多时内存占用过大（且高度受 u16 限制）
+const MAX_RENDER_ROWS: usize = 10_000;
+
+// 不进入交互界面，执行查询并用 TUI 相同的表格渲染器输出到文件（.svg 或文本）。
+// 超过 MAX_RENDER_ROWS 的行不输出；返回 (渲染的行数, 结果总行数)
+pub async fn render_query_to_file(config: &Config, query: &str, out: &Path, color: bool) -> Result<(usize, usize)> {
+    let db = new_adapter(config).await?;
+    let (headers, mut rows) = db.execute_query_raw(query).await?;
+    let total = rows.len();
+    rows.truncate(MAX_RENDER_ROWS);
+    let row_count = rows.len();
+
+    let mut content = Content::new();
+    // 每列固定 15 宽度 + 1 间隔，保证所有列都能完整输出
+    let width = (headers.len() * 16 + 2).clamp(40, u16::MAX as usize) as u16;
+    let height = (row_count + 3) as u16;
+    content.set_table_data(headers, rows);
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|f| content.render(f, f.area()))?;
+    let buffer = terminal.backend().buffer().clone();
+
+    let is_svg = out.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("svg"));
+    let output = if is_svg { buffer_to_svg(&buffer, color) } else { buffer_to_text(&buffer, color) };
+    fs::write(out, output)?;
+    Ok((row_count, total))
+}
+
+// 按行遍历缓冲区，跳过宽字符占用的后续单元格
+fn visible_cells(buffer: &Buffer, y: u16) -> Vec<(u16, &ratatui::buffer::Cell)> {
+    let mut cells = Vec::new();
+    let mut skip = 0usize;
+    for x in 0..buffer.area.width {
+        let cell = &buffer[(x, y)];
+        if skip == 0 {
+            cells.push((x, cell));
+        }
+        skip = std::cmp::max(skip, cell.symbol().width()).saturating_sub(1);
+    }
+    cells
+}
+
+fn buffer_to_text(buffer: &Buffer, color: bool) -> String {
+    let mut out = String::new();
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        let mut current: Option<Color> = None;
+        for (_, cell) in visible_cells(buffer, y) {
+            if color && current != Some(cell.fg) {
+                line.push_str(ansi_fg(cell.fg));
+                current = Some(cell.fg);
+            }
+            line.push_str(cell.symbol());
+        }
+        if color { line.push_str("\x1b[0m"); }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn buffer_to_svg(buffer: &Buffer, color: bool) -> String {
+    let width = buffer.area.width as usize * CELL_W;
+    let height = buffer.area.height as usize * CELL_H;
+    let background = if color { "#101010" } else { "#ffffff" };
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{bg}\"/>\n\
+         <g font-family=\"monospace\" font-size=\"14\" xml:space=\"preserve\">\n",
+        w = width, h = height, bg = background,
+    );
+    for y in 0..buffer.area.height {
+        for (x, cell) in visible_cells(buffer, y) {
+            let symbol = cell.symbol();
+            if symbol.trim().is_empty() { continue; }
+            let fill = if color { svg_color(cell.fg) } else { "#000000" };
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>\n",
+                x as usize * CELL_W,
+                (y as usize + 1) * CELL_H - 4,
+                fill,
+                escape_xml(symbol),
+            ));
+        }
+    }
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn ansi_fg(color: Color) -> &'static str {
+    match color {
+        Color::Black => "\x1b[30m",
+        Color::Red => "\x1b[31m",
+        Color::Green => "\x1b[32m",
+        Color::Yellow => "\x1b[33m",
+        Color::Blue => "\x1b[34m",
+        Color::Magenta => "\x1b[35m",
+        Color::Cyan => "\x1b[36m",
+        Color::Gray => "\x1b[37m",
+        Color::DarkGray => "\x1b[90m",
+        Color::White => "\x1b[97m",
+        _ => "\x1b[39m",
+    }
+}
+
+fn svg_color(color: Color) -> &'static str {
+    match color {
+        Color::Black => "#000000",
+        Color::Red => "#e05555",
+        Color::Green => "#4ec94e",
+        Color::Yellow => "#e5c07b",
+        Color::Blue => "#61afef",
+        Color::Magenta => "#c678dd",
+        Color::Cyan => "#56b6c2",
+        Color::Gray => "#a0a0a0",
+        Color::DarkGray => "#606060",
+        Color::White => "#ffffff",
+        _ => "#d0d0d0",
+    }
+}
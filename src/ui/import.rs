@@ -0,0 +1,192 @@
+use std::sync::Arc;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::task::JoinHandle;
+
+use crate::db::DbAdapter;
+
+// 解析分隔符文本（RFC 4180）：字段可加双引号，引号内可含分隔符与换行，"" 表示一个引号。
+// 返回每条记录及其起始行号（从 1 开始），跳过空行
+pub fn parse_delimited(text: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+    records
+}
+
+// 一次导入任务：目标表、写入的列与各行（行号, 值）；值为 None 时写入 NULL
+pub struct ImportJob {
+    pub table: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<(usize, Vec<Option<String>>)>,
+}
+
+// 导入结果：成功行数、失败的行（行号, 错误）与首次 COPY 失败的原因（之后改用 INSERT）
+#[derive(Default)]
+pub struct ImportReport {
+    pub inserted: usize,
+    pub errors: Vec<(usize, String)>,
+    pub copy_error: Option<String>,
+}
+
+enum ImportEvent {
+    Progress(usize),
+    Finished(ImportReport),
+}
+
+// 后台执行中的导入；按批写入，每批完成后上报进度
+pub struct RunningImport {
+    pub table: String,
+    pub total: usize,
+    pub done: usize,
+    rx: UnboundedReceiver<ImportEvent>,
+    task: JoinHandle<()>,
+}
+
+impl RunningImport {
+    pub fn spawn(db: Arc<dyn DbAdapter>, job: ImportJob) -> Self {
+        let (tx, rx) = unbounded_channel();
+        let table = job.table.clone();
+        let total = job.rows.len();
+        let task = tokio::spawn(async move {
+            let report = run_job(db.as_ref(), &job, |done| {
+                let _ = tx.send(ImportEvent::Progress(done));
+            })
+            .await;
+            let _ = tx.send(ImportEvent::Finished(report));
+        });
+        Self { table, total, done: 0, rx, task }
+    }
+
+    // 收取进度；完成时返回报告
+    pub fn poll(&mut self) -> Option<ImportReport> {
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                ImportEvent::Progress(done) => self.done = done,
+                ImportEvent::Finished(report) => return Some(report),
+            }
+        }
+        None
+    }
+
+    // 停止导入；已提交的批次不会回滚
+    pub fn cancel(self) {
+        self.task.abort();
+    }
+}
+
+// 支持 COPY 的驱动每次 COPY 的行数
+const COPY_CHUNK_ROWS: usize = 10_000;
+
+// 支持 COPY 时按块整体 COPY；不支持或某块失败时改用多行 INSERT，逐批上报进度
+async fn run_job(db: &dyn DbAdapter, job: &ImportJob, progress: impl Fn(usize)) -> ImportReport {
+    let mut report = ImportReport::default();
+    let mut done = 0;
+    let chunk_rows = if db.supports_copy() { COPY_CHUNK_ROWS } else { db.max_insert_rows().max(1) };
+    for chunk in job.rows.chunks(chunk_rows) {
+        // COPY 整块成功或整块失败，失败后用 INSERT 重做不会重复写入；失败原因保留在报告中
+        let copied = db.supports_copy()
+            && match db.copy_in_csv(&job.table, &job.columns, copy_data(chunk)).await {
+                Ok(_) => true,
+                Err(e) => {
+                    report.copy_error.get_or_insert_with(|| e.to_string());
+                    false
+                }
+            };
+        if copied {
+            report.inserted += chunk.len();
+        } else {
+            insert_rows(db, job, chunk, &mut report).await;
+        }
+        done += chunk.len();
+        progress(done);
+    }
+    report
+}
+
+// 每批一条多行 INSERT；某批失败时逐行重试，定位出错的行并继续导入其余行
+async fn insert_rows(db: &dyn DbAdapter, job: &ImportJob, rows: &[(usize, Vec<Option<String>>)], report: &mut ImportReport) {
+    for batch in rows.chunks(db.max_insert_rows().max(1)) {
+        if db.execute_non_query(&insert_sql(db, job, batch)).await.is_ok() {
+            report.inserted += batch.len();
+            continue;
+        }
+        for row in batch {
+            match db.execute_non_query(&insert_sql(db, job, std::slice::from_ref(row))).await {
+                Ok(_) => report.inserted += 1,
+                Err(e) => report.errors.push((row.0, e.to_string())),
+            }
+        }
+    }
+}
+
+// COPY ... FROM STDIN (FORMAT csv) 的数据：值一律加引号，不加引号的空字段表示 NULL
+fn copy_data(rows: &[(usize, Vec<Option<String>>)]) -> Vec<u8> {
+    let mut out = String::new();
+    for (_, row) in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|v| v.as_deref().map_or_else(String::new, |v| format!("\"{}\"", v.replace('"', "\"\""))))
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out.into_bytes()
+}
+
+// 值一律按字符串字面量写入，由数据库按列类型转换，避免客户端猜测类型
+fn insert_sql(db: &dyn DbAdapter, job: &ImportJob, rows: &[(usize, Vec<Option<String>>)]) -> String {
+    let table = db.quote_table(&job.table);
+    let columns = job.columns.iter().map(|c| db.quote_ident(c)).collect::<Vec<_>>().join(", ");
+    let values: Vec<String> = rows
+        .iter()
+        .map(|(_, row)| {
+            let values: Vec<String> = row
+                .iter()
+                .map(|v| v.as_deref().map_or_else(|| "NULL".to_string(), |v| db.quote_literal(v)))
+                .collect();
+            format!("({})", values.join(", "))
+        })
+        .collect();
+    format!("INSERT INTO {} ({}) VALUES {}", table, columns, values.join(", "))
+}
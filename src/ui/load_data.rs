@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::{config::Config, db::DbAdapter, t};
+
+// LOAD DATA LOCAL INFILE 的文件格式，按扩展名与首行推断
+pub struct LoadDataPlan {
+    pub path: PathBuf,
+    pub table: String,
+    pub delimiter: char,
+    // 首行为列名时跳过
+    pub header: bool,
+    // 按 \N 表示 NULL、反斜杠转义（MySQL 导出与 \export tsv 的格式）；CSV 则不转义
+    pub backslash_escapes: bool,
+    pub crlf: bool,
+}
+
+impl LoadDataPlan {
+    pub fn detect(path: &Path, table: &str, no_header: bool) -> Result<Self> {
+        let path = std::fs::canonicalize(path).map_err(|e| anyhow!(t!("loaddata.file_missing", path.display(), e)))?;
+        let mut head = vec![0u8; 64 * 1024];
+        let read = std::io::Read::read(&mut std::fs::File::open(&path)?, &mut head)?;
+        let head = String::from_utf8_lossy(&head[..read]).into_owned();
+        let first_line = head.lines().next().unwrap_or("");
+        let tsv = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("tsv"))
+            || first_line.matches('\t').count() > first_line.matches(',').count();
+        Ok(Self {
+            table: table.to_string(),
+            delimiter: if tsv { '\t' } else if first_line.matches(';').count() > first_line.matches(',').count() { ';' } else { ',' },
+            header: !no_header,
+            backslash_escapes: tsv,
+            crlf: head.contains("\r\n"),
+            path,
+        })
+    }
+
+    // 生成的语句；字符集与连接一致，避免中文等多字节字符被按 latin1 解释
+    pub fn statement(&self, db: &dyn DbAdapter, charset: &str) -> String {
+        let escape = |c: char| match c {
+            '\t' => "\\t".to_string(),
+            c => c.to_string(),
+        };
+        let mut sql = format!(
+            "LOAD DATA LOCAL INFILE {} INTO TABLE {} CHARACTER SET {} FIELDS TERMINATED BY '{}'",
+            db.quote_literal(&self.path.display().to_string()),
+            db.quote_table(&self.table),
+            charset,
+            escape(self.delimiter),
+        );
+        if self.backslash_escapes {
+            sql.push_str(" ESCAPED BY '\\\\'");
+        } else {
+            sql.push_str(" OPTIONALLY ENCLOSED BY '\"' ESCAPED BY ''");
+        }
+        sql.push_str(if self.crlf { " LINES TERMINATED BY '\\r\\n'" } else { " LINES TERMINATED BY '\\n'" });
+        if self.header {
+            sql.push_str(" IGNORE 1 LINES");
+        }
+        sql
+    }
+}
+
+// 服务端需开启 local_infile 才接受客户端文件
+pub async fn check_server(db: &dyn DbAdapter) -> Result<()> {
+    let (_, rows) = db.execute_query_raw("SELECT @@GLOBAL.local_infile").await?;
+    match rows.first().and_then(|r| r.first()).map(String::as_str) {
+        Some("1") | Some("ON") => Ok(()),
+        _ => Err(anyhow!(t!("loaddata.local_infile_off"))),
+    }
+}
+
+// sqlx 不支持 LOCAL INFILE 协议，交给 mysql 命令行客户端执行（连接参数与当前会话一致，
+// 密码经 MYSQL_PWD 传递），返回导入的行数与警告数
+pub async fn run(config: &Config, database: Option<&str>, statement: &str) -> Result<String> {
+    let mut cmd = Command::new("mysql");
+    cmd.arg("--local-infile=1")
+        .arg("--batch")
+        .arg("--skip-column-names")
+        .arg(format!("--user={}", config.username))
+        .arg(format!("--default-character-set={}", config.charset));
+    match config.socket.as_deref() {
+        Some(socket) => cmd.arg(format!("--socket={}", socket.display())),
+        None => cmd.arg(format!("--host={}", config.host)).arg(format!("--port={}", config.port())),
+    };
+    if let Some(mode) = config.ssl_mode.as_deref() {
+        let mode = match mode {
+            "disable" => "DISABLED",
+            "prefer" => "PREFERRED",
+            "require" => "REQUIRED",
+            "verify-ca" => "VERIFY_CA",
+            _ => "VERIFY_IDENTITY",
+        };
+        cmd.arg(format!("--ssl-mode={}", mode));
+    }
+    for (flag, file) in [("--ssl-ca", &config.ssl_ca), ("--ssl-cert", &config.ssl_cert), ("--ssl-key", &config.ssl_key)] {
+        if let Some(file) = file {
+            cmd.arg(format!("{}={}", flag, file.display()));
+        }
+    }
+    if let Some(database) = database {
+        cmd.arg(format!("--database={}", database));
+    }
+    cmd.arg("-e").arg(format!("{}; SELECT ROW_COUNT(), @@warning_count", statement));
+    if !config.password.is_empty() {
+        cmd.env("MYSQL_PWD", &config.password);
+    }
+    let output = cmd
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| anyhow!(t!("loaddata.client_missing", e)))?;
+    if !output.status.success() {
+        return Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut counts = stdout.split_whitespace();
+    match (counts.next(), counts.next()) {
+        (Some(rows), Some("0")) | (Some(rows), None) => Ok(t!("loaddata.done", rows)),
+        (Some(rows), Some(warnings)) => Ok(t!("loaddata.done_warnings", rows, warnings)),
+        _ => Ok(t!("loaddata.finished").to_string()),
+    }
+}
@@ -0,0 +1,43 @@
+// 反斜杠元命令（\replace、\export 等）的参数解析
+
+// 按空白切分参数，支持单/双引号包裹含空格的参数
+pub fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut has_token = false;
+    for ch in input.chars() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '\'' || ch == '"' => {
+                quote = Some(ch);
+                has_token = true;
+            }
+            None if ch.is_whitespace() => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    args
+}
+
+// 若输入以指定元命令开头（后接空白或结束），返回其后的参数部分
+pub fn strip_command<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    let rest = input.trim().strip_prefix(name)?;
+    if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
@@ -1,4 +1,21 @@
+pub mod actions;
 pub mod app;
+pub mod batch;
+pub mod bench;
+pub mod clipboard;
 pub mod components;
+pub mod connect_form;
+pub mod connection_manager;
+pub mod export;
+pub mod headless;
+pub mod import;
+pub mod load_data;
+pub mod meta_command;
+pub mod query_task;
+pub mod terminal;
+pub mod theme;
+pub mod xquery;
 
 pub use app::App;
+pub use connect_form::ConnectForm;
+pub use connection_manager::ConnectionManager;
@@ -0,0 +1,235 @@
+use anyhow::{anyhow, Result};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, error::TryRecvError, Receiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use crate::db::{DbAdapter, RowBatch};
+use crate::models::{ExecResult, ParamValue};
+use crate::sql;
+use crate::t;
+
+// 后台语句的执行结果：查询类返回首批结果（及尚未取回的剩余行），其余返回受影响行数
+pub enum QueryOutcome {
+    Rows(Result<(Vec<String>, Vec<Vec<String>>)>, Option<ResultStream>),
+    Affected(Result<ExecResult>),
+}
+
+impl QueryOutcome {
+    pub fn error(&self) -> Option<&anyhow::Error> {
+        match self {
+            QueryOutcome::Rows(Err(e), _) | QueryOutcome::Affected(Err(e)) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// 流式查询中尚未取回的行；丢弃时中止拉取，释放连接
+pub struct ResultStream {
+    rx: Receiver<RowBatch>,
+    producer: Option<JoinHandle<Result<()>>>,
+}
+
+// 非阻塞地取下一批行的结果
+pub enum StreamPoll {
+    Pending,
+    Rows(Vec<Vec<String>>),
+    Done(Result<()>),
+}
+
+impl ResultStream {
+    // 语句超时限制整条语句（含之后逐批拉取的剩余行），与服务端的语句超时一致；
+    // 到时中止拉取，已取回的行保留，剩余行以超时错误结束
+    fn start(db: Arc<dyn DbAdapter>, query: String, timeout: Option<u64>) -> Self {
+        // 容量 1：界面不取时驱动最多多读一批
+        let (tx, rx) = mpsc::channel(1);
+        let producer = tokio::spawn(async move { with_timeout(timeout, db.stream_query(&query, tx)).await });
+        Self { rx, producer: Some(producer) }
+    }
+
+    // 等待列名与首批行；结果不足一批或已取完时不再返回流
+    async fn first_page(mut self) -> Result<(Vec<String>, Vec<Vec<String>>, Option<Self>)> {
+        let mut headers = Vec::new();
+        loop {
+            match self.rx.recv().await {
+                Some(RowBatch::Columns(columns)) => headers = columns,
+                Some(RowBatch::Rows(rows)) => return Ok((headers, rows, Some(self))),
+                None => {
+                    self.finish().await?;
+                    return Ok((headers, Vec::new(), None));
+                }
+            }
+        }
+    }
+
+    pub async fn poll(&mut self) -> StreamPoll {
+        match self.rx.try_recv() {
+            Ok(RowBatch::Rows(rows)) => StreamPoll::Rows(rows),
+            Ok(RowBatch::Columns(_)) | Err(TryRecvError::Empty) => StreamPoll::Pending,
+            Err(TryRecvError::Disconnected) => StreamPoll::Done(self.finish().await),
+        }
+    }
+
+    async fn finish(&mut self) -> Result<()> {
+        match self.producer.take() {
+            Some(producer) => producer.await.map_err(|e| anyhow!(t!("query.task_failed", e)))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ResultStream {
+    fn drop(&mut self) {
+        if let Some(producer) = &self.producer {
+            producer.abort();
+        }
+    }
+}
+
+// 待执行的语句；params 非空时 sql 中的占位符已改写为驱动的写法，按位置绑定
+pub struct Statement {
+    pub sql: String,
+    pub params: Vec<ParamValue>,
+}
+
+impl Statement {
+    pub fn plain(sql: String) -> Self {
+        Self { sql, params: Vec::new() }
+    }
+}
+
+// 正在后台执行的语句；id 用于丢弃已取消任务的迟到结果
+pub struct RunningQuery {
+    pub id: u64,
+    pub sql: String,
+    pub params: Vec<ParamValue>,
+    pub vertical: bool,
+    pub started: Instant,
+    // 已因连接断开重试过一次
+    pub retried: bool,
+    // 自动追加的行数限制（未写 LIMIT 的 SELECT）
+    pub auto_limit: Option<usize>,
+    task: JoinHandle<()>,
+}
+
+impl RunningQuery {
+    // 在 tokio 任务中执行语句，完成后把 (id, 结果) 发回界面循环；
+    // default_limit 为未写 LIMIT 的 SELECT 自动追加的行数限制
+    pub fn spawn(
+        id: u64,
+        db: Arc<dyn DbAdapter>,
+        statement: Statement,
+        vertical: bool,
+        timeout: Option<u64>,
+        default_limit: Option<usize>,
+        tx: UnboundedSender<(u64, QueryOutcome)>,
+    ) -> Self {
+        let Statement { sql: statement, params } = statement;
+        let limited = default_limit.and_then(|limit| {
+            let suffix = db.limit_suffix(limit)?;
+            sql::with_default_limit(&statement, &suffix, db.dialect()).map(|query| (query, limit))
+        });
+        let auto_limit = limited.as_ref().map(|(_, limit)| *limit);
+        let query = limited.map_or_else(|| statement.clone(), |(query, _)| query);
+        let bound = params.clone();
+        let task = tokio::spawn(async move {
+            let returns_rows = sql::returns_rows(&sql::first_keyword(&query, db.dialect()));
+            let outcome = if !bound.is_empty() {
+                // 绑定参数的语句一次取回全部结果
+                if returns_rows {
+                    QueryOutcome::Rows(with_timeout(timeout, db.query_with_params(&query, &bound)).await, None)
+                } else {
+                    QueryOutcome::Affected(with_timeout(timeout, db.execute_with_params(&query, &bound)).await)
+                }
+            } else if returns_rows {
+                // 首批结果之后按界面滚动逐批拉取；超时由拉取任务限制
+                let stream = ResultStream::start(db, query, timeout);
+                match stream.first_page().await {
+                    Ok((headers, rows, rest)) => QueryOutcome::Rows(Ok((headers, rows)), rest),
+                    Err(e) => QueryOutcome::Rows(Err(e), None),
+                }
+            } else {
+                QueryOutcome::Affected(with_timeout(timeout, db.execute_non_query(&query)).await)
+            };
+            let _ = tx.send((id, outcome));
+        });
+        Self { id, sql: statement, params, vertical, started: Instant::now(), retried: false, auto_limit, task }
+    }
+
+    // 停止等待结果；服务端的语句由调用方通过 DbAdapter::cancel_running 中止，不支持的驱动可能仍在执行
+    pub fn cancel(self) {
+        self.task.abort();
+    }
+}
+
+// 超时后客户端额外等待的时间，优先让服务端中止语句并返回自己的错误
+const TIMEOUT_GRACE: Duration = Duration::from_secs(2);
+
+// 按语句超时执行：服务端中止的错误改写为统一提示；不支持服务端超时的驱动由客户端放弃等待
+pub async fn with_timeout<T>(timeout: Option<u64>, fut: impl Future<Output = Result<T>>) -> Result<T> {
+    let Some(secs) = timeout else {
+        return fut.await;
+    };
+    match tokio::time::timeout(Duration::from_secs(secs) + TIMEOUT_GRACE, fut).await {
+        Ok(Err(e)) if sql::is_timeout_error(&e.to_string()) => {
+            Err(anyhow!(t!("query.timeout_server", secs)))
+        }
+        Ok(result) => result,
+        Err(_) => Err(anyhow!(t!("query.timeout_client", secs))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Database, SchemaColumn, Table};
+    use async_trait::async_trait;
+    use tokio::sync::mpsc::Sender;
+
+    // 先返回一批行，之后一直不结束的查询
+    struct StallingDb;
+
+    #[async_trait]
+    impl DbAdapter for StallingDb {
+        fn driver_name(&self) -> &'static str { "test" }
+        fn keywords(&self) -> &'static [&'static str] { &[] }
+        fn system_databases(&self) -> &'static [&'static str] { &[] }
+        async fn get_databases(&self) -> Result<Vec<Database>> { Ok(Vec::new()) }
+        async fn get_tables(&self, _database_name: &str) -> Result<Vec<Table>> { Ok(Vec::new()) }
+        async fn get_table_schema(&self, _database_name: &str, _table_name: &str) -> Result<(Vec<SchemaColumn>, Option<String>)> {
+            Ok((Vec::new(), None))
+        }
+        async fn execute_query_raw(&self, _query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+            Ok((Vec::new(), Vec::new()))
+        }
+        async fn stream_query(&self, _query: &str, tx: Sender<RowBatch>) -> Result<()> {
+            let _ = tx.send(RowBatch::Columns(vec!["n".to_string()])).await;
+            let _ = tx.send(RowBatch::Rows(vec![vec!["1".to_string()]])).await;
+            std::future::pending().await
+        }
+        async fn execute_non_query(&self, _query: &str) -> Result<ExecResult> { Ok(ExecResult::affected(0)) }
+        async fn get_version(&self) -> Result<String> { Ok(String::new()) }
+        async fn get_current_user(&self) -> Result<String> { Ok(String::new()) }
+    }
+
+    #[tokio::test]
+    async fn timeout_covers_rows_fetched_after_the_first_page() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let _running = RunningQuery::spawn(1, Arc::new(StallingDb), Statement::plain("SELECT n".to_string()), false, Some(1), None, tx);
+        let (_, outcome) = rx.recv().await.unwrap();
+        let QueryOutcome::Rows(Ok((headers, rows)), Some(mut rest)) = outcome else { panic!("首批结果应立即返回") };
+        assert_eq!(headers, vec!["n".to_string()]);
+        assert_eq!(rows.len(), 1);
+
+        let started = Instant::now();
+        let result = loop {
+            match rest.poll().await {
+                StreamPoll::Done(result) => break result,
+                _ => tokio::time::sleep(Duration::from_millis(50)).await,
+            }
+        };
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(1) + TIMEOUT_GRACE + Duration::from_secs(1));
+    }
+}
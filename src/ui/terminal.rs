@@ -0,0 +1,114 @@
+use anyhow::Result;
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::{Backend, CrosstermBackend}, Terminal};
+use std::io::{self, Stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 终端是否处于 raw mode + 备用屏幕（保证恢复逻辑只执行一次）
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+// 任意线程/任务发生过 panic（主循环据此退出）
+static PANICKED: AtomicBool = AtomicBool::new(false);
+// 是否开启了鼠标捕获（挂起与恢复终端时据此关闭/重新开启）
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+// 持有终端的 RAII 守卫：创建时进入 raw mode 与备用屏幕，Drop 时恢复
+pub struct TerminalGuard {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl TerminalGuard {
+    pub fn new() -> Result<Self> {
+        enable_raw_mode()?;
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        let mut stdout = io::stdout();
+        if let Err(e) = execute!(stdout, EnterAlternateScreen) {
+            restore_terminal();
+            return Err(e.into());
+        }
+        let terminal = match Terminal::new(CrosstermBackend::new(stdout)) {
+            Ok(t) => t,
+            Err(e) => {
+                restore_terminal();
+                return Err(e.into());
+            }
+        };
+        Ok(Self { terminal })
+    }
+
+    pub fn terminal_mut(&mut self) -> &mut Terminal<CrosstermBackend<Stdout>> {
+        &mut self.terminal
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = self.terminal.show_cursor();
+        restore_terminal();
+    }
+}
+
+// 开启鼠标捕获：点击、滚轮与拖动作为事件送入程序（终端自身的鼠标选择随之失效）
+pub fn enable_mouse_capture() -> Result<()> {
+    execute!(io::stdout(), EnableMouseCapture)?;
+    MOUSE_CAPTURE.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+// 暂时交还终端（如运行外部编辑器）：离开备用屏幕并关闭 raw mode，f 返回后恢复并整屏重绘
+pub fn suspend<B: Backend, T>(terminal: &mut Terminal<B>, f: impl FnOnce() -> T) -> Result<T> {
+    let mut stdout = io::stdout();
+    let mouse = MOUSE_CAPTURE.load(Ordering::SeqCst);
+    if mouse {
+        execute!(stdout, DisableMouseCapture)?;
+    }
+    execute!(stdout, LeaveAlternateScreen, Show)?;
+    disable_raw_mode()?;
+    let result = f();
+    enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+    Ok(result)
+}
+
+// 恢复终端状态；幂等，忽略所有错误
+pub fn restore_terminal() {
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let mut stdout = io::stdout();
+    if MOUSE_CAPTURE.swap(false, Ordering::SeqCst) {
+        let _ = execute!(stdout, DisableMouseCapture);
+    }
+    let _ = execute!(stdout, LeaveAlternateScreen, Show);
+    let _ = disable_raw_mode();
+    let _ = stdout.flush();
+}
+
+// 是否处于 raw mode + 备用屏幕（界面是否已显示）
+pub fn is_active() -> bool {
+    TERMINAL_ACTIVE.load(Ordering::SeqCst)
+}
+
+// 是否有 panic 发生（包括后台 tokio 任务中的 panic）
+pub fn panicked() -> bool {
+    PANICKED.load(Ordering::SeqCst)
+}
+
+// 全局 panic 处理器：先恢复终端再输出 panic 信息。
+// tokio 会吞掉后台任务的 panic，但 hook 仍会被调用，因此同时记录标志让主循环退出
+pub fn install_panic_hook() {
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        PANICKED.store(true, Ordering::SeqCst);
+        restore_terminal();
+        original_hook(panic_info);
+    }));
+}
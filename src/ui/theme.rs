@@ -0,0 +1,244 @@
+use anyhow::{anyhow, Result};
+use ratatui::style::{Color, Modifier, Style};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::config::settings::ThemeSettings;
+use crate::t;
+
+// 内置主题名
+pub const THEMES: &[&str] = &["dark", "light", "high-contrast"];
+
+// 界面配色：各组件按用途取色，不直接写死颜色
+#[derive(Debug, Clone)]
+pub struct Theme {
+    // 边框与面板默认前景
+    pub border: Color,
+    // 正文（库名、表名、输入内容）
+    pub text: Color,
+    // 次要文字（注释、说明）
+    pub muted: Color,
+    // 更弱的提示（分隔、占位）
+    pub dim: Color,
+    // 表头、模式标签、状态消息
+    pub heading: Color,
+    // 当前库、签名等提示信息
+    pub info: Color,
+    // 状态栏中的用户
+    pub secondary: Color,
+    // 状态栏中的服务器版本
+    pub tertiary: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    // 选中项
+    pub selected_fg: Color,
+    pub selected_bg: Color,
+    // 查找匹配
+    pub match_fg: Color,
+    pub match_bg: Color,
+    // \watch 刷新后变化的单元格
+    pub changed_fg: Color,
+    pub changed_bg: Color,
+    // SQL 高亮
+    pub keyword: Color,
+    pub string: Color,
+    pub number: Color,
+}
+
+impl Theme {
+    // 默认：黑底绿字
+    pub fn dark() -> Self {
+        Self {
+            border: Color::Green,
+            text: Color::White,
+            muted: Color::Gray,
+            dim: Color::DarkGray,
+            heading: Color::Yellow,
+            info: Color::Cyan,
+            secondary: Color::Magenta,
+            tertiary: Color::Blue,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            selected_fg: Color::Black,
+            selected_bg: Color::Green,
+            match_fg: Color::Black,
+            match_bg: Color::Yellow,
+            changed_fg: Color::Black,
+            changed_bg: Color::LightMagenta,
+            keyword: Color::Cyan,
+            string: Color::Green,
+            number: Color::Yellow,
+        }
+    }
+
+    // 浅色背景的终端：避免黄色、白色等在白底上看不清的颜色
+    pub fn light() -> Self {
+        Self {
+            border: Color::Blue,
+            text: Color::Black,
+            muted: Color::DarkGray,
+            dim: Color::Gray,
+            heading: Color::Magenta,
+            info: Color::Blue,
+            secondary: Color::Magenta,
+            tertiary: Color::Blue,
+            success: Color::Green,
+            warning: Color::Indexed(130),
+            error: Color::Red,
+            selected_fg: Color::White,
+            selected_bg: Color::Blue,
+            match_fg: Color::Black,
+            match_bg: Color::LightYellow,
+            changed_fg: Color::Black,
+            changed_bg: Color::LightMagenta,
+            keyword: Color::Blue,
+            string: Color::Green,
+            number: Color::Magenta,
+        }
+    }
+
+    // 高对比度：全部使用亮色，选中项反白
+    pub fn high_contrast() -> Self {
+        Self {
+            border: Color::White,
+            text: Color::White,
+            muted: Color::White,
+            dim: Color::Gray,
+            heading: Color::LightYellow,
+            info: Color::LightCyan,
+            secondary: Color::LightMagenta,
+            tertiary: Color::LightBlue,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            selected_fg: Color::Black,
+            selected_bg: Color::White,
+            match_fg: Color::Black,
+            match_bg: Color::LightYellow,
+            changed_fg: Color::Black,
+            changed_bg: Color::LightMagenta,
+            keyword: Color::LightCyan,
+            string: Color::LightGreen,
+            number: Color::LightYellow,
+        }
+    }
+
+    // config.toml 的 [theme]：name 选择内置主题，其余键覆盖对应颜色
+    // （颜色名如 "blue"、"lightred"，256 色序号如 "130"，或 "#rrggbb"）
+    pub fn from_settings(settings: &ThemeSettings) -> Result<Self> {
+        let mut theme = match settings.name.as_str() {
+            "dark" => Self::dark(),
+            "light" => Self::light(),
+            "high-contrast" => Self::high_contrast(),
+            name => return Err(anyhow!(t!("theme.unknown", name, THEMES.join(" | ")))),
+        };
+        let mut errors = Vec::new();
+        for (key, value) in &settings.colors {
+            let Some(slot) = theme.slot(key) else {
+                errors.push(t!("theme.unknown_slot", key));
+                continue;
+            };
+            match Color::from_str(value) {
+                Ok(color) => *slot = color,
+                Err(_) => errors.push(t!("theme.bad_color", key, value)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(theme)
+        } else {
+            Err(anyhow!(errors.join("\n  - ")))
+        }
+    }
+
+    fn slot(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "border" => &mut self.border,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "dim" => &mut self.dim,
+            "heading" => &mut self.heading,
+            "info" => &mut self.info,
+            "secondary" => &mut self.secondary,
+            "tertiary" => &mut self.tertiary,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "selected_fg" => &mut self.selected_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "match_fg" => &mut self.match_fg,
+            "match_bg" => &mut self.match_bg,
+            "changed_fg" => &mut self.changed_fg,
+            "changed_bg" => &mut self.changed_bg,
+            "keyword" => &mut self.keyword,
+            "string" => &mut self.string,
+            "number" => &mut self.number,
+            _ => return None,
+        })
+    }
+
+    pub fn fg(&self, color: Color) -> Style {
+        Style::default().fg(color)
+    }
+
+    pub fn border(&self) -> Style {
+        self.fg(self.border)
+    }
+
+    pub fn text(&self) -> Style {
+        self.fg(self.text)
+    }
+
+    pub fn muted(&self) -> Style {
+        self.fg(self.muted)
+    }
+
+    pub fn dim(&self) -> Style {
+        self.fg(self.dim)
+    }
+
+    pub fn heading(&self) -> Style {
+        self.fg(self.heading).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn info(&self) -> Style {
+        self.fg(self.info)
+    }
+
+    pub fn success(&self) -> Style {
+        self.fg(self.success)
+    }
+
+    pub fn warning(&self) -> Style {
+        self.fg(self.warning)
+    }
+
+    pub fn error(&self) -> Style {
+        self.fg(self.error)
+    }
+
+    pub fn selected(&self) -> Style {
+        Style::default().fg(self.selected_fg).bg(self.selected_bg).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn matched(&self) -> Style {
+        Style::default().fg(self.match_fg).bg(self.match_bg)
+    }
+
+    pub fn changed(&self) -> Style {
+        Style::default().fg(self.changed_fg).bg(self.changed_bg)
+    }
+}
+
+static CURRENT: OnceLock<Theme> = OnceLock::new();
+
+// 启动时设置一次；之后的调用不生效
+pub fn init(theme: Theme) {
+    let _ = CURRENT.set(theme);
+}
+
+// 当前主题；未设置时为默认的深色主题
+pub fn current() -> &'static Theme {
+    CURRENT.get_or_init(Theme::dark)
+}
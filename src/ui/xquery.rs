@@ -0,0 +1,113 @@
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::db::new_adapter;
+use crate::ui::query_task::with_timeout;
+
+// \xquery 的汇总结果：按列名对齐后堆叠的行（首列为来源库）与失败的库
+pub struct XQueryReport {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub failures: Vec<String>,
+}
+
+enum XQueryEvent {
+    Progress(usize),
+    Finished(XQueryReport),
+}
+
+// 后台执行中的跨库查询；逐库执行，每个库完成后上报进度
+pub struct RunningXQuery {
+    pub query: String,
+    pub total: usize,
+    pub done: usize,
+    rx: UnboundedReceiver<XQueryEvent>,
+    task: JoinHandle<()>,
+}
+
+impl RunningXQuery {
+    // returns_rows 为 false 时各库只报告受影响的行数
+    pub fn spawn(config: Config, databases: Vec<String>, query: String, returns_rows: bool) -> Self {
+        let (tx, rx) = unbounded_channel();
+        let total = databases.len();
+        let statement = query.clone();
+        let task = tokio::spawn(async move {
+            let report = run(&config, &databases, &statement, returns_rows, |done| {
+                let _ = tx.send(XQueryEvent::Progress(done));
+            })
+            .await;
+            let _ = tx.send(XQueryEvent::Finished(report));
+        });
+        Self { query, total, done: 0, rx, task }
+    }
+
+    // 收取进度；完成时返回结果
+    pub fn poll(&mut self) -> Option<XQueryReport> {
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                XQueryEvent::Progress(done) => self.done = done,
+                XQueryEvent::Finished(report) => return Some(report),
+            }
+        }
+        None
+    }
+
+    // 停止执行；尚未轮到的库不再执行，正在执行的连接随任务丢弃而关闭
+    pub fn cancel(self) {
+        self.task.abort();
+    }
+}
+
+// 每个目标库单独建立连接，不影响当前会话；连接与执行一起受语句超时限制，出错的库记入失败继续下一个
+async fn run(config: &Config, databases: &[String], query: &str, returns_rows: bool, progress: impl Fn(usize)) -> XQueryReport {
+    let timeout = config.statement_timeout();
+    let mut headers: Vec<String> = vec!["source".to_string()];
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut failures: Vec<String> = Vec::new();
+    for (i, database) in databases.iter().enumerate() {
+        let mut target = config.clone();
+        target.database = Some(database.clone());
+        let result = with_timeout(timeout, async {
+            let db = new_adapter(&target).await?;
+            if returns_rows {
+                db.execute_query_raw(query).await
+            } else {
+                let result = db.execute_non_query(query).await?;
+                Ok((vec!["rows_affected".to_string()], vec![vec![result.rows_affected.to_string()]]))
+            }
+        })
+        .await;
+        progress(i + 1);
+        let (result_headers, result_rows): (Vec<String>, Vec<Vec<String>>) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                failures.push(format!("{}: {}", database, e));
+                continue;
+            }
+        };
+        // 各库列不完全一致时按列名合并，缺失的列留空
+        let positions: Vec<usize> = result_headers
+            .iter()
+            .map(|h| match headers.iter().position(|existing| existing == h) {
+                Some(i) => i,
+                None => {
+                    headers.push(h.clone());
+                    headers.len() - 1
+                }
+            })
+            .collect();
+        for row in result_rows {
+            let mut stacked = vec![String::new(); headers.len()];
+            stacked[0] = database.clone();
+            for (value, &pos) in row.into_iter().zip(&positions) {
+                stacked[pos] = value;
+            }
+            rows.push(stacked);
+        }
+    }
+    for row in &mut rows {
+        row.resize(headers.len(), String::new());
+    }
+    XQueryReport { headers, rows, failures }
+}